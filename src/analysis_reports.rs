@@ -1,3 +1,5 @@
+use crate::grammar::HiddenVerb;
+use crate::span::Span;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -11,6 +13,7 @@ pub struct StickySentence {
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +58,7 @@ pub struct TransitionFound {
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +76,7 @@ pub struct WordOccurrence {
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,6 +203,7 @@ pub struct ComplexParagraph {
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,7 +217,8 @@ pub struct ComplexParagraphsReport {
 pub struct StyleReport {
     pub passive_voice_count: usize,
     pub adverb_count: usize,
-    pub hidden_verbs: Vec<String>,
+    /// Weak verb and nominalization phrases, in text order: "make a decision"
+    pub hidden_verbs: Vec<HiddenVerb>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -227,13 +234,14 @@ pub struct FullAnalysisReport {
     pub transitions: TransitionReport,
     pub overused_words: OverusedWordsReport,
     pub repeated_phrases: RepeatedPhrasesReport,
-    pub echoes: EchoesReport,
-    pub sensory: SensoryReport,
+    pub echoes: Option<EchoesReport>,            // None when echo_detection is disabled
+    pub sensory: Option<SensoryReport>,          // None when sensory_analysis is disabled
     pub diction: DictionReport,
-    pub cliches: ClichesReport,
-    pub consistency: ConsistencyReport,
+    pub cliches: Option<ClichesReport>,          // None when cliche_detection is disabled
+    pub consistency: Option<ConsistencyReport>,  // None when consistency_check is disabled
     pub acronyms: AcronymReport,
     pub conjunction_starts: ConjunctionStartsReport,
-    pub business_jargon: BusinessJargonReport,
+    pub business_jargon: Option<BusinessJargonReport>,  // None when jargon_detection is disabled
     pub complex_paragraphs: ComplexParagraphsReport,
+    pub skipped_passes: Vec<String>,  // Names of the report fields disabled by feature toggles
 }
@@ -7,8 +7,13 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
-use Rust_Grammar::{Config, TextAnalyzer};
-use Rust_Grammar::grammar::{PassiveVoiceMatch, GrammarIssue};
+use Rust_Grammar::{Config, ReadabilityBreakdown, TextAnalyzer, TextCounts};
+use Rust_Grammar::error::Deadline;
+use Rust_Grammar::readability::Difficulty;
+use Rust_Grammar::grammar::{HiddenVerbDetector, PassiveVoiceMatch, GrammarIssue, PosTagger, SentenceSpan, SentenceSplitter, Tag, Token};
+use Rust_Grammar::fix::{match_case, Applicability, Fix};
+use Rust_Grammar::span::{Lowercased, OffsetIndex};
+use Rust_Grammar::suppression::{Suppressions, HIDDEN_VERB};
 use tower_http::cors::CorsLayer;
 
 #[tokio::main]
@@ -42,6 +47,24 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+/// Default config with result caching, so re-submitted texts are served from memory
+fn analysis_config() -> Config {
+    let mut config = Config::default();
+    config.analysis.cache_results = true;
+    config
+}
+
+// IDs of the server's own findings, usable in suppression directives
+const ADVERB: &str = "adverb";
+const EMOTION_TELL: &str = "emotion-tell";
+const ING_START: &str = "ing-start";
+const WEAK_CONSTRUCTION: &str = "weak-construction";
+const INCLUSIVE_LANGUAGE: &str = "inclusive-language";
+const FILLER_WORD: &str = "filler-word";
+const COMPLEX_SENTENCE: &str = "complex-sentence";
+const REPEATED_START: &str = "repeated-start";
+const STYLE_GUIDE: &str = "style-guide";
+
 // Request body structure
 #[derive(Debug, Deserialize)]
 struct AnalyzeRequest {
@@ -128,6 +151,8 @@ struct AnalysisIssue {
 #[derive(Debug, Serialize)]
 struct Suggestions {
     recommendation: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
 }
 
 // Comprehensive scores (matching the user's format)
@@ -347,6 +372,8 @@ struct AnalyzeResponse {
     scores: ComprehensiveScores,
     issues: Vec<AnalysisIssue>,
     summary: AnalysisSummary,
+    #[serde(rename = "unusedSuppressions")]
+    unused_suppressions: Vec<UnusedSuppression>,
 }
 
 #[derive(Debug, Serialize)]
@@ -358,6 +385,17 @@ struct AnalysisSummary {
     character_count: usize,
 }
 
+// A suppression directive that silences no finding
+#[derive(Debug, Serialize)]
+struct UnusedSuppression {
+    directive: String,  // e.g. "grammar-disable cliche"
+    line: usize,
+    start: usize,
+    end: usize,
+    #[serde(rename = "paragraphKey", skip_serializing_if = "Option::is_none")]
+    paragraph_key: Option<String>,
+}
+
 // API handler
 async fn analyze_text(
     Json(payload): Json<AnalyzeRequest>,
@@ -367,26 +405,26 @@ async fn analyze_text(
     }
 
     // Create analyzer with default config
-    let config = Config::default();
+    let config = analysis_config();
     let analyzer = TextAnalyzer::new(payload.text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Run full analysis
     let full_report = analyzer
         .generate_full_report()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let passive_voice = analyzer
         .detect_passive_voice()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let grammar = analyzer
         .check_grammar()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let stats = analyzer.statistics();
     let readability = analyzer.readability_metrics()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Calculate comprehensive scores
     let scores = calculate_comprehensive_scores(
@@ -396,11 +434,19 @@ async fn analyze_text(
         &stats,
         &readability,
         &payload.text,
+        analyzer.suppressions(),
     );
 
     // Convert all issues to the requested format
     let issues = convert_to_issues(&full_report, &passive_voice, &grammar, &payload.text);
 
+    // Directives silencing nothing, the server's own findings included
+    let unused_suppressions = if analyzer.suppressions().is_empty() {
+        Vec::new()
+    } else {
+        unused_directives(&analyzer, &server_findings(&payload.text), None)?
+    };
+
     let response = AnalyzeResponse {
         scores,
         issues,
@@ -408,12 +454,13 @@ async fn analyze_text(
             total_issues: passive_voice.len() + grammar.len() + 
                          full_report.sticky_sentences.sticky_sentences.len() +
                          full_report.overused_words.overused_words.len() +
-                         full_report.cliches.cliches.len(),
+                         full_report.cliches.as_ref().map_or(0, |c| c.cliches.len()),
             word_count: stats.word_count,
             sentence_count: stats.sentence_count,
             paragraph_count: stats.paragraph_count,
             character_count: stats.character_count,
         },
+        unused_suppressions,
     };
 
     Ok(Json(response))
@@ -427,26 +474,26 @@ async fn get_scores_only(Json(payload): Json<AnalyzeRequest>) -> Result<Json<Sco
     }
 
     // Create analyzer with default config
-    let config = Config::default();
+    let config = analysis_config();
     let analyzer = TextAnalyzer::new(payload.text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Run full analysis
     let full_report = analyzer
         .generate_full_report()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let passive_voice = analyzer
         .detect_passive_voice()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let grammar = analyzer
         .check_grammar()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let stats = analyzer.statistics();
     let readability = analyzer.readability_metrics()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Calculate comprehensive scores with user-friendly messages
     let scores = create_user_friendly_scores(
@@ -456,13 +503,14 @@ async fn get_scores_only(Json(payload): Json<AnalyzeRequest>) -> Result<Json<Sco
         &stats,
         &readability,
         &payload.text,
+        analyzer.suppressions(),
     );
 
     let response = ScoreOnlyResponse {
         scores,
         word_count: stats.word_count,
         sentence_count: stats.sentence_count,
-        complex_words_count: count_complex_words(&payload.text),
+        complex_words_count: analyzer.sentence_counts().iter().sum::<TextCounts>().polysyllables,
     };
 
     Ok(Json(response))
@@ -493,7 +541,7 @@ async fn get_sentence_length(Json(payload): Json<SentenceLengthRequest>) -> Resu
     let num_characters = character_array.count();
 
     // Split into sentences for overall analysis
-    let all_sentences = split_into_sentences(&combined_text);
+    let all_sentences: Vec<String> = sentence_spans(&combined_text).into_iter().map(|s| s.text).collect();
     let sentence_count = all_sentences.len().max(1);
 
     // Calculate average sentence length
@@ -559,11 +607,9 @@ async fn get_sentence_length(Json(payload): Json<SentenceLengthRequest>) -> Resu
     let mut individual_sentence_lengths = Vec::new();
 
     for paragraph in &payload.data {
-        // Split sentences and track positions manually
-        let text = &paragraph.text;
-        let sentences = split_sentences_with_positions(text);
-
-        for (sentence_text, start, end) in sentences {
+        for sentence in sentence_spans(&paragraph.text) {
+            let sentence_text = sentence.text;
+            let (start, end) = (sentence.span.char_start, sentence.span.char_end);
             let word_count = sentence_text.split_whitespace().count();
             
             // Use character-based length calculation
@@ -622,21 +668,51 @@ struct ReadabilityResponse {
     flesch_reading_ease: f64,
     #[serde(rename = "fleschKincaidGrade")]
     flesch_kincaid_grade: f64,
+    #[serde(rename = "smogIndex")]
+    smog_index: Option<f64>,
+    #[serde(rename = "gunningFog")]
+    gunning_fog: f64,
     #[serde(rename = "colemanLiau")]
     coleman_liau: f64,
     #[serde(rename = "automatedReadabilityIndex")]
     automated_readability_index: f64,
+    #[serde(rename = "daleChall")]
+    dale_chall: f64,
+    spache: f64,
+    #[serde(rename = "linsearWrite")]
+    linsear_write: f64,
+    forcast: f64,
+    #[serde(rename = "consensusGrade")]
+    consensus_grade: f64,
     #[serde(rename = "difficultParagraphs")]
     difficult_paragraphs: Vec<DifficultParagraph>,
+    #[serde(rename = "hardestSentences")]
+    hardest_sentences: Vec<DifficultSentence>,
 }
 
 #[derive(Debug, Serialize)]
 struct DifficultParagraph {
-    difficulty: String,  // "very hard", "slightly difficult", etc.
+    difficulty: String,  // "hard" or "very hard"
     start: usize,  // Relative to paragraph
     end: usize,    // Relative to paragraph
     string: String,
     excerpt: String,  // First ~50 chars for display
+    #[serde(rename = "gradeLevel")]
+    grade_level: f64,
+    #[serde(rename = "paragraphKey")]
+    paragraph_key: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DifficultSentence {
+    difficulty: String,  // "hard" or "very hard"
+    start: usize,  // Relative to paragraph
+    end: usize,    // Relative to paragraph
+    string: String,
+    #[serde(rename = "gradeLevel")]
+    grade_level: f64,
+    #[serde(rename = "wordCount")]
+    word_count: usize,
     #[serde(rename = "paragraphKey")]
     paragraph_key: String,
 }
@@ -703,6 +779,8 @@ struct PassiveVoiceResponse {
     style_guide_items_message: String,
     #[serde(rename = "styleGuideItems")]
     style_guide_items: Vec<EnhancementOccurrence>,
+    #[serde(rename = "unusedSuppressions")]
+    unused_suppressions: Vec<UnusedSuppression>,
 }
 
 #[derive(Debug, Serialize)]
@@ -748,6 +826,8 @@ struct OccurrenceDetail {
     #[serde(rename = "paragraphKey")]
     paragraph_key: String,
     report: String,  // Which report this belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<Fix>,
 }
 
 #[derive(Debug, Serialize)]
@@ -785,9 +865,17 @@ async fn get_readability(
             message: "No content to analyze.".to_string(),
             flesch_reading_ease: 0.0,
             flesch_kincaid_grade: 0.0,
+            smog_index: None,
+            gunning_fog: 0.0,
             coleman_liau: 0.0,
             automated_readability_index: 0.0,
+            dale_chall: 0.0,
+            spache: 0.0,
+            linsear_write: 0.0,
+            forcast: 0.0,
+            consensus_grade: 0.0,
             difficult_paragraphs: Vec::new(),
+            hardest_sentences: Vec::new(),
         }));
     }
 
@@ -804,27 +892,36 @@ async fn get_readability(
             message: "No content to analyze.".to_string(),
             flesch_reading_ease: 0.0,
             flesch_kincaid_grade: 0.0,
+            smog_index: None,
+            gunning_fog: 0.0,
             coleman_liau: 0.0,
             automated_readability_index: 0.0,
+            dale_chall: 0.0,
+            spache: 0.0,
+            linsear_write: 0.0,
+            forcast: 0.0,
+            consensus_grade: 0.0,
             difficult_paragraphs: Vec::new(),
+            hardest_sentences: Vec::new(),
         }));
     }
 
-    let config = Config::default();
+    let config = analysis_config();
     let analyzer = TextAnalyzer::new(combined_text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let stats = analyzer.statistics();
     let readability = analyzer.readability_metrics()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Calculate estimated reading time (assuming 200 words per minute)
     let minutes = stats.word_count / 200;
     let seconds = (stats.word_count % 200) * 60 / 200;
     let estimated_reading_time = format!("{} min, {} sec", minutes, seconds);
 
-    // Analyze each paragraph for difficulty
+    // Grade each paragraph and sentence, ranking the hard sentences across paragraphs
     let mut difficult_paragraphs = Vec::new();
+    let mut hardest_sentences = Vec::new();
 
     for paragraph in &payload.data {
         let text = &paragraph.text;
@@ -833,54 +930,42 @@ async fn get_readability(
             continue;
         }
 
-        // Simple heuristic: paragraphs with average word length > 6 and sentence length > 25
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let avg_word_len = if !words.is_empty() {
-            words.iter().map(|w| w.len()).sum::<usize>() as f64 / words.len() as f64
-        } else {
-            0.0
-        };
-
-        let sentences = text.split(&['.', '!', '?'][..]).filter(|s| !s.trim().is_empty()).count().max(1);
-        let avg_sentence_len = words.len() as f64 / sentences as f64;
-
-        let difficulty = if avg_word_len > 6.5 && avg_sentence_len > 30.0 {
-            Some("very hard")
-        } else if avg_word_len > 5.5 && avg_sentence_len > 25.0 {
-            Some("hard")
-        } else if avg_sentence_len > 20.0 {
-            Some("slightly difficult")
-        } else {
-            None
-        };
+        let breakdown = ReadabilityBreakdown::new(text, &sentence_spans(text), &analyzer.config().readability);
 
-        if let Some(diff) = difficulty {
-            // Use character-based length
-            let character_array = text.chars();
-            let text_char_len = character_array.count();
-            
-            let excerpt = if text_char_len > 50 {
-                text.chars().take(50).collect::<String>() + "..."
+        for passage in breakdown.paragraphs.iter().filter(|p| p.difficulty != Difficulty::Readable) {
+            let excerpt = if passage.text.chars().count() > 50 {
+                passage.text.chars().take(50).collect::<String>() + "..."
             } else {
-                text.to_string()
+                passage.text.clone()
             };
 
             difficult_paragraphs.push(DifficultParagraph {
-                difficulty: diff.to_string(),
-                start: 0,
-                end: text_char_len,
-                string: text.to_string(),
+                difficulty: difficulty_label(passage.difficulty).to_string(),
+                start: passage.span.char_start,
+                end: passage.span.char_end,
+                string: passage.text.clone(),
                 excerpt,
+                grade_level: passage.grade_level,
                 paragraph_key: paragraph.key.clone(),
             });
         }
+
+        hardest_sentences.extend(breakdown.hardest().map(|sentence| DifficultSentence {
+            difficulty: difficulty_label(sentence.difficulty).to_string(),
+            start: sentence.span.char_start,
+            end: sentence.span.char_end,
+            string: sentence.text.clone(),
+            grade_level: sentence.grade_level,
+            word_count: sentence.word_count,
+            paragraph_key: paragraph.key.clone(),
+        }));
     }
 
-    // Round scores to 2 decimal places
-    let flesch_ease = (readability.flesch_reading_ease * 100.0).round() / 100.0;
-    let flesch_grade = (readability.flesch_kincaid_grade * 100.0).round() / 100.0;
-    let coleman = (readability.smog_index.unwrap_or(0.0) * 100.0).round() / 100.0;
-    let ari = (readability.avg_words_per_sentence * 100.0).round() / 100.0;
+    hardest_sentences.sort_by(|a, b| {
+        b.grade_level.total_cmp(&a.grade_level).then(b.word_count.cmp(&a.word_count))
+    });
+
+    let flesch_ease = readability.flesch_reading_ease;
 
     // Generate intelligent message based on Flesch Reading Ease score
     let message = if flesch_ease >= 90.0 {
@@ -902,11 +987,19 @@ async fn get_readability(
     let response = ReadabilityResponse {
         estimated_reading_time,
         message,
-        flesch_reading_ease: flesch_ease,
-        flesch_kincaid_grade: flesch_grade,
-        coleman_liau: coleman,
-        automated_readability_index: ari,
+        flesch_reading_ease: readability.flesch_reading_ease,
+        flesch_kincaid_grade: readability.flesch_kincaid_grade,
+        smog_index: readability.smog_index,
+        gunning_fog: readability.gunning_fog,
+        coleman_liau: readability.coleman_liau,
+        automated_readability_index: readability.automated_readability_index,
+        dale_chall: readability.dale_chall,
+        spache: readability.spache,
+        linsear_write: readability.linsear_write,
+        forcast: readability.forcast,
+        consensus_grade: readability.consensus_grade,
         difficult_paragraphs,
+        hardest_sentences,
     };
 
     Ok(Json(response))
@@ -950,6 +1043,7 @@ async fn get_passive_voice(
             repeated_sentence_starts: Vec::new(),
             style_guide_items_message: "No content to analyze.".to_string(),
             style_guide_items: Vec::new(),
+            unused_suppressions: Vec::new(),
         }));
     }
 
@@ -992,20 +1086,30 @@ async fn get_passive_voice(
             repeated_sentence_starts: Vec::new(),
             style_guide_items_message: "No content to analyze.".to_string(),
             style_guide_items: Vec::new(),
+            unused_suppressions: Vec::new(),
         }));
     }
 
-    let config = Config::default();
+    // One time budget for the analyzers of every paragraph
+    let config = analysis_config();
+    let deadline = Deadline::after(config.validation.timeout_seconds);
     let analyzer = TextAnalyzer::new(combined_text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?
+        .with_deadline(deadline);
 
     let stats = analyzer.statistics();
     let sentence_count = stats.sentence_count.max(1);
 
-    // Process each paragraph to find passive voice with paragraph-relative positions
-    let mut all_passive_verbs: Vec<(String, String, usize, usize, String)> = Vec::new(); // (verb, key, start, end, string)
+    // The server's own findings are silenced by the directives of their paragraph
+    let mut paragraphs: Vec<ParagraphFindings> = payload.data.iter().map(ParagraphFindings::new).collect();
 
-    for paragraph in &payload.data {
+    // Process each paragraph to find passive voice with paragraph-relative positions,
+    // grouped by verb phrase
+    let mut passive_verb_map: std::collections::HashMap<String, Vec<OccurrenceDetail>> =
+        std::collections::HashMap::new();
+
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
         
         if text.trim().is_empty() {
@@ -1013,63 +1117,34 @@ async fn get_passive_voice(
         }
 
         // Analyze this paragraph
-        let para_config = Config::default();
+        let para_config = analysis_config();
         let para_analyzer = TextAnalyzer::new(text.clone(), para_config)
-            .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+            .map_err(ApiError::from)?
+            .with_deadline(deadline);
 
         let passive_matches = para_analyzer.detect_passive_voice()
-            .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+            .map_err(ApiError::from)?;
 
-        // Convert to character-based positions relative to paragraph
+        // Report character-based positions relative to paragraph
         for pv in passive_matches {
-            // Find nearest valid character boundaries
-            let safe_start = find_char_boundary(text, pv.start_index);
-            let safe_end = find_char_boundary(text, pv.end_index);
-            
-            // Convert byte positions to character positions
-            let char_start = text[..safe_start].chars().count();
-            let char_end = text[..safe_end].chars().count();
-            let text_chars: Vec<char> = text.chars().collect();
-            
-            // Safely extract substring
-            let string: String = if char_end <= text_chars.len() {
-                text_chars[char_start..char_end].iter().collect()
-            } else {
-                text_chars[char_start..].iter().collect()
-            };
-
-            all_passive_verbs.push((
-                pv.text.clone(),
-                paragraph.key.clone(),
-                char_start,
-                char_end,
-                string,
-            ));
+            passive_verb_map.entry(pv.text)
+                .or_insert_with(Vec::new)
+                .push(OccurrenceDetail {
+                    start: pv.span.char_start,
+                    end: pv.span.char_end,
+                    string: text[pv.span.byte_range()].to_string(),
+                    paragraph_key: paragraph.key.clone(),
+                    report: "passiveVerbs".to_string(),
+                    fix: pv.fix,
+                });
         }
-    }
-
-    // Group by verb phrase
-    let mut passive_verb_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
-        std::collections::HashMap::new();
-    
-    for (verb, key, start, end, string) in all_passive_verbs {
-        passive_verb_map.entry(verb).or_insert_with(Vec::new).push((key, start, end, string));
+        p.analyzer = Some(para_analyzer);
     }
 
     let mut passive_verbs = Vec::new();
     let total_passive_count = passive_verb_map.values().map(|v| v.len()).sum::<usize>();
 
-    for (verb, occurrences_list) in passive_verb_map {
-        let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail {
-                start,
-                end,
-                string,
-                paragraph_key: key,
-                report: "passiveVerbs".to_string(),
-            }
-        }).collect();
-
+    for (verb, occurrences) in passive_verb_map {
         passive_verbs.push(PassiveVerbOccurrence {
             verb,
             count: occurrences.len(),
@@ -1082,61 +1157,33 @@ async fn get_passive_voice(
 
     // Get full report for additional analysis
     let full_report = analyzer.generate_full_report()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // ========== 1. HIDDEN VERBS (Nominalizations) ==========
-    let hidden_verb_patterns = vec![
-        ("make a decision", "decide"), ("take action", "act"),
-        ("give consideration", "consider"), ("make an assumption", "assume"),
-        ("have a discussion", "discuss"), ("make a payment", "pay"),
-        ("The illusion of", "illusion"), ("The idea of", "idea"),
-        ("The concept of", "concept"), ("The notion of", "notion"),
-        ("implementation of", "implement"), ("creation of", "create"),
-        ("utilization of", "utilize"), ("examination of", "examine"),
-    ];
-    
-    let mut hidden_verbs_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
+    let hidden_verb_detector = HiddenVerbDetector::new();
+    let mut hidden_verbs_map: std::collections::HashMap<String, Vec<OccurrenceDetail>> =
         std::collections::HashMap::new();
-    
-    for paragraph in &payload.data {
-        let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
-        
-        for (pattern, _replacement) in &hidden_verb_patterns {
-            let pattern_lower = pattern.to_lowercase();
-            let mut start_pos = 0;
-            
-            while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
-                let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
-                
-                let safe_start = find_char_boundary(text, actual_pos);
-                let safe_end = find_char_boundary(text, end_pos);
-                
-                let char_start = text[..safe_start].chars().count();
-                let char_end = text[..safe_end].chars().count();
-                let text_chars: Vec<char> = text.chars().collect();
-                
-                let string: String = if char_end <= text_chars.len() {
-                    text_chars[char_start..char_end].iter().collect()
-                } else {
-                    text_chars[char_start..].iter().collect()
-                };
-                
-                hidden_verbs_map.entry(pattern.to_string())
-                    .or_insert_with(Vec::new)
-                    .push((paragraph.key.clone(), char_start, char_end, string));
-                
-                start_pos = end_pos;
+
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
+        for hv in hidden_verb_detector.detect(&paragraph.text) {
+            if !p.report(HIDDEN_VERB, hv.span.byte_start) {
+                continue;
             }
+            hidden_verbs_map.entry(hv.text.to_lowercase())
+                .or_insert_with(Vec::new)
+                .push(OccurrenceDetail {
+                    start: hv.span.char_start,
+                    end: hv.span.char_end,
+                    string: hv.text,
+                    paragraph_key: paragraph.key.clone(),
+                    report: "hiddenVerbs".to_string(),
+                    fix: Some(hv.fix),
+                });
         }
     }
-    
-    let hidden_verbs: Vec<HiddenVerbOccurrence> = hidden_verbs_map.into_iter().map(|(phrase, occurrences_list)| {
-        let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "hiddenVerbs".to_string() }
-        }).collect();
-        
+
+    let hidden_verbs: Vec<HiddenVerbOccurrence> = hidden_verbs_map.into_iter().map(|(phrase, occurrences)| {
         HiddenVerbOccurrence {
             phrase,
             count: occurrences.len(),
@@ -1159,53 +1206,29 @@ async fn get_passive_voice(
     let mut adverbs_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    let tagger = PosTagger::new();
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let index = OffsetIndex::new(text);
         
-        for pattern in &adverb_patterns {
-            let pattern_lower = pattern.to_lowercase();
-            let mut start_pos = 0;
-            
-            while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
-                let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
-                
-                // Check if it's a whole word (not part of another word)
-                let is_word_start = actual_pos == 0 || 
-                    text_lower.chars().nth(actual_pos - 1).map_or(false, |c| !c.is_alphabetic());
-                let is_word_end = end_pos >= text_lower.chars().count() ||  // ✅ FIXED: Use .chars().count()
-                    text_lower.chars().nth(end_pos).map_or(false, |c| !c.is_alphabetic());
-                
-                if is_word_start && is_word_end {
-                    let safe_start = find_char_boundary(text, actual_pos);
-                    let safe_end = find_char_boundary(text, end_pos);
-                    
-                    // Convert to character positions
-                    let char_start = text[..safe_start].chars().count();
-                    let char_end = text[..safe_end].chars().count();
-                    let text_chars: Vec<char> = text.chars().collect();
-                    
-                    // Extract the actual string
-                    let string: String = if char_end <= text_chars.len() {
-                        text_chars[char_start..char_end].iter().collect()
-                    } else {
-                        text_chars[char_start..].iter().collect()
-                    };
-                    
-                    adverbs_map.entry(pattern_lower.clone())
-                        .or_insert_with(Vec::new)
-                        .push((paragraph.key.clone(), char_start, char_end, string));
-                }
+        // Only words used as adverbs: "a pretty garden" is left alone
+        for token in tagger.tag(text) {
+            if token.tag == Tag::Adverb && adverb_patterns.contains(&token.lower.as_str())
+                && p.report(ADVERB, token.start)
+            {
+                let (char_start, char_end, string) = char_occurrence(&index, text, token.start, token.end);
                 
-                start_pos = actual_pos + 1;
+                adverbs_map.entry(token.lower)
+                    .or_insert_with(Vec::new)
+                    .push((paragraph.key.clone(), char_start, char_end, string));
             }
         }
     }
     
     let adverbs_list: Vec<AdverbOccurrence> = adverbs_map.into_iter().map(|(adverb, occurrences_list)| {
         let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "adverbs".to_string() }
+            OccurrenceDetail { start, end, string, paragraph_key: key, report: "adverbs".to_string(), fix: None }
         }).collect();
         
         AdverbOccurrence {
@@ -1229,9 +1252,12 @@ async fn get_passive_voice(
     let mut readability_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let lower = Lowercased::new(text);
+        let text_lower = &lower.text;
+        let index = OffsetIndex::new(text);
         
         for pattern in &readability_patterns {
             let pattern_lower = pattern.to_lowercase();
@@ -1239,24 +1265,16 @@ async fn get_passive_voice(
             
             while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
                 let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
+                let end_pos = actual_pos + pattern_lower.len();
                 
-                let safe_start = find_char_boundary(text, actual_pos);
-                let safe_end = find_char_boundary(text, end_pos);
-                
-                let char_start = text[..safe_start].chars().count();
-                let char_end = text[..safe_end].chars().count();
-                let text_chars: Vec<char> = text.chars().collect();
-                
-                let string: String = if char_end <= text_chars.len() {
-                    text_chars[char_start..char_end].iter().collect()
-                } else {
-                    text_chars[char_start..].iter().collect()
-                };
-                
-                readability_map.entry(pattern.to_string())
-                    .or_insert_with(Vec::new)
-                    .push((paragraph.key.clone(), char_start, char_end, string));
+                if p.report(WEAK_CONSTRUCTION, lower.original(actual_pos)) {
+                    let (char_start, char_end, string) =
+                        char_occurrence(&index, text, lower.original(actual_pos), lower.original(end_pos));
+
+                    readability_map.entry(pattern.to_string())
+                        .or_insert_with(Vec::new)
+                        .push((paragraph.key.clone(), char_start, char_end, string));
+                }
                 
                 start_pos = end_pos;
             }
@@ -1265,7 +1283,7 @@ async fn get_passive_voice(
     
     let readability_enhancements: Vec<EnhancementOccurrence> = readability_map.into_iter().map(|(phrase, occurrences_list)| {
         let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "readabilityEnhancements".to_string() }
+            OccurrenceDetail { start, end, string, paragraph_key: key, report: "readabilityEnhancements".to_string(), fix: None }
         }).collect();
         
         EnhancementOccurrence {
@@ -1285,48 +1303,54 @@ async fn get_passive_voice(
         ("chairman", "chairperson"), ("businessman", "businessperson"),
     ];
     
-    let mut inclusive_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
+    let mut inclusive_map: std::collections::HashMap<String, Vec<OccurrenceDetail>> =
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let lower = Lowercased::new(text);
+        let text_lower = &lower.text;
+        let index = OffsetIndex::new(text);
         
-        for (pattern, _suggestion) in &inclusive_patterns {
+        for (pattern, suggestion) in &inclusive_patterns {
             let pattern_lower = pattern.to_lowercase();
             let mut start_pos = 0;
             
             while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
                 let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
+                let end_pos = actual_pos + pattern_lower.len();
                 
-                let safe_start = find_char_boundary(text, actual_pos);
-                let safe_end = find_char_boundary(text, end_pos);
-                
-                let char_start = text[..safe_start].chars().count();
-                let char_end = text[..safe_end].chars().count();
-                let text_chars: Vec<char> = text.chars().collect();
-                
-                let string: String = if char_end <= text_chars.len() {
-                    text_chars[char_start..char_end].iter().collect()
-                } else {
-                    text_chars[char_start..].iter().collect()
-                };
+                let (byte_start, byte_end) = (lower.original(actual_pos), lower.original(end_pos));
+                if !p.report(INCLUSIVE_LANGUAGE, byte_start) {
+                    start_pos = end_pos;
+                    continue;
+                }
+                let (char_start, char_end, string) = char_occurrence(&index, text, byte_start, byte_end);
+                let fix = Fix::replace(
+                    format!("Replace with \"{}\"", suggestion),
+                    index.span(byte_start, byte_end),
+                    match_case(suggestion, &string),
+                    Applicability::MaybeIncorrect,
+                );
                 
                 inclusive_map.entry(pattern.to_string())
                     .or_insert_with(Vec::new)
-                    .push((paragraph.key.clone(), char_start, char_end, string));
+                    .push(OccurrenceDetail {
+                        start: char_start,
+                        end: char_end,
+                        string,
+                        paragraph_key: paragraph.key.clone(),
+                        report: "inclusiveLanguage".to_string(),
+                        fix: Some(fix),
+                    });
                 
                 start_pos = end_pos;
             }
         }
     }
     
-    let inclusive_language_improvements: Vec<EnhancementOccurrence> = inclusive_map.into_iter().map(|(phrase, occurrences_list)| {
-        let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "inclusiveLanguage".to_string() }
-        }).collect();
-        
+    let inclusive_language_improvements: Vec<EnhancementOccurrence> = inclusive_map.into_iter().map(|(phrase, occurrences)| {
         EnhancementOccurrence {
             phrase,
             count: occurrences.len(),
@@ -1344,9 +1368,12 @@ async fn get_passive_voice(
     let mut emotion_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let lower = Lowercased::new(text);
+        let text_lower = &lower.text;
+        let index = OffsetIndex::new(text);
         
         for pattern in &emotion_tell_words {
             let pattern_lower = pattern.to_lowercase();
@@ -1354,29 +1381,17 @@ async fn get_passive_voice(
             
             while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
                 let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
+                let end_pos = actual_pos + pattern_lower.len();
                 
                 // Check if it's a whole word (not part of another word)
-                let is_word_start = actual_pos == 0 || 
-                    text_lower.chars().nth(actual_pos - 1).map_or(false, |c| !c.is_alphabetic());
-                let is_word_end = end_pos >= text_lower.chars().count() ||  // ✅ FIXED: Use .chars().count()
-                    text_lower.chars().nth(end_pos).map_or(false, |c| !c.is_alphabetic());
+                let is_word_start = text_lower[..actual_pos].chars().next_back()
+                    .map_or(true, |c| !c.is_alphabetic());
+                let is_word_end = text_lower[end_pos..].chars().next()
+                    .map_or(true, |c| !c.is_alphabetic());
                 
-                if is_word_start && is_word_end {
-                    let safe_start = find_char_boundary(text, actual_pos);
-                    let safe_end = find_char_boundary(text, end_pos);
-                    
-                    // Convert to character positions
-                    let char_start = text[..safe_start].chars().count();
-                    let char_end = text[..safe_end].chars().count();
-                    let text_chars: Vec<char> = text.chars().collect();
-                    
-                    // Extract the actual string
-                    let string: String = if char_end <= text_chars.len() {
-                        text_chars[char_start..char_end].iter().collect()
-                    } else {
-                        text_chars[char_start..].iter().collect()
-                    };
+                if is_word_start && is_word_end && p.report(EMOTION_TELL, lower.original(actual_pos)) {
+                    let (char_start, char_end, string) =
+                        char_occurrence(&index, text, lower.original(actual_pos), lower.original(end_pos));
                     
                     emotion_map.entry(pattern_lower.clone())
                         .or_insert_with(Vec::new)
@@ -1390,7 +1405,7 @@ async fn get_passive_voice(
     
     let emotion_tells: Vec<EnhancementOccurrence> = emotion_map.into_iter().map(|(phrase, occurrences_list)| {
         let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "emotionTells".to_string() }
+            OccurrenceDetail { start, end, string, paragraph_key: key, report: "emotionTells".to_string(), fix: None }
         }).collect();
         
         EnhancementOccurrence {
@@ -1409,9 +1424,12 @@ async fn get_passive_voice(
     let mut style_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let lower = Lowercased::new(text);
+        let text_lower = &lower.text;
+        let index = OffsetIndex::new(text);
         
         for pattern in &style_patterns {
             let pattern_lower = pattern.to_lowercase();
@@ -1419,31 +1437,17 @@ async fn get_passive_voice(
             
             while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
                 let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.len();
+                let end_pos = actual_pos + pattern_lower.len();
                 
-                // Convert to character positions for boundary checking
-                let char_pos_start = text[..actual_pos].chars().count();
-                let char_pos_end = text[..end_pos.min(text.len())].chars().count();
-                let text_chars: Vec<char> = text.chars().collect();
+                // Check if it's a whole word
+                let is_word_start = text_lower[..actual_pos].chars().next_back()
+                    .map_or(true, |c| !c.is_alphabetic());
+                let is_word_end = text_lower[end_pos..].chars().next()
+                    .map_or(true, |c| !c.is_alphabetic());
                 
-                // Check if it's a whole word using character positions
-                let is_word_start = char_pos_start == 0 || 
-                    (char_pos_start > 0 && !text_chars[char_pos_start - 1].is_alphabetic());
-                let is_word_end = char_pos_end >= text_chars.len() || 
-                    !text_chars[char_pos_end].is_alphabetic();
-                
-                if is_word_start && is_word_end {
-                    let safe_start = find_char_boundary(text, actual_pos);
-                    let safe_end = find_char_boundary(text, end_pos);
-                    
-                    let char_start = text[..safe_start].chars().count();
-                    let char_end = text[..safe_end].chars().count();
-                    
-                    let string: String = if char_end <= text_chars.len() {
-                        text_chars[char_start..char_end].iter().collect()
-                    } else {
-                        text_chars[char_start..].iter().collect()
-                    };
+                if is_word_start && is_word_end && p.report(FILLER_WORD, lower.original(actual_pos)) {
+                    let (char_start, char_end, string) =
+                        char_occurrence(&index, text, lower.original(actual_pos), lower.original(end_pos));
                     
                     style_map.entry(pattern.to_string())
                         .or_insert_with(Vec::new)
@@ -1457,7 +1461,7 @@ async fn get_passive_voice(
     
     let style_improvements: Vec<EnhancementOccurrence> = style_map.into_iter().map(|(phrase, occurrences_list)| {
         let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "styleImprovements".to_string() }
+            OccurrenceDetail { start, end, string, paragraph_key: key, report: "styleImprovements".to_string(), fix: None }
         }).collect();
         
         EnhancementOccurrence {
@@ -1471,80 +1475,45 @@ async fn get_passive_voice(
     // Detect sentences with many commas (indicator of complex clauses)
     let mut long_clauses: Vec<EnhancementOccurrence> = Vec::new();
     
-    for paragraph in &payload.data {
-        let text = &paragraph.text;
-        let text_chars: Vec<char> = text.chars().collect();
-        let text_len = text_chars.len();
-        
-        let mut current_pos = 0;
-        
-        while current_pos < text_len {
-            // Find the next sentence ending
-            let mut sentence_end = current_pos;
-            let mut found_delimiter = false;
-            
-            for i in current_pos..text_len {
-                if text_chars[i] == '.' || text_chars[i] == '!' || text_chars[i] == '?' {
-                    sentence_end = i + 1; // Include the delimiter
-                    found_delimiter = true;
-                    break;
-                }
-            }
-            
-            // If no delimiter found, take rest of text
-            if !found_delimiter {
-                sentence_end = text_len;
-            }
-            
-            // Extract sentence
-            let sentence_chars: Vec<char> = text_chars[current_pos..sentence_end].iter().cloned().collect();
-            let sentence: String = sentence_chars.iter().collect();
-            let sentence_trimmed = sentence.trim();
-            
-            if !sentence_trimmed.is_empty() {
-                let comma_count = sentence.matches(',').count();
-                
-                if comma_count >= 3 {
-                    let sentence_char_count = sentence_chars.len();
-                    let excerpt = if sentence_char_count > 50 {
-                        sentence_chars.iter().take(50).collect::<String>() + "..."
-                    } else {
-                        sentence.clone()
-                    };
-                    
-                    long_clauses.push(EnhancementOccurrence {
-                        phrase: format!("Complex sentence with {} commas", comma_count),
-                        count: 1,
-                        occurrences: vec![OccurrenceDetail {
-                            start: current_pos,
-                            end: sentence_end,  // Now includes the punctuation
-                            string: excerpt,
-                            paragraph_key: paragraph.key.clone(),
-                            report: "longSubordinateClauses".to_string(),
-                        }],
-                    });
-                }
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
+        for sentence in sentence_spans(&paragraph.text) {
+            let comma_count = sentence.text.matches(',').count();
+
+            if comma_count >= 3 && p.report(COMPLEX_SENTENCE, sentence.span.byte_start) {
+                let excerpt = if sentence.text.chars().count() > 50 {
+                    sentence.text.chars().take(50).collect::<String>() + "..."
+                } else {
+                    sentence.text.clone()
+                };
+
+                long_clauses.push(EnhancementOccurrence {
+                    phrase: format!("Complex sentence with {} commas", comma_count),
+                    count: 1,
+                    occurrences: vec![OccurrenceDetail {
+                        start: sentence.span.char_start,
+                        end: sentence.span.char_end,  // Includes the punctuation
+                        string: excerpt,
+                        paragraph_key: paragraph.key.clone(),
+                        report: "longSubordinateClauses".to_string(),
+                        fix: None,
+                    }],
+                });
             }
-            
-            current_pos = sentence_end;
         }
     }
 
     // ========== 8. REPEATED SENTENCE STARTS ==========
     let mut sentence_starts: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
-        let text = &paragraph.text;
-        let sentences: Vec<&str> = text.split(&['.', '!', '?'][..])
-            .filter(|s| !s.trim().is_empty())
-            .collect();
-        
-        for sentence in sentences {
-            let trimmed = sentence.trim();
+    for p in &mut paragraphs {
+        for sentence in sentence_spans(&p.paragraph.text) {
+            let trimmed = sentence.text.as_str();
             if let Some(first_word) = trimmed.split_whitespace().next() {
                 let first_word_clean = first_word.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase();
                 
-                if first_word_clean.len() > 2 {  // Ignore very short words
+                // Ignore very short words
+                if first_word_clean.len() > 2 && p.report(REPEATED_START, sentence.span.byte_start) {
                     sentence_starts.entry(first_word_clean)
                         .or_insert_with(Vec::new)
                         .push(trimmed.chars().take(50).collect::<String>());
@@ -1563,8 +1532,9 @@ async fn get_passive_voice(
         .collect();
 
     // ========== 9. BUSINESS JARGON ==========
-    let business_jargon_items: Vec<EnhancementOccurrence> = full_report.business_jargon.jargon_list
+    let business_jargon_items: Vec<EnhancementOccurrence> = full_report.business_jargon
         .into_iter()
+        .flat_map(|report| report.jargon_list)
         .map(|item| EnhancementOccurrence {
             phrase: item.jargon.clone(),
             count: item.count,
@@ -1579,48 +1549,54 @@ async fn get_passive_voice(
         ("for all intensive purposes", "for all intents and purposes"),
     ];
     
-    let mut style_guide_map: std::collections::HashMap<String, Vec<(String, usize, usize, String)>> = 
+    let mut style_guide_map: std::collections::HashMap<String, Vec<OccurrenceDetail>> =
         std::collections::HashMap::new();
     
-    for paragraph in &payload.data {
+    for p in &mut paragraphs {
+        let paragraph = p.paragraph;
         let text = &paragraph.text;
-        let text_lower = text.to_lowercase();
+        let lower = Lowercased::new(text);
+        let text_lower = &lower.text;
+        let index = OffsetIndex::new(text);
         
-        for (pattern, _correction) in &style_guide_patterns {
+        for (pattern, correction) in &style_guide_patterns {
             let pattern_lower = pattern.to_lowercase();
             let mut start_pos = 0;
             
             while let Some(pos) = text_lower[start_pos..].find(&pattern_lower) {
                 let actual_pos = start_pos + pos;
-                let end_pos = actual_pos + pattern.chars().count();  // ✅ FIXED: Use .chars().count()
+                let end_pos = actual_pos + pattern_lower.len();
                 
-                let safe_start = find_char_boundary(text, actual_pos);
-                let safe_end = find_char_boundary(text, end_pos);
-                
-                let char_start = text[..safe_start].chars().count();
-                let char_end = text[..safe_end].chars().count();
-                let text_chars: Vec<char> = text.chars().collect();
-                
-                let string: String = if char_end <= text_chars.len() {
-                    text_chars[char_start..char_end].iter().collect()
-                } else {
-                    text_chars[char_start..].iter().collect()
-                };
+                let (byte_start, byte_end) = (lower.original(actual_pos), lower.original(end_pos));
+                if !p.report(STYLE_GUIDE, byte_start) {
+                    start_pos = end_pos;
+                    continue;
+                }
+                let (char_start, char_end, string) = char_occurrence(&index, text, byte_start, byte_end);
+                let fix = Fix::replace(
+                    format!("Replace with \"{}\"", correction),
+                    index.span(byte_start, byte_end),
+                    match_case(correction, &string),
+                    Applicability::MachineApplicable,
+                );
                 
                 style_guide_map.entry(pattern.to_string())
                     .or_insert_with(Vec::new)
-                    .push((paragraph.key.clone(), char_start, char_end, string));
+                    .push(OccurrenceDetail {
+                        start: char_start,
+                        end: char_end,
+                        string,
+                        paragraph_key: paragraph.key.clone(),
+                        report: "styleGuideItems".to_string(),
+                        fix: Some(fix),
+                    });
                 
                 start_pos = end_pos;
             }
         }
     }
     
-    let style_guide_items: Vec<EnhancementOccurrence> = style_guide_map.into_iter().map(|(phrase, occurrences_list)| {
-        let occurrences: Vec<OccurrenceDetail> = occurrences_list.into_iter().map(|(key, start, end, string)| {
-            OccurrenceDetail { start, end, string, paragraph_key: key, report: "styleGuideItems".to_string() }
-        }).collect();
-        
+    let style_guide_items: Vec<EnhancementOccurrence> = style_guide_map.into_iter().map(|(phrase, occurrences)| {
         EnhancementOccurrence {
             phrase,
             count: occurrences.len(),
@@ -1765,6 +1741,14 @@ async fn get_passive_voice(
         format!("{} common grammar/style mistakes found. Review and correct these errors.", style_guide_count)
     };
 
+    // Directives silencing nothing, the server's own findings included
+    let mut unused_suppressions = Vec::new();
+    for p in &paragraphs {
+        if let Some(analyzer) = p.analyzer.as_ref().filter(|a| !a.suppressions().is_empty()) {
+            unused_suppressions.extend(unused_directives(analyzer, &p.findings, Some(&p.paragraph.key))?);
+        }
+    }
+
     let response = PassiveVoiceResponse {
         passive_verbs_found: total_passive_count,
         passive_verbs_message,
@@ -1796,6 +1780,7 @@ async fn get_passive_voice(
         repeated_sentence_starts,
         style_guide_items_message,
         style_guide_items,
+        unused_suppressions,
     };
 
     Ok(Json(response))
@@ -1830,12 +1815,15 @@ async fn get_glue_index(
         }));
     }
 
-    let config = Config::default();
+    // One time budget for the analyzers of every paragraph
+    let config = analysis_config();
+    let deadline = Deadline::after(config.validation.timeout_seconds);
     let analyzer = TextAnalyzer::new(combined_text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?
+        .with_deadline(deadline);
 
     let full_report = analyzer.generate_full_report()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Calculate overall glue index - now using glue_index field directly
     let glue_index = (full_report.sticky_sentences.glue_index * 10.0).round() / 10.0;
@@ -1853,23 +1841,21 @@ async fn get_glue_index(
         }
 
         // Analyze this paragraph
-        let para_config = Config::default();
+        let para_config = analysis_config();
         let para_analyzer = TextAnalyzer::new(text.clone(), para_config)
-            .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+            .map_err(ApiError::from)?
+            .with_deadline(deadline);
 
         let para_report = para_analyzer.generate_full_report()
-            .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+            .map_err(ApiError::from)?;
 
         // Process sticky sentences (>45% glue) - now using fields directly from struct
         for s in para_report.sticky_sentences.sticky_sentences {
             // Use the sentence text directly from the analyzer - it's already complete!
             let string = s.sentence.clone();
             
-            // Convert byte positions to character positions for the API response
-            let safe_start = find_char_boundary(text, s.start_index);
-            let safe_end = find_char_boundary(text, s.end_index.min(text.len()));
-            let char_start = text[..safe_start].chars().count();
-            let char_end = text[..safe_end].chars().count();
+            // The API reports character positions
+            let (char_start, char_end) = (s.span.char_start, s.span.char_end);
             
             let char_length = string.chars().count();
             let excerpt = if char_length > 50 {
@@ -1894,11 +1880,8 @@ async fn get_glue_index(
             // Use the sentence text directly from the analyzer - it's already complete!
             let string = s.sentence.clone();
             
-            // Convert byte positions to character positions for the API response
-            let safe_start = find_char_boundary(text, s.start_index);
-            let safe_end = find_char_boundary(text, s.end_index.min(text.len()));
-            let char_start = text[..safe_start].chars().count();
-            let char_end = text[..safe_end].chars().count();
+            // The API reports character positions
+            let (char_start, char_end) = (s.span.char_start, s.span.char_end);
             
             let char_length = string.chars().count();
             let excerpt = if char_length > 50 {
@@ -1932,25 +1915,37 @@ async fn get_glue_index(
     Ok(Json(response))
 }
 
-// Helper function to find the nearest valid character boundary
-fn find_char_boundary(text: &str, byte_index: usize) -> usize {
-    if byte_index >= text.len() {
-        return text.len();
-    }
-    
-    // If already at a char boundary, return it
-    if text.is_char_boundary(byte_index) {
-        return byte_index;
-    }
-    
-    // Search backwards for the nearest char boundary
-    for i in (0..=byte_index).rev() {
-        if text.is_char_boundary(i) {
-            return i;
+// Char offsets and text of a byte range, for the API's char-based positions
+fn char_occurrence(index: &OffsetIndex, text: &str, byte_start: usize, byte_end: usize) -> (usize, usize, String) {
+    let span = index.span(byte_start, byte_end);
+    (span.char_start, span.char_end, text[span.byte_range()].to_string())
+}
+
+/// A request paragraph with its suppression directives and the server's own findings in it
+struct ParagraphFindings<'a> {
+    paragraph: &'a ParagraphData,
+    suppressions: Suppressions,
+    /// Analyzer of the paragraph, once its passive voice is detected
+    analyzer: Option<TextAnalyzer>,
+    /// Every finding made, silenced or not, as rule ID and byte offset
+    findings: Vec<(&'static str, usize)>,
+}
+
+impl<'a> ParagraphFindings<'a> {
+    fn new(paragraph: &'a ParagraphData) -> Self {
+        Self {
+            paragraph,
+            suppressions: Suppressions::parse(&paragraph.text),
+            analyzer: None,
+            findings: Vec::new(),
         }
     }
-    
-    0
+
+    /// Record a finding of `rule` at byte `offset`, returning whether to report it
+    fn report(&mut self, rule: &'static str, offset: usize) -> bool {
+        self.findings.push((rule, offset));
+        !self.suppressions.suppresses(rule, offset)
+    }
 }
 
 // Create user-friendly scores with ideal values and quality messages
@@ -1961,6 +1956,7 @@ fn create_user_friendly_scores(
     stats: &Rust_Grammar::TextStatistics,
     readability: &Rust_Grammar::ReadabilityMetrics,
     text: &str,
+    suppressions: &Suppressions,
 ) -> UserFriendlyScores {
     let sentence_count = stats.sentence_count.max(1) as f64;
     let word_count = stats.word_count.max(1) as f64;
@@ -1968,21 +1964,21 @@ fn create_user_friendly_scores(
     // Calculate key metrics
     let passive_percentage = (passive_voice.len() as f64 / sentence_count) * 100.0;
     let glue_percentage = full_report.sticky_sentences.overall_glue_index;
-    let jargon_count = full_report.business_jargon.total_jargon;
+    let jargon_count = full_report.business_jargon.as_ref().map_or(0, |j| j.total_jargon);
     let conjunction_percentage = full_report.conjunction_starts.percentage;
     let slow_pacing_percentage = full_report.pacing.slow_paced_percentage;
     let very_long_count = full_report.sentence_length.very_long_sentences;
     let very_long_percentage = (very_long_count as f64 / sentence_count) * 100.0;
     
     // Count weak adverbs
-    let weak_adverbs = count_weak_adverbs(text);
+    let weak_adverbs = count_weak_adverbs(text, suppressions);
     let weak_adverbs_percentage = (weak_adverbs as f64 / word_count) * 100.0;
     
     // Count emotion tells
-    let emotion_tells = count_emotion_tells(text);
+    let emotion_tells = count_emotion_tells(text, suppressions);
     
     // Count ing starts
-    let ing_starts = count_ing_starts(text);
+    let ing_starts = count_ing_starts(text, suppressions);
     let ing_percentage = (ing_starts as f64 / sentence_count) * 100.0;
     
     // Dialogue analysis
@@ -2105,65 +2101,36 @@ fn calculate_comprehensive_scores(
     stats: &Rust_Grammar::TextStatistics,
     readability: &Rust_Grammar::ReadabilityMetrics,
     text: &str,
+    suppressions: &Suppressions,
 ) -> ComprehensiveScores {
     let sentence_count = stats.sentence_count.max(1) as f64;
     let word_count = stats.word_count.max(1) as f64;
+    let jargon_count = full_report.business_jargon.as_ref().map_or(0, |j| j.total_jargon);
     
     // Calculate individual sentence details with positions
-    let sentences = split_into_sentences(text);
-    let mut individual_lengths = Vec::new();
-    
-    // Convert text to character array for accurate Unicode indexing
-    let text_chars: Vec<char> = text.chars().collect();
-    let mut char_pos = 0;
-    
-    for sentence in &sentences {
-        let sentence_trimmed = sentence.trim();
-        let sentence_chars: Vec<char> = sentence_trimmed.chars().collect();
-        
-        // Find sentence in character array
-        let mut found = false;
-        for i in char_pos..text_chars.len() {
-            if i + sentence_chars.len() <= text_chars.len() {
-                let slice: Vec<char> = text_chars[i..i + sentence_chars.len()].to_vec();
-                if slice == sentence_chars {
-                    let start = i;
-                    let end = i + sentence_chars.len();
-                    let char_length = sentence_chars.len();
-                    let word_count_val = sentence.split_whitespace().count();
-                    
-                    individual_lengths.push(SentenceOccurrence {
-                        start,
-                        end,
-                        length: char_length,
-                        string: if sentence.len() > 50 {
-                            sentence.chars().take(50).collect::<String>() + "..."
-                        } else {
-                            sentence.clone()
-                        },
-                        word_count: word_count_val,
-                        paragraph_key: format!("{}", estimate_paragraph(text, start)),
-                    });
-                    
-                    char_pos = end;
-                    found = true;
-                    break;
-                }
-            }
-        }
-        
-        // If not found with exact match, skip to next sentence
-        if !found {
-            char_pos += sentence.chars().count();
-        }
-    }
+    let sentences = sentence_spans(text);
+    let individual_lengths: Vec<SentenceOccurrence> = sentences
+        .iter()
+        .map(|sentence| SentenceOccurrence {
+            start: sentence.span.char_start,
+            end: sentence.span.char_end,
+            length: sentence.span.char_end - sentence.span.char_start,
+            string: if sentence.text.len() > 50 {
+                sentence.text.chars().take(50).collect::<String>() + "..."
+            } else {
+                sentence.text.clone()
+            },
+            word_count: sentence.text.split_whitespace().count(),
+            paragraph_key: format!("{}", sentence.paragraph),
+        })
+        .collect();
     
     // Calculate distribution
-    let under_10 = sentences.iter().map(|s| s.split_whitespace().count()).filter(|&l| l < 10).count();
-    let range_10_to_19 = sentences.iter().map(|s| s.split_whitespace().count()).filter(|l| *l >= 10 && *l < 20).count();
-    let range_20_to_29 = sentences.iter().map(|s| s.split_whitespace().count()).filter(|l| *l >= 20 && *l < 30).count();
-    let range_30_to_39 = sentences.iter().map(|s| s.split_whitespace().count()).filter(|l| *l >= 30 && *l < 40).count();
-    let over_40 = sentences.iter().map(|s| s.split_whitespace().count()).filter(|l| *l >= 40).count();
+    let under_10 = sentences.iter().map(|s| s.text.split_whitespace().count()).filter(|&l| l < 10).count();
+    let range_10_to_19 = sentences.iter().map(|s| s.text.split_whitespace().count()).filter(|l| *l >= 10 && *l < 20).count();
+    let range_20_to_29 = sentences.iter().map(|s| s.text.split_whitespace().count()).filter(|l| *l >= 20 && *l < 30).count();
+    let range_30_to_39 = sentences.iter().map(|s| s.text.split_whitespace().count()).filter(|l| *l >= 30 && *l < 40).count();
+    let over_40 = sentences.iter().map(|s| s.text.split_whitespace().count()).filter(|l| *l >= 40).count();
     
     // Calculate percentages
     let passive_percentage = (passive_voice.len() as f64 / sentence_count) * 100.0;
@@ -2175,19 +2142,19 @@ fn calculate_comprehensive_scores(
     let very_long_percentage = (very_long_count as f64 / sentence_count) * 100.0;
     
     // Count -ing starts (approximation)
-    let ing_starts = count_ing_starts(text);
+    let ing_starts = count_ing_starts(text, suppressions);
     let ing_percentage = (ing_starts as f64 / sentence_count) * 100.0;
-    let ing_starts_positions = get_ing_starts_positions(text);
+    let ing_starts_positions = get_ing_starts_positions(text, suppressions);
     
     // Count weak adverbs (words ending in -ly)
-    let weak_adverbs = count_weak_adverbs(text);
-    let weak_adverbs_positions = get_weak_adverbs_positions(text);
+    let weak_adverbs = count_weak_adverbs(text, suppressions);
+    let weak_adverbs_positions = get_weak_adverbs_positions(text, suppressions);
     
     // Dialogue analysis (approximation based on quotes)
     let (dialogue_tags, unusual_tags, tags_with_adverbs) = analyze_dialogue(text);
     
     // Emotion tells (words like "felt", "seemed", "appeared")
-    let emotion_tells = count_emotion_tells(text);
+    let emotion_tells = count_emotion_tells(text, suppressions);
     
     ComprehensiveScores {
         style_score: ScoreDetail {
@@ -2262,10 +2229,11 @@ fn calculate_comprehensive_scores(
         },
         
         business_jargon: CountScore {
-            count: full_report.business_jargon.total_jargon,
-            percentage: Some((full_report.business_jargon.total_jargon as f64 / word_count) * 100.0),
+            count: jargon_count,
+            percentage: Some((jargon_count as f64 / word_count) * 100.0),
             message: None,
-            occurrences: Some(full_report.business_jargon.jargon_list.iter()
+            occurrences: Some(full_report.business_jargon.iter()
+                .flat_map(|report| &report.jargon_list)
                 .flat_map(|j| j.occurrences.iter().map(move |occ| Occurrence {
                     start: occ.start_index,
                     end: occ.end_index,
@@ -2374,54 +2342,61 @@ fn convert_to_issues(
     grammar: &[GrammarIssue],
     text: &str,
 ) -> Vec<AnalysisIssue> {
+    // The API reports character positions; byte offsets only locate the paragraph
     let mut issues = Vec::new();
 
     // Add passive voice
     for pv in passive_voice {
+        let (start, end) = (pv.span.char_start, pv.span.char_end);
         issues.push(AnalysisIssue {
-            id: format!("{}_{}_{}_{}_passive", "auto", pv.start_index, pv.end_index, pv.length),
-            start: pv.start_index,
-            length: pv.length,
-            end: pv.end_index,
+            id: format!("{}_{}_{}_{}_passive", "auto", start, end, end - start),
+            start,
+            length: end - start,
+            end,
             paragraph_key: format!("{}", estimate_paragraph(text, pv.start_index)),
             string: pv.text.clone(),
             issue_type: "PassiveVoice".to_string(),
             suggestions: Suggestions {
                 recommendation: vec!["Consider using active voice for clarity".to_string()],
+                fixes: pv.fix.iter().cloned().collect(),
             },
         });
     }
 
     // Add grammar issues
     for issue in grammar {
-        let issue_text = extract_text(text, issue.start_index, issue.end_index);
+        let issue_text = text[issue.span.byte_range()].to_string();
+        let (start, end) = (issue.span.char_start, issue.span.char_end);
         issues.push(AnalysisIssue {
-            id: format!("{}_{}_{}_{}_grammar", "auto", issue.start_index, issue.end_index, issue.length),
-            start: issue.start_index,
-            length: issue.length,
-            end: issue.end_index,
+            id: format!("{}_{}_{}_{}_grammar", "auto", start, end, end - start),
+            start,
+            length: end - start,
+            end,
             paragraph_key: format!("{}", issue.sentence_num / 3),
             string: issue_text,
             issue_type: format!("Grammar_{:?}", issue.issue_type),
             suggestions: Suggestions {
                 recommendation: vec![issue.message.clone()],
+                fixes: issue.fixes.clone(),
             },
         });
     }
 
     // Add other issues (clichés, jargon, etc.) - shortened for brevity
-    for cliche in &full_report.cliches.cliches {
+    for cliche in full_report.cliches.iter().flat_map(|c| &c.cliches) {
         for occ in &cliche.occurrences {
+            let (start, end) = (occ.span.char_start, occ.span.char_end);
             issues.push(AnalysisIssue {
-                id: format!("{}_{}_{}_{}_cliche", "auto", occ.start_index, occ.end_index, occ.length),
-                start: occ.start_index,
-                length: occ.length,
-                end: occ.end_index,
+                id: format!("{}_{}_{}_{}_cliche", "auto", start, end, end - start),
+                start,
+                length: end - start,
+                end,
                 paragraph_key: format!("{}", estimate_paragraph(text, occ.start_index)),
                 string: cliche.cliche.clone(),
                 issue_type: "Cliche".to_string(),
                 suggestions: Suggestions {
                     recommendation: vec!["Avoid clichés".to_string()],
+                    fixes: Vec::new(),
                 },
             });
         }
@@ -2465,87 +2440,102 @@ fn calculate_variety_score(report: &Rust_Grammar::SentenceLengthReport) -> i32 {
     }
 }
 
-fn count_ing_starts(text: &str) -> usize {
+/// Sentences starting with an -ing word, other than those `suppressions` silence
+fn ing_starts<'t>(text: &'t str, suppressions: &Suppressions) -> Vec<regex::Match<'t>> {
     use regex::Regex;
     let ing_pattern = Regex::new(r"(?m)^[A-Z]\w+ing\b").unwrap();
-    ing_pattern.find_iter(text).count()
+    ing_pattern.find_iter(text)
+        .filter(|m| !suppressions.suppresses(ING_START, m.start()))
+        .collect()
 }
 
-fn get_ing_starts_positions(text: &str) -> Vec<Occurrence> {
-    use regex::Regex;
-    let ing_pattern = Regex::new(r"(?m)^[A-Z]\w+ing\b").unwrap();
-    ing_pattern.find_iter(text).map(|m| Occurrence {
-        start: m.start(),
-        end: m.end(),
-        length: m.end() - m.start(),
-        string: m.as_str().to_string(),
-        paragraph_key: format!("{}", estimate_paragraph(text, m.start())),
+fn count_ing_starts(text: &str, suppressions: &Suppressions) -> usize {
+    ing_starts(text, suppressions).len()
+}
+
+fn get_ing_starts_positions(text: &str, suppressions: &Suppressions) -> Vec<Occurrence> {
+    let index = OffsetIndex::new(text);
+    ing_starts(text, suppressions).into_iter().map(|m| {
+        let (start, end, string) = char_occurrence(&index, text, m.start(), m.end());
+        Occurrence {
+            start,
+            end,
+            length: end - start,
+            string,
+            paragraph_key: format!("{}", estimate_paragraph(text, m.start())),
+        }
     }).collect()
 }
 
-fn count_weak_adverbs(text: &str) -> usize {
-    use regex::Regex;
-    let adverb_pattern = Regex::new(r"\b\w+ly\b").unwrap();
-    adverb_pattern.find_iter(text).count()
+/// Tokens tagged as -ly adverbs, other than those `suppressions` silence
+fn weak_adverbs(text: &str, suppressions: &Suppressions) -> Vec<Token> {
+    PosTagger::new()
+        .tag(text)
+        .into_iter()
+        .filter(|t| t.tag == Tag::Adverb && t.lower.ends_with("ly"))
+        .filter(|t| !suppressions.suppresses(ADVERB, t.start))
+        .collect()
 }
 
-fn get_weak_adverbs_positions(text: &str) -> Vec<Occurrence> {
-    use regex::Regex;
-    let adverb_pattern = Regex::new(r"\b\w+ly\b").unwrap();
-    adverb_pattern.find_iter(text).map(|m| Occurrence {
-        start: m.start(),
-        end: m.end(),
-        length: m.end() - m.start(),
-        string: m.as_str().to_string(),
-        paragraph_key: format!("{}", estimate_paragraph(text, m.start())),
+fn count_weak_adverbs(text: &str, suppressions: &Suppressions) -> usize {
+    weak_adverbs(text, suppressions).len()
+}
+
+fn get_weak_adverbs_positions(text: &str, suppressions: &Suppressions) -> Vec<Occurrence> {
+    let index = OffsetIndex::new(text);
+    weak_adverbs(text, suppressions).into_iter().map(|t| {
+        let span = index.span(t.start, t.end);
+        Occurrence {
+            start: span.char_start,
+            end: span.char_end,
+            length: span.char_end - span.char_start,
+            paragraph_key: format!("{}", estimate_paragraph(text, t.start)),
+            string: t.text,
+        }
     }).collect()
 }
 
-fn count_emotion_tells(text: &str) -> usize {
+/// Byte offsets of the emotion tells, other than those `suppressions` silence
+fn emotion_tells(text: &str, suppressions: &Suppressions) -> Vec<usize> {
     let emotion_words = ["felt", "seemed", "appeared", "looked like", "sounded like"];
-    let lower = text.to_lowercase();
+    let lower = Lowercased::new(text);
     emotion_words.iter()
-        .map(|word| lower.matches(word).count())
-        .sum()
+        .flat_map(|word| lower.text.match_indices(word).map(|(pos, _)| lower.original(pos)))
+        .filter(|&offset| !suppressions.suppresses(EMOTION_TELL, offset))
+        .collect()
 }
 
-fn count_complex_words(text: &str) -> usize {
-    // Count words with 3 or more syllables
-    text.split_whitespace()
-        .filter(|word| {
-            let clean_word = word.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase();
-            count_syllables(&clean_word) >= 3
-        })
-        .count()
+fn count_emotion_tells(text: &str, suppressions: &Suppressions) -> usize {
+    emotion_tells(text, suppressions).len()
 }
 
-fn count_syllables(word: &str) -> usize {
-    if word.is_empty() {
-        return 0;
-    }
-    
-    let vowels = ['a', 'e', 'i', 'o', 'u', 'y'];
-    let mut count = 0;
-    let mut previous_was_vowel = false;
-    let chars: Vec<char> = word.chars().collect();
-    
-    for ch in chars.iter() {
-        let is_vowel = vowels.contains(&ch.to_lowercase().next().unwrap_or(' '));
-        
-        if is_vowel && !previous_was_vowel {
-            count += 1;
-        }
-        
-        previous_was_vowel = is_vowel;
-    }
-    
-    // Subtract 1 for silent 'e' at the end
-    if word.ends_with('e') && count > 1 {
-        count -= 1;
-    }
-    
-    // Every word has at least one syllable
-    count.max(1)
+/// The server's own findings in a whole text, silenced or not, as rule ID and byte offset
+fn server_findings(text: &str) -> Vec<(&'static str, usize)> {
+    let none = Suppressions::default();
+    let mut findings: Vec<_> = ing_starts(text, &none).iter().map(|m| (ING_START, m.start())).collect();
+    findings.extend(weak_adverbs(text, &none).iter().map(|t| (ADVERB, t.start)));
+    findings.extend(emotion_tells(text, &none).into_iter().map(|offset| (EMOTION_TELL, offset)));
+    findings
+}
+
+/// Directives of `analyzer` silencing neither one of its findings nor one of the server's own `findings`
+fn unused_directives(
+    analyzer: &TextAnalyzer,
+    findings: &[(&str, usize)],
+    paragraph_key: Option<&str>,
+) -> Result<Vec<UnusedSuppression>, ApiError> {
+    let server_unused = analyzer.suppressions().unused(findings);
+    Ok(analyzer.unused_suppressions()?
+        .into_iter()
+        .filter(|directive| server_unused.contains(&directive))
+        .map(|directive| UnusedSuppression {
+            directive: directive.to_string(),
+            line: directive.line,
+            start: directive.span.char_start,
+            end: directive.span.char_end,
+            paragraph_key: paragraph_key.map(str::to_string),
+        })
+        .collect())
 }
 
 fn analyze_dialogue(text: &str) -> ((f64, usize, usize), (f64, usize, usize), (f64, usize, usize)) {
@@ -2582,39 +2572,22 @@ fn analyze_dialogue(text: &str) -> ((f64, usize, usize), (f64, usize, usize), (f
     )
 }
 
-fn extract_text(text: &str, start: usize, end: usize) -> String {
-    text.chars().skip(start).take(end - start).collect()
-}
-
 fn estimate_paragraph(text: &str, position: usize) -> usize {
     text[..position.min(text.len())].matches("\n\n").count()
 }
 
-fn split_into_sentences(text: &str) -> Vec<String> {
-    use regex::Regex;
-    // Simple sentence splitter - matches ., !, ? followed by space or end
-    let sentence_pattern = Regex::new(r"[.!?]+(?:\s+|$)").unwrap();
-    
-    let mut sentences = Vec::new();
-    let mut last_end = 0;
-    
-    for mat in sentence_pattern.find_iter(text) {
-        let sentence = text[last_end..mat.end()].trim().to_string();
-        if !sentence.is_empty() {
-            sentences.push(sentence);
-        }
-        last_end = mat.end();
-    }
-    
-    // Add any remaining text
-    if last_end < text.len() {
-        let remaining = text[last_end..].trim().to_string();
-        if !remaining.is_empty() {
-            sentences.push(remaining);
-        }
+// Sentences of `text` with their spans; the API reports the char offsets
+fn sentence_spans(text: &str) -> Vec<SentenceSpan> {
+    SentenceSplitter::default().split_spans(text).unwrap_or_default()
+}
+
+// How the API names a difficulty
+fn difficulty_label(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Readable => "readable",
+        Difficulty::Hard => "hard",
+        Difficulty::VeryHard => "very hard",
     }
-    
-    sentences
 }
 
 // Helper function to determine sentence kind based on word count
@@ -2628,79 +2601,6 @@ fn get_sentence_kind(word_count: usize) -> String {
     }
 }
 
-fn split_sentences_with_positions(text: &str) -> Vec<(String, usize, usize)> {
-    use regex::Regex;
-    
-    let mut sentences = Vec::new();
-    
-    // Convert entire text to character array - THIS IS CRITICAL FOR UNICODE
-    let text_chars: Vec<char> = text.chars().collect();
-    let total_chars = text_chars.len();
-    
-    // Match sentence endings: ., !, or ?
-    let sentence_pattern = Regex::new(r"[.!?]").unwrap();
-    
-    let mut char_position = 0;
-    
-    // Find all punctuation marks
-    let text_string: String = text_chars.iter().collect();
-    
-    for mat in sentence_pattern.find_iter(&text_string) {
-        // Convert byte position to character position
-        let punct_byte_pos = mat.start();
-        let punct_char_pos = text[..punct_byte_pos].chars().count();
-        
-        // Sentence goes from char_position to after the punctuation
-        let sentence_start = char_position;
-        let sentence_end = punct_char_pos + 1; // Include the punctuation mark
-        
-        if sentence_end <= total_chars && sentence_start < sentence_end {
-            // Extract sentence using CHARACTER positions
-            let sentence_chars: Vec<char> = text_chars[sentence_start..sentence_end].to_vec();
-            let sentence_text: String = sentence_chars.iter().collect();
-            
-            if !sentence_text.trim().is_empty() {
-                // Calculate length using CHARACTER count
-                let character_array = sentence_text.chars();
-                let length = character_array.count();
-                
-                // Push with character-based positions
-                sentences.push((
-                    sentence_text,
-                    sentence_start,
-                    sentence_start + length
-                ));
-            }
-        }
-        
-        // Move to next sentence - skip whitespace
-        char_position = sentence_end;
-        while char_position < total_chars && text_chars[char_position].is_whitespace() {
-            char_position += 1;
-        }
-    }
-    
-    // Handle any remaining text (text without ending punctuation)
-    if char_position < total_chars {
-        let remaining_chars: Vec<char> = text_chars[char_position..].to_vec();
-        let remaining_text: String = remaining_chars.iter().collect();
-        
-        if !remaining_text.trim().is_empty() {
-            // Calculate length using CHARACTER count
-            let character_array = remaining_text.chars();
-            let length = character_array.count();
-            
-            sentences.push((
-                remaining_text,
-                char_position,
-                char_position + length
-            ));
-        }
-    }
-    
-    sentences
-}
-
 // Message helper functions for /score endpoint
 fn get_style_score_message(score: i32) -> String {
     match score {
@@ -2847,13 +2747,24 @@ fn get_dialogue_tags_message(count: usize) -> String {
 // Error handling
 enum ApiError {
     EmptyText,
+    Timeout(String),
     AnalysisError(String),
 }
 
+impl From<Rust_Grammar::AnalysisError> for ApiError {
+    fn from(err: Rust_Grammar::AnalysisError) -> Self {
+        match err {
+            Rust_Grammar::AnalysisError::TimeoutError(_) => ApiError::Timeout(err.to_string()),
+            _ => ApiError::AnalysisError(err.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
             ApiError::EmptyText => (StatusCode::BAD_REQUEST, "Text cannot be empty".to_string()),
+            ApiError::Timeout(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
             ApiError::AnalysisError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
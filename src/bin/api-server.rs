@@ -8,6 +8,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use Rust_Grammar::{Config, TextAnalyzer};
+use Rust_Grammar::fix::Fix;
 use tower_http::cors::CorsLayer;
 
 #[tokio::main]
@@ -30,6 +31,13 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+/// Default config with result caching, so re-submitted texts are served from memory
+fn analysis_config() -> Config {
+    let mut config = Config::default();
+    config.analysis.cache_results = true;
+    config
+}
+
 // Request body structure
 #[derive(Debug, Deserialize)]
 struct AnalyzeRequest {
@@ -55,6 +63,8 @@ struct AnalysisIssue {
 #[derive(Debug, Serialize)]
 struct Suggestions {
     recommendation: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
 }
 
 // API response
@@ -81,22 +91,22 @@ async fn analyze_text(
     }
 
     // Create analyzer with default config
-    let config = Config::default();
+    let config = analysis_config();
     let analyzer = TextAnalyzer::new(payload.text.clone(), config)
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     // Run full analysis
     let full_report = analyzer
         .generate_full_report()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let passive_voice = analyzer
         .detect_passive_voice()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let grammar = analyzer
         .check_grammar()
-        .map_err(|e| ApiError::AnalysisError(e.to_string()))?;
+        .map_err(ApiError::from)?;
 
     let stats = analyzer.statistics();
 
@@ -121,6 +131,7 @@ async fn analyze_text(
                 recommendation: vec![
                     "Consider using active voice for clarity".to_string(),
                 ],
+                fixes: Vec::new(),
             },
         });
     }
@@ -128,7 +139,7 @@ async fn analyze_text(
     // Add grammar issues
     for issue in grammar {
         issue_counter += 1;
-        let issue_text = extract_text(&payload.text, issue.start_index, issue.end_index);
+        let issue_text = payload.text[issue.span.byte_range()].to_string();
         issues.push(AnalysisIssue {
             id: format!("{}_{}_{}_{}_grammar_{}", 
                 "auto", issue.start_index, issue.end_index, issue.length,
@@ -141,6 +152,7 @@ async fn analyze_text(
             issue_type: format!("Grammar_{:?}", issue.issue_type),
             suggestions: Suggestions {
                 recommendation: vec![issue.message],
+                fixes: issue.fixes,
             },
         });
     }
@@ -163,6 +175,7 @@ async fn analyze_text(
                     format!("Reduce glue words ({}% glue)", sticky.glue_percentage.round()),
                     "Use more concrete, meaningful words".to_string(),
                 ],
+                fixes: Vec::new(),
             },
         });
     }
@@ -186,6 +199,7 @@ async fn analyze_text(
                         format!("Used {} times ({:.1}% frequency)", word.count, word.frequency),
                         "Consider using synonyms".to_string(),
                     ],
+                    fixes: Vec::new(),
                 },
             });
         }
@@ -210,13 +224,14 @@ async fn analyze_text(
                         format!("Repeated {} times", phrase.count),
                         "Consider rephrasing for variety".to_string(),
                     ],
+                    fixes: Vec::new(),
                 },
             });
         }
     }
 
     // Add clichés
-    for cliche in &full_report.cliches.cliches {
+    for cliche in full_report.cliches.iter().flat_map(|c| &c.cliches) {
         for occ in &cliche.occurrences {
             issue_counter += 1;
             issues.push(AnalysisIssue {
@@ -234,6 +249,7 @@ async fn analyze_text(
                         "Avoid clichés".to_string(),
                         "Use original phrasing".to_string(),
                     ],
+                    fixes: Vec::new(),
                 },
             });
         }
@@ -258,13 +274,14 @@ async fn analyze_text(
                         "Be more specific".to_string(),
                         "Use concrete language".to_string(),
                     ],
+                    fixes: Vec::new(),
                 },
             });
         }
     }
 
     // Add business jargon
-    for jargon in &full_report.business_jargon.jargon_list {
+    for jargon in full_report.business_jargon.iter().flat_map(|j| &j.jargon_list) {
         for occ in &jargon.occurrences {
             issue_counter += 1;
             issues.push(AnalysisIssue {
@@ -282,6 +299,7 @@ async fn analyze_text(
                         "Avoid corporate jargon".to_string(),
                         "Use plain language".to_string(),
                     ],
+                    fixes: Vec::new(),
                 },
             });
         }
@@ -304,13 +322,6 @@ async fn analyze_text(
 }
 
 // Helper functions
-fn extract_text(text: &str, start: usize, end: usize) -> String {
-    text.chars()
-        .skip(start)
-        .take(end - start)
-        .collect()
-}
-
 fn estimate_paragraph(text: &str, position: usize) -> usize {
     let before = &text[..position.min(text.len())];
     before.matches("\n\n").count()
@@ -319,13 +330,24 @@ fn estimate_paragraph(text: &str, position: usize) -> usize {
 // Error handling
 enum ApiError {
     EmptyText,
+    Timeout(String),
     AnalysisError(String),
 }
 
+impl From<Rust_Grammar::AnalysisError> for ApiError {
+    fn from(err: Rust_Grammar::AnalysisError) -> Self {
+        match err {
+            Rust_Grammar::AnalysisError::TimeoutError(_) => ApiError::Timeout(err.to_string()),
+            _ => ApiError::AnalysisError(err.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
             ApiError::EmptyText => (StatusCode::BAD_REQUEST, "Text cannot be empty".to_string()),
+            ApiError::Timeout(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
             ApiError::AnalysisError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
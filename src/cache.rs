@@ -0,0 +1,413 @@
+// Result cache
+// Content-addressed store of analysis results, in memory with an optional disk backing
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::grammar::GrammarIssue;
+use crate::input::SourceMap;
+use crate::suppression::Suppressions;
+use crate::readability::ReadabilityBreakdown;
+use crate::spelling::find_dictionary;
+use crate::{FullAnalysisReport, ReadabilityMetrics};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tracing::{debug, warn};
+
+lazy_static! {
+    /// Process-wide cache used by analyzers with `analysis.cache_results` set
+    static ref SHARED_CACHE: Arc<AnalysisCache> =
+        Arc::new(AnalysisCache::new(AnalysisCache::DEFAULT_CAPACITY));
+}
+
+const FNV_OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// Content hash of an analyzed text and the configuration it was analyzed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(u128);
+
+impl CacheKey {
+    /// Key for `text` analyzed under `config`
+    ///
+    /// `text` is the analyzed text, which for plain input has `\n` line endings
+    /// and no trailing blanks, so inputs differing only in those share a key;
+    /// the analyzer maps their results back to each input's own offsets. For
+    /// structured input the source map is hashed too, since results are stored
+    /// in source offsets. Settings that cannot change a result (parallelism,
+    /// caching, output) are left out. Glossary file entries are part of the
+    /// configuration once loaded, and the size and modification time of the
+    /// spelling dictionary files are hashed, so editing either gives new keys.
+    pub fn new(text: &str, source_map: Option<&SourceMap>, config: &Config) -> Self {
+        let mut effective = config.clone();
+        effective.analysis.parallel_processing = false;
+        effective.analysis.cache_results = false;
+        effective.output = Config::default().output;
+        let config_json = serde_json::to_string(&effective).unwrap_or_default();
+
+        let mut hasher = Fnv128(FNV_OFFSET);
+        hasher.write_field(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write_field(config_json.as_bytes());
+        if config.spelling.enabled {
+            if let Ok((aff, dic)) = find_dictionary(&config.spelling) {
+                hasher.write_field(file_stamp(&aff).as_bytes());
+                hasher.write_field(file_stamp(&dic).as_bytes());
+            }
+        }
+        hasher.write_field(text.as_bytes());
+        if let Some(map) = source_map {
+            hasher.write_field(format!("{:?}", map).as_bytes());
+        }
+        Self(hasher.0)
+    }
+
+    /// Also key on the grammar rules run, which may be registered outside the config
+    pub fn with_rules<'r>(self, rule_ids: impl IntoIterator<Item = &'r str>) -> Self {
+        let mut hasher = Fnv128(self.0);
+        for id in rule_ids {
+            hasher.write_field(id.as_bytes());
+        }
+        Self(hasher.0)
+    }
+
+    /// Also key on suppression directives, which plain text loses when they are blanked out
+    pub fn with_suppressions(self, suppressions: &Suppressions) -> Self {
+        let mut hasher = Fnv128(self.0);
+        for directive in suppressions.directives() {
+            hasher.write_field(format!("{}@{}", directive, directive.span.byte_start).as_bytes());
+        }
+        Self(hasher.0)
+    }
+}
+
+/// Size and modification time of a file, empty if they cannot be read
+fn file_stamp(path: &Path) -> String {
+    let Ok(metadata) = fs::metadata(path) else {
+        return String::new();
+    };
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos());
+    format!("{}:{}@{}", path.display(), metadata.len(), modified)
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// 128-bit FNV-1a, stable across builds so disk entries stay addressable
+struct Fnv128(u128);
+
+impl Fnv128 {
+    /// Hash a length-prefixed field so adjacent fields cannot run together
+    fn write_field(&mut self, bytes: &[u8]) {
+        for &b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= b as u128;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Everything cached for one key; each result is filled in when first computed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachedResults {
+    pub full_report: Option<FullAnalysisReport>,
+    pub readability: Option<ReadabilityMetrics>,
+    pub grammar: Option<Vec<GrammarIssue>>,
+    pub readability_breakdown: Option<ReadabilityBreakdown>,
+}
+
+/// A result type that can be stored in the cache
+pub trait Cacheable: Clone + Serialize + DeserializeOwned {
+    fn slot(results: &mut CachedResults) -> &mut Option<Self>;
+}
+
+impl Cacheable for FullAnalysisReport {
+    fn slot(results: &mut CachedResults) -> &mut Option<Self> {
+        &mut results.full_report
+    }
+}
+
+impl Cacheable for ReadabilityMetrics {
+    fn slot(results: &mut CachedResults) -> &mut Option<Self> {
+        &mut results.readability
+    }
+}
+
+impl Cacheable for ReadabilityBreakdown {
+    fn slot(results: &mut CachedResults) -> &mut Option<Self> {
+        &mut results.readability_breakdown
+    }
+}
+
+impl Cacheable for Vec<GrammarIssue> {
+    fn slot(results: &mut CachedResults) -> &mut Option<Self> {
+        &mut results.grammar
+    }
+}
+
+struct Entry {
+    results: CachedResults,
+    last_used: AtomicU64,
+}
+
+/// Thread-safe LRU cache of analysis results, optionally persisted to a directory
+pub struct AnalysisCache {
+    entries: DashMap<CacheKey, Entry>,
+    capacity: usize,
+    clock: AtomicU64,
+    disk_dir: Option<PathBuf>,
+}
+
+impl AnalysisCache {
+    /// Number of texts kept in memory by the shared cache
+    pub const DEFAULT_CAPACITY: usize = 128;
+
+    /// Create an in-memory cache holding at most `capacity` texts
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: DashMap::new(),
+            capacity: capacity.max(1),
+            clock: AtomicU64::new(0),
+            disk_dir: None,
+        }
+    }
+
+    /// Also persist entries as JSON files in `dir`, created on first write
+    pub fn with_disk_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.disk_dir = Some(dir.into());
+        self
+    }
+
+    /// The process-wide in-memory cache
+    pub fn shared() -> Arc<AnalysisCache> {
+        Arc::clone(&SHARED_CACHE)
+    }
+
+    /// Return the cached `T` for `key`, or compute and store it
+    ///
+    /// Errors from `compute` are returned and never cached.
+    pub fn get_or_compute<T: Cacheable>(
+        &self,
+        key: CacheKey,
+        compute: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        if let Some(value) = self.get::<T>(key) {
+            debug!("cache hit for {}", key);
+            return Ok(value);
+        }
+        let value = compute()?;
+        self.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Look up a cached `T`, consulting the disk store on a memory miss
+    pub fn get<T: Cacheable>(&self, key: CacheKey) -> Option<T> {
+        if !self.entries.contains_key(&key) {
+            let results = self.load(key)?;
+            self.insert_entry(key, results);
+        }
+        let mut entry = self.entries.get_mut(&key)?;
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        T::slot(&mut entry.results).clone()
+    }
+
+    /// Store `value` for `key`, evicting the least recently used text if full
+    pub fn insert<T: Cacheable>(&self, key: CacheKey, value: T) {
+        if !self.entries.contains_key(&key) {
+            self.insert_entry(key, CachedResults::default());
+        }
+        let results = match self.entries.get_mut(&key) {
+            Some(mut entry) => {
+                *T::slot(&mut entry.results) = Some(value);
+                entry.last_used.store(self.tick(), Ordering::Relaxed);
+                self.disk_dir.as_ref().map(|_| entry.results.clone())
+            }
+            None => return,
+        };
+        if let Some(results) = results {
+            self.store(key, &results);
+        }
+    }
+
+    /// Number of texts held in memory
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop all in-memory entries (the disk store is left untouched)
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn insert_entry(&self, key: CacheKey, results: CachedResults) {
+        while self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|e| e.last_used.load(Ordering::Relaxed))
+                .map(|e| *e.key());
+            match oldest {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.entries.entry(key).or_insert_with(|| Entry {
+            results,
+            last_used: AtomicU64::new(self.tick()),
+        });
+    }
+
+    fn entry_path(dir: &Path, key: CacheKey) -> PathBuf {
+        dir.join(format!("{}.json", key))
+    }
+
+    fn load(&self, key: CacheKey) -> Option<CachedResults> {
+        let path = Self::entry_path(self.disk_dir.as_ref()?, key);
+        let json = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(results) => Some(results),
+            Err(e) => {
+                warn!("Ignoring unreadable cache entry {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Write an entry to disk; failures only cost a future cache miss
+    fn store(&self, key: CacheKey, results: &CachedResults) {
+        let Some(dir) = &self.disk_dir else {
+            return;
+        };
+        let path = Self::entry_path(dir, key);
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        let written = fs::create_dir_all(dir)
+            .and_then(|_| {
+                let json = serde_json::to_vec(results).map_err(std::io::Error::other)?;
+                fs::write(&tmp, json)
+            })
+            .and_then(|_| fs::rename(&tmp, &path));
+        if let Err(e) = written {
+            warn!("Failed to write cache entry {}: {}", path.display(), e);
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(ease: f64) -> ReadabilityMetrics {
+        ReadabilityMetrics {
+            flesch_reading_ease: ease,
+            ..ReadabilityMetrics::default()
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_text_and_effective_config() {
+        let config = Config::default();
+        let key = CacheKey::new("Some text.", None, &config);
+        assert_eq!(key, CacheKey::new("Some text.", None, &config));
+        assert_ne!(key, CacheKey::new("Some text!", None, &config));
+
+        let mut sequential = config.clone();
+        sequential.analysis.parallel_processing = false;
+        sequential.output.color = false;
+        assert_eq!(key, CacheKey::new("Some text.", None, &sequential));
+
+        let mut stricter = config.clone();
+        stricter.thresholds.echo_distance = 5;
+        assert_ne!(key, CacheKey::new("Some text.", None, &stricter));
+    }
+
+    #[test]
+    fn test_key_depends_on_glossary_and_dictionary_files() {
+        let mut config = Config::default();
+        let key = CacheKey::new("Some text.", None, &config);
+        config.glossary.banned.insert("e-mail".to_string(), "email".to_string());
+        assert_ne!(key, CacheKey::new("Some text.", None, &config));
+
+        let dir = tempfile::tempdir().unwrap();
+        let dic = dir.path().join("test.dic");
+        fs::write(dir.path().join("test.aff"), "SET UTF-8\n").unwrap();
+        fs::write(&dic, "1\ntext\n").unwrap();
+        config.spelling.enabled = true;
+        config.spelling.dictionary = Some(dic.clone());
+        let key = CacheKey::new("Some text.", None, &config);
+        assert_eq!(key, CacheKey::new("Some text.", None, &config));
+
+        fs::write(&dic, "2\ntext\nsome\n").unwrap();
+        assert_ne!(key, CacheKey::new("Some text.", None, &config));
+    }
+
+    #[test]
+    fn test_get_or_compute_reuses_results() {
+        let cache = AnalysisCache::new(4);
+        let key = CacheKey::new("text", None, &Config::default());
+
+        let first = cache.get_or_compute(key, || Ok(metrics(50.0))).unwrap();
+        let second = cache
+            .get_or_compute(key, || -> Result<ReadabilityMetrics> { panic!("recomputed") })
+            .unwrap();
+        assert_eq!(first.flesch_reading_ease, second.flesch_reading_ease);
+
+        // Other result kinds for the same text are computed separately
+        assert!(cache.get::<Vec<GrammarIssue>>(key).is_none());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = AnalysisCache::new(2);
+        let config = Config::default();
+        let keys: Vec<CacheKey> = ["a", "b", "c"]
+            .iter()
+            .map(|t| CacheKey::new(t, None, &config))
+            .collect();
+
+        cache.insert(keys[0], metrics(1.0));
+        cache.insert(keys[1], metrics(2.0));
+        assert!(cache.get::<ReadabilityMetrics>(keys[0]).is_some());
+        cache.insert(keys[2], metrics(3.0));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get::<ReadabilityMetrics>(keys[0]).is_some());
+        assert!(cache.get::<ReadabilityMetrics>(keys[1]).is_none());
+    }
+
+    #[test]
+    fn test_disk_store_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = CacheKey::new("persisted", None, &Config::default());
+
+        AnalysisCache::new(4)
+            .with_disk_dir(dir.path())
+            .insert(key, metrics(42.0));
+
+        let reopened = AnalysisCache::new(4).with_disk_dir(dir.path());
+        let cached = reopened.get::<ReadabilityMetrics>(key).unwrap();
+        assert_eq!(cached.flesch_reading_ease, 42.0);
+    }
+}
@@ -1,106 +1,199 @@
 use crate::word_lists::*;
-use crate::dictionaries::count_syllables;
+use crate::dictionaries::{count_syllables, lemma};
 use crate::analysis_reports::*;
-use crate::grammar::SentenceSplitter;
-use crate::error::Result;
+use crate::config::{Config, Glossary, ThresholdSettings};
+use crate::grammar::{HiddenVerbDetector, PosTagger, SentenceSpan, Tag};
+use crate::error::{Deadline, Result};
+use crate::span::{locate_sequence, Lowercased, OffsetIndex};
 use regex::Regex;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
 lazy_static! {
-    static ref ADVERBS: Regex = Regex::new(r"\b\w+ly\b").unwrap();
     static ref ACRONYMS: Regex = Regex::new(r"\b[A-Z]{2,}\b").unwrap();
     static ref FIRST_WORD: Regex = Regex::new(r"^\s*(\w+)").unwrap();
     static ref WORD_PATTERN: Regex = Regex::new(r"\b[\p{L}\p{N}]+(?:[-'][\p{L}\p{N}]+)*\b").unwrap();
 }
 
+/// Lemma a word is counted under for overused words and echoes: "ran" and "running" count as "run"
+///
+/// Glue words and words of three letters or fewer are not counted.
+pub(crate) fn counted_lemma(word: &str) -> Option<String> {
+    if GLUE_WORDS.contains(word) || word.len() <= 3 {
+        return None;
+    }
+    let lemma = lemma(word);
+    (!GLUE_WORDS.contains(lemma.as_str())).then_some(lemma)
+}
+
 pub struct ComprehensiveAnalyzer<'a> {
     text: &'a str,
-    sentences: &'a [String],
+    sentences: &'a [SentenceSpan],
     paragraphs: &'a [String],
     words: &'a [String],
+    thresholds: &'a ThresholdSettings,
+    /// Project terms, whose spelling and capitalization are taken as given
+    glossary: &'a Glossary,
+    parallel: bool,
+    deadline: Deadline,
+    /// Lowercased text for case-insensitive searches, mapped back to `text`
+    lower: Lowercased,
+    index: OffsetIndex<'a>,
+    paragraph_starts: Vec<usize>,
 }
 
 impl<'a> ComprehensiveAnalyzer<'a> {
+    /// Create an analyzer using the thresholds and parallelism settings of `config`
+    ///
+    /// `sentences` are split from `text`, and their `paragraph` indexes `paragraphs`.
     pub fn new(
         text: &'a str,
-        sentences: &'a [String],
+        sentences: &'a [SentenceSpan],
         paragraphs: &'a [String],
         words: &'a [String],
+        config: &'a Config,
     ) -> Self {
         Self {
             text,
             sentences,
             paragraphs,
             words,
+            thresholds: &config.thresholds,
+            glossary: &config.glossary,
+            parallel: cfg!(feature = "parallel") && config.analysis.parallel_processing,
+            deadline: Deadline::none(),
+            lower: Lowercased::new(text),
+            index: OffsetIndex::new(text),
+            paragraph_starts: locate_sequence(text, paragraphs),
+        }
+    }
+
+    /// Run per-sentence and per-paragraph loops on the rayon pool
+    ///
+    /// Has no effect unless the `parallel` feature is enabled. Results keep
+    /// their sequential order either way.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Stop every pass with `TimeoutError` once `deadline` passes
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Map `f` over `items` in order, in parallel when enabled, checking the deadline per item
+    fn map_ordered<T, R, F>(&self, items: &[T], f: F) -> Result<Vec<R>>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(usize, &T) -> R + Sync + Send,
+    {
+        let f = |i: usize, item: &T| -> Result<R> {
+            self.deadline.check()?;
+            Ok(f(i, item))
+        };
+
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            use rayon::prelude::*;
+            return items.par_iter().enumerate().map(|(i, item)| f(i, item)).collect();
+        }
+        items.iter().enumerate().map(|(i, item)| f(i, item)).collect()
+    }
+
+    /// Occurrence of `start..end` in the text
+    fn occurrence(&self, start: usize, end: usize) -> WordOccurrence {
+        WordOccurrence {
+            start_index: start,
+            end_index: end,
+            length: end - start,
+            span: self.index.span(start, end),
+        }
+    }
+
+    /// Occurrence of a match at `start..end` of the lowercased text
+    fn lower_occurrence(&self, start: usize, end: usize) -> WordOccurrence {
+        self.occurrence(self.lower.original(start), self.lower.original(end))
+    }
+
+    /// Start offsets of every match of `needle` in the lowercased text
+    fn find_lower(&self, needle: &str) -> Vec<usize> {
+        let mut positions = Vec::new();
+        let mut start = 0;
+        while let Some(pos) = self.lower.text[start..].find(needle) {
+            let actual_pos = start + pos;
+            positions.push(actual_pos);
+            start = actual_pos + 1;
+            while !self.lower.text.is_char_boundary(start) {
+                start += 1;
+            }
         }
+        positions
     }
 
     // ========== FEATURE 1: STICKY SENTENCES ==========
     pub fn analyze_sticky_sentences(&self) -> Result<StickySentencesReport> {
+        self.deadline.check()?;
         let mut sticky_sentences = Vec::new();
         let mut semi_sticky_sentences = Vec::new();
         let mut total_glue = 0;
         let total_words = self.words.len();
         
-        // Track cumulative position
-        let mut cumulative_pos = 0;
 
-        for (i, sentence) in self.sentences.iter().enumerate() {
-            let sentence_start = cumulative_pos;
-            let sentence_end = cumulative_pos + sentence.len();
-            
+        // Sticky above threshold + 5%, semi-sticky within 5% of the threshold
+        let sticky_above = self.thresholds.sticky_sentence_threshold + 5.0;
+        let semi_sticky_from = self.thresholds.sticky_sentence_threshold - 5.0;
+
+        let classified = self.map_ordered(self.sentences, |i, s| {
+            let sentence = &s.text;
+            let (sentence_start, sentence_end) = (s.span.byte_start, s.span.byte_end);
+
             let words: Vec<String> = WORD_PATTERN
                 .find_iter(&sentence.to_lowercase())
                 .map(|m| m.as_str().to_string())
                 .collect();
 
             if words.is_empty() {
-                cumulative_pos = sentence_end + 1;
-                continue;
+                return None;
             }
 
             let glue_count = words.iter().filter(|w| GLUE_WORDS.contains(w.as_str())).count();
             let glue_percentage = (glue_count as f64 / words.len() as f64) * 100.0;
 
-            // Categorize: >45% = sticky, 35-45% = semi-sticky
-            if glue_percentage > 45.0 {
-                // Sticky sentence
-                let truncated = if sentence.chars().count() > 100 {
-                    let truncated: String = sentence.chars().take(100).collect();
-                    format!("{}...", truncated)
-                } else {
-                    sentence.clone()
-                };
-
-                sticky_sentences.push(StickySentence {
-                    sentence_num: i + 1,
-                    glue_percentage: (glue_percentage * 10.0).round() / 10.0,
-                    sentence: truncated,
-                    start_index: sentence_start,
-                    end_index: sentence_end,
-                    length: sentence.len(),
-                });
-            } else if glue_percentage >= 35.0 && glue_percentage <= 45.0 {
-                // Semi-sticky sentence
-                let truncated = if sentence.chars().count() > 100 {
-                    let truncated: String = sentence.chars().take(100).collect();
-                    format!("{}...", truncated)
-                } else {
-                    sentence.clone()
-                };
-
-                semi_sticky_sentences.push(StickySentence {
+            // Categorize: e.g. >45% = sticky, 35-45% = semi-sticky at the default 40%
+            if glue_percentage < semi_sticky_from {
+                return None;
+            }
+
+            let truncated = if sentence.chars().count() > 100 {
+                let truncated: String = sentence.chars().take(100).collect();
+                format!("{}...", truncated)
+            } else {
+                sentence.clone()
+            };
+
+            Some((
+                glue_percentage > sticky_above,
+                StickySentence {
                     sentence_num: i + 1,
                     glue_percentage: (glue_percentage * 10.0).round() / 10.0,
                     sentence: truncated,
                     start_index: sentence_start,
                     end_index: sentence_end,
                     length: sentence.len(),
-                });
+                    span: s.span,
+                },
+            ))
+        })?;
+
+        for (is_sticky, sentence) in classified.into_iter().flatten() {
+            if is_sticky {
+                sticky_sentences.push(sentence);
+            } else {
+                semi_sticky_sentences.push(sentence);
             }
-            
-            cumulative_pos = sentence_end + 1; // +1 for separator
         }
 
         // Calculate overall glue index
@@ -129,10 +222,11 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 2: PACING REPORT ==========
     pub fn analyze_pacing(&self) -> Result<PacingReport> {
+        self.deadline.check()?;
         let sentence_lengths: Vec<usize> = self
             .sentences
             .iter()
-            .map(|s| WORD_PATTERN.find_iter(s).count())
+            .map(|s| WORD_PATTERN.find_iter(&s.text).count())
             .collect();
 
         let fast_paced = sentence_lengths.iter().filter(|&&l| l < 10).count();
@@ -171,10 +265,11 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 3: SENTENCE LENGTH & VARIETY ==========
     pub fn analyze_sentence_length(&self) -> Result<SentenceLengthReport> {
+        self.deadline.check()?;
         let sentence_lengths: Vec<usize> = self
             .sentences
             .iter()
-            .map(|s| WORD_PATTERN.find_iter(s).count())
+            .map(|s| WORD_PATTERN.find_iter(&s.text).count())
             .collect();
 
         if sentence_lengths.is_empty() {
@@ -209,8 +304,7 @@ impl<'a> ComprehensiveAnalyzer<'a> {
         let very_long: Vec<(usize, usize)> = sentence_lengths
             .iter()
             .enumerate()
-            .filter(|(_, &len)| len > 30)
-            // .filter(|&(_, len)| len > 30)
+            .filter(|(_, &len)| len > self.thresholds.very_long_sentence)
             .map(|(i, &len)| (i + 1, len))
             .collect();
 
@@ -229,17 +323,21 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 4: TRANSITION ANALYSIS ==========
     pub fn analyze_transitions(&self) -> Result<TransitionReport> {
+        self.deadline.check()?;
         let mut all_transitions = Vec::new();
         let mut sentences_with_transitions = 0;
         let mut transition_counts: HashMap<String, usize> = HashMap::new();
         
-        // Track cumulative position for sentences
-        let mut cumulative_pos = 0;
-
-        for (sent_idx, sentence) in self.sentences.iter().enumerate() {
+        for (sent_idx, s) in self.sentences.iter().enumerate() {
+            self.deadline.check()?;
             let sentence_num = sent_idx + 1;
-            let sentence_start = cumulative_pos;
-            let sentence_lower = sentence.to_lowercase();
+            let sentence = &s.text;
+            let sentence_start = s.span.byte_start;
+            let lowered = Lowercased::new(sentence);
+            let sentence_lower = &lowered.text;
+            let located = |pos: usize, len: usize| {
+                (sentence_start + lowered.original(pos), sentence_start + lowered.original(pos + len))
+            };
             let mut found_in_sentence = false;
 
             // Check single-word transitions
@@ -248,20 +346,21 @@ impl<'a> ComprehensiveAnalyzer<'a> {
                 while let Some(pos) = sentence_lower[start..].find(transition) {
                     let actual_pos = start + pos;
                     // Make sure it's a whole word match
-                    let is_word_boundary = (actual_pos == 0 || !sentence_lower.chars().nth(actual_pos - 1).unwrap_or(' ').is_alphanumeric())
-                        && (actual_pos + transition.len() >= sentence_lower.len() 
-                            || !sentence_lower.chars().nth(actual_pos + transition.len()).unwrap_or(' ').is_alphanumeric());
+                    let is_word_boundary = !sentence_lower[..actual_pos].chars().next_back().is_some_and(char::is_alphanumeric)
+                        && !sentence_lower[actual_pos + transition.len()..].chars().next().is_some_and(char::is_alphanumeric);
                     
                     if is_word_boundary {
                         found_in_sentence = true;
                         *transition_counts.entry(transition.to_string()).or_insert(0) += 1;
                         
+                        let (start_index, end_index) = located(actual_pos, transition.len());
                         all_transitions.push(TransitionFound {
                             transition: transition.to_string(),
                             sentence_num,
-                            start_index: sentence_start + actual_pos,
-                            end_index: sentence_start + actual_pos + transition.len(),
-                            length: transition.len(),
+                            start_index,
+                            end_index,
+                            length: end_index - start_index,
+                            span: self.index.span(start_index, end_index),
                         });
                     }
                     start = actual_pos + 1;
@@ -276,12 +375,14 @@ impl<'a> ComprehensiveAnalyzer<'a> {
                     found_in_sentence = true;
                     *transition_counts.entry(phrase.to_string()).or_insert(0) += 1;
                     
+                    let (start_index, end_index) = located(actual_pos, phrase.len());
                     all_transitions.push(TransitionFound {
                         transition: phrase.to_string(),
                         sentence_num,
-                        start_index: sentence_start + actual_pos,
-                        end_index: sentence_start + actual_pos + phrase.len(),
-                        length: phrase.len(),
+                        start_index,
+                        end_index,
+                        length: end_index - start_index,
+                        span: self.index.span(start_index, end_index),
                     });
                     
                     start = actual_pos + 1;
@@ -291,8 +392,6 @@ impl<'a> ComprehensiveAnalyzer<'a> {
             if found_in_sentence {
                 sentences_with_transitions += 1;
             }
-            
-            cumulative_pos = sentence_start + sentence.len() + 1; // +1 for separator
         }
 
         let total_sentences = self.sentences.len();
@@ -318,53 +417,44 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 5: OVERUSED WORDS ==========
     pub fn analyze_overused_words(&self) -> Result<OverusedWordsReport> {
-        let mut word_counts: HashMap<String, Vec<usize>> = HashMap::new();
-        let text_lower = self.text.to_lowercase();
-        
-        // Track each word position
-        let words_with_pos: Vec<_> = WORD_PATTERN
-            .find_iter(&text_lower)
-            .map(|m| (m.as_str().to_string(), m.start(), m.end()))
-            .collect();
-        
-        // Build word -> positions map
-        for (word, start, _end) in words_with_pos {
-            word_counts
-                .entry(word)
-                .or_insert_with(Vec::new)
-                .push(start);
+        self.deadline.check()?;
+        let mut word_counts: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        let mut unique_words = std::collections::HashSet::new();
+
+        // Build lemma -> positions map, so every form of a word counts together
+        for m in WORD_PATTERN.find_iter(&self.lower.text) {
+            unique_words.insert(m.as_str());
+            if let Some(lemma) = counted_lemma(m.as_str()) {
+                word_counts
+                    .entry(lemma)
+                    .or_default()
+                    .push((m.start(), m.end()));
+            }
         }
 
         let total_words = self.words.len();
         let mut overused = Vec::new();
-        let total_unique_words = word_counts.len();
+        let total_unique_words = unique_words.len();
 
         for (word, positions) in word_counts {
             let count = positions.len();
-            if !GLUE_WORDS.contains(word.as_str()) && word.len() > 3 {
-                let frequency = (count as f64 / total_words as f64) * 100.0;
-                if frequency > 0.5 {
-                    let occurrences: Vec<WordOccurrence> = positions
-                        .iter()
-                        .map(|&start| {
-                            let length = word.len();
-                            WordOccurrence {
-                                start_index: start,
-                                end_index: start + length,
-                                length,
-                            }
-                        })
-                        .collect();
-                    
-                    overused.push(OverusedWord {
-                        word: word.clone(),
-                        count,
-                        frequency: (frequency * 100.0).round() / 100.0,
-                        occurrences,
-                    });
-                }
+            let frequency = (count as f64 / total_words as f64) * 100.0;
+            if frequency > self.thresholds.overused_word_threshold {
+                let occurrences: Vec<WordOccurrence> = positions
+                    .iter()
+                    .map(|&(start, end)| self.lower_occurrence(start, end))
+                    .collect();
+
+                overused.push(OverusedWord {
+                    word,
+                    count,
+                    frequency: (frequency * 100.0).round() / 100.0,
+                    occurrences,
+                });
             }
         }
+        // Most used first, ties by lemma, whatever the hash order
+        overused.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
 
         Ok(OverusedWordsReport {
             overused_words: overused,
@@ -374,17 +464,19 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 6: REPEATED PHRASES ==========
     pub fn analyze_repeated_phrases(&self) -> Result<RepeatedPhrasesReport> {
-        let text_lower = self.text.to_lowercase();
-        let mut phrase_positions: HashMap<String, Vec<usize>> = HashMap::new();
+        self.deadline.check()?;
+        let mut phrase_positions: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
         
-        // Extract words with positions
+        // Extract word lemmas with positions, so "made a decision" repeats "make a decision"
         let words_with_pos: Vec<_> = WORD_PATTERN
-            .find_iter(&text_lower)
-            .map(|m| (m.as_str().to_string(), m.start(), m.end()))
+            .find_iter(&self.lower.text)
+            .map(|m| (lemma(m.as_str()), m.start(), m.end()))
             .collect();
 
         // Check 2-word, 3-word, and 4-word phrases
         for phrase_length in 2..=4 {
+            self.deadline.check()?;
+
             // Skip if we don't have enough words for this phrase length
             if words_with_pos.len() < phrase_length {
                 continue;
@@ -397,21 +489,23 @@ impl<'a> ComprehensiveAnalyzer<'a> {
                     .collect();
                 let phrase = phrase_words.join(" ");
                 let start_pos = words_with_pos[i].1;
+                let end_pos = words_with_pos[i + phrase_length - 1].2;
                 
                 phrase_positions
                     .entry(phrase)
                     .or_insert_with(Vec::new)
-                    .push(start_pos);
+                    .push((start_pos, end_pos));
             }
         }
 
         // Filter to only those that appear more than once
-        let mut repeats: Vec<(String, Vec<usize>)> = phrase_positions
+        let mut repeats: Vec<(String, Vec<(usize, usize)>)> = phrase_positions
             .into_iter()
             .filter(|(_, positions)| positions.len() > 1)
             .collect();
 
-        repeats.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+        // Ties go to the phrase found first, so the same ones make the cut every run
+        repeats.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.1[0].cmp(&b.1[0])));
 
         let total_repeated_phrases = repeats.len();
         
@@ -420,14 +514,10 @@ impl<'a> ComprehensiveAnalyzer<'a> {
             .into_iter()
             .take(50)
             .map(|(phrase, positions)| {
-                let phrase_len = phrase.len();
+                // The words may be separated by more than one space in the text
                 let occurrences: Vec<WordOccurrence> = positions
                     .iter()
-                    .map(|&start| WordOccurrence {
-                        start_index: start,
-                        end_index: start + phrase_len,
-                        length: phrase_len,
-                    })
+                    .map(|&(start, end)| self.lower_occurrence(start, end))
                     .collect();
                 
                 RepeatedPhrase {
@@ -446,43 +536,46 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 7: ECHOES ==========
     pub fn analyze_echoes(&self) -> Result<EchoesReport> {
-        let mut echoes = Vec::new();
-
-        for (para_num, paragraph) in self.paragraphs.iter().enumerate() {
-            let para_lower = paragraph.to_lowercase();
+        self.deadline.check()?;
+        let per_paragraph = self.map_ordered(self.paragraphs, |para_num, paragraph| {
+            let mut echoes = Vec::new();
+            let para_start = self.paragraph_starts[para_num];
+            let para_lower = Lowercased::new(paragraph);
             
-            // Extract words with their character positions
+            // Extract words with their positions in the text
             let words_with_pos: Vec<_> = WORD_PATTERN
-                .find_iter(&para_lower)
+                .find_iter(&para_lower.text)
                 .filter(|m| m.as_str().len() >= 4)
-                .map(|m| (m.as_str().to_string(), m.start(), m.end()))
+                .map(|m| {
+                    let start = para_start + para_lower.original(m.start());
+                    let end = para_start + para_lower.original(m.end());
+                    (counted_lemma(m.as_str()), start, end)
+                })
                 .collect();
 
-            // Group by word
+            // Group by lemma, leaving out glue words
             let mut word_positions: HashMap<String, Vec<(usize, usize, usize)>> = HashMap::new();
-            for (word_idx, (word, start, end)) in words_with_pos.iter().enumerate() {
-                word_positions
-                    .entry(word.clone())
-                    .or_insert_with(Vec::new)
-                    .push((word_idx, *start, *end));
+            for (word_idx, (lemma, start, end)) in words_with_pos.iter().enumerate() {
+                if let Some(lemma) = lemma {
+                    word_positions
+                        .entry(lemma.clone())
+                        .or_default()
+                        .push((word_idx, *start, *end));
+                }
             }
 
             for (word, positions) in word_positions.into_iter() {
-                if positions.len() > 1 && !GLUE_WORDS.contains(word.as_str()) {
+                if positions.len() > 1 {
                     for i in 0..positions.len() - 1 {
                         let (idx1, _, _) = positions[i];
                         let (idx2, _, _) = positions[i + 1];
                         let distance = idx2 - idx1;
                         
-                        if distance < 20 {
+                        if distance < self.thresholds.echo_distance {
                             // Create WordOccurrence for each position
                             let char_positions: Vec<WordOccurrence> = positions
                                 .iter()
-                                .map(|(_, start, end)| WordOccurrence {
-                                    start_index: *start,
-                                    end_index: *end,
-                                    length: end - start,
-                                })
+                                .map(|&(_, start, end)| self.occurrence(start, end))
                                 .collect();
                             
                             echoes.push(Echo {
@@ -497,8 +590,13 @@ impl<'a> ComprehensiveAnalyzer<'a> {
                     }
                 }
             }
-        }
 
+            // Keep document order within the paragraph
+            echoes.sort_by_key(|e| e.positions[0].start_index);
+            echoes
+        })?;
+
+        let mut echoes: Vec<Echo> = per_paragraph.into_iter().flatten().collect();
         echoes.sort_by_key(|e| e.distance);
         let total_echoes = echoes.len();
 
@@ -510,6 +608,7 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 8: SENSORY REPORT ==========
     pub fn analyze_sensory_words(&self) -> Result<SensoryReport> {
+        self.deadline.check()?;
         let mut sensory_usage: HashMap<String, usize> = HashMap::new();
         let mut sensory_words_found: HashMap<String, std::collections::HashSet<String>> =
             HashMap::new();
@@ -564,11 +663,11 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 9: DICTION (VAGUE WORDS) ==========
     pub fn analyze_diction(&self) -> Result<DictionReport> {
-        let text_lower = self.text.to_lowercase();
+        self.deadline.check()?;
         let mut vague_positions: HashMap<String, Vec<usize>> = HashMap::new();
 
         // Check single words with positions
-        for mat in WORD_PATTERN.find_iter(&text_lower) {
+        for mat in WORD_PATTERN.find_iter(&self.lower.text) {
             let word = mat.as_str();
             if VAGUE_WORDS.contains(word) {
                 vague_positions
@@ -580,14 +679,11 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
         // Check phrases with positions
         for &phrase in VAGUE_PHRASES.iter() {
-            let mut start = 0;
-            while let Some(pos) = text_lower[start..].find(phrase) {
-                let actual_pos = start + pos;
+            for actual_pos in self.find_lower(phrase) {
                 vague_positions
                     .entry(phrase.to_string())
                     .or_insert_with(Vec::new)
                     .push(actual_pos);
-                start = actual_pos + 1;
             }
         }
 
@@ -598,14 +694,9 @@ impl<'a> ComprehensiveAnalyzer<'a> {
         let mut most_common_vague: Vec<VagueWord> = vague_positions
             .into_iter()
             .map(|(word, positions)| {
-                let word_len = word.len();
                 let occurrences: Vec<WordOccurrence> = positions
                     .iter()
-                    .map(|&start| WordOccurrence {
-                        start_index: start,
-                        end_index: start + word_len,
-                        length: word_len,
-                    })
+                    .map(|&start| self.lower_occurrence(start, start + word.len()))
                     .collect();
                 
                 VagueWord {
@@ -627,29 +718,17 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 10: CLICHÉS ==========
     pub fn analyze_cliches(&self) -> Result<ClichesReport> {
-        let text_lower = self.text.to_lowercase();
+        self.deadline.check()?;
         let mut cliches_found = Vec::new();
 
         for &cliche in CLICHES.iter() {
-            let mut positions = Vec::new();
-            let mut start = 0;
-            
             // Find all occurrences of this cliché
-            while let Some(pos) = text_lower[start..].find(cliche) {
-                let actual_pos = start + pos;
-                positions.push(actual_pos);
-                start = actual_pos + 1;
-            }
+            let positions = self.find_lower(cliche);
             
             if !positions.is_empty() {
-                let cliche_len = cliche.len();
                 let occurrences: Vec<WordOccurrence> = positions
                     .iter()
-                    .map(|&start| WordOccurrence {
-                        start_index: start,
-                        end_index: start + cliche_len,
-                        length: cliche_len,
-                    })
+                    .map(|&start| self.lower_occurrence(start, start + cliche.len()))
                     .collect();
                 
                 cliches_found.push(ClicheFound {
@@ -670,6 +749,7 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 11: CONSISTENCY CHECK ==========
     pub fn analyze_consistency(&self) -> Result<ConsistencyReport> {
+        self.deadline.check()?;
         let mut issues = Vec::new();
         let text_lower = self.text.to_lowercase();
 
@@ -685,8 +765,12 @@ impl<'a> ComprehensiveAnalyzer<'a> {
             }
         }
 
-        // Check for inconsistent hyphenation
+        // Check for inconsistent hyphenation; the glossary rule reports banned forms
         for (word1, word2) in HYPHEN_PATTERNS.iter() {
+            let banned = |word: &str| self.glossary.banned.keys().any(|b| b.eq_ignore_ascii_case(word));
+            if banned(word1) || banned(word2) {
+                continue;
+            }
             let has_word1 = text_lower.contains(word1);
             let has_word2 = text_lower.contains(word2);
             if has_word1 && has_word2 {
@@ -714,6 +798,10 @@ impl<'a> ComprehensiveAnalyzer<'a> {
         }
 
         for (lower_word, variations) in word_variations {
+            // Glossary terms are checked against their listed form instead
+            if self.glossary.knows(&lower_word) {
+                continue;
+            }
             if variations.len() > 1 && lower_word.len() > 3 {
                 let non_capitalized: Vec<_> = variations
                     .iter()
@@ -752,6 +840,7 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 12: ACRONYM REPORT ==========
     pub fn analyze_acronyms(&self) -> Result<AcronymReport> {
+        self.deadline.check()?;
         let acronyms: Vec<String> = ACRONYMS
             .find_iter(self.text)
             .map(|m| m.as_str().to_string())
@@ -777,29 +866,17 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 13: BUSINESS JARGON ==========
     pub fn analyze_business_jargon(&self) -> Result<BusinessJargonReport> {
+        self.deadline.check()?;
         let mut jargon_found = Vec::new();
-        let text_lower = self.text.to_lowercase();
 
         // Check single-word jargon with positions
         for &jargon in BUSINESS_JARGON.iter() {
-            let mut positions = Vec::new();
-            let mut start = 0;
-            
-            while let Some(pos) = text_lower[start..].find(jargon) {
-                let actual_pos = start + pos;
-                positions.push(actual_pos);
-                start = actual_pos + 1;
-            }
+            let positions = self.find_lower(jargon);
             
             if !positions.is_empty() {
-                let jargon_len = jargon.len();
                 let occurrences: Vec<WordOccurrence> = positions
                     .iter()
-                    .map(|&start| WordOccurrence {
-                        start_index: start,
-                        end_index: start + jargon_len,
-                        length: jargon_len,
-                    })
+                    .map(|&start| self.lower_occurrence(start, start + jargon.len()))
                     .collect();
                 
                 jargon_found.push(JargonFound {
@@ -812,24 +889,12 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
         // Check multi-word jargon phrases with positions
         for &phrase in BUSINESS_JARGON_PHRASES.iter() {
-            let mut positions = Vec::new();
-            let mut start = 0;
-            
-            while let Some(pos) = text_lower[start..].find(phrase) {
-                let actual_pos = start + pos;
-                positions.push(actual_pos);
-                start = actual_pos + 1;
-            }
+            let positions = self.find_lower(phrase);
             
             if !positions.is_empty() {
-                let phrase_len = phrase.len();
                 let occurrences: Vec<WordOccurrence> = positions
                     .iter()
-                    .map(|&start| WordOccurrence {
-                        start_index: start,
-                        end_index: start + phrase_len,
-                        length: phrase_len,
-                    })
+                    .map(|&start| self.lower_occurrence(start, start + phrase.len()))
                     .collect();
                 
                 jargon_found.push(JargonFound {
@@ -852,40 +917,52 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 14: COMPLEX PARAGRAPHS ==========
     pub fn analyze_complex_paragraphs(&self) -> Result<ComplexParagraphsReport> {
-        let mut complex_paragraphs = Vec::new();
-        let splitter = SentenceSplitter::default();
-        
-        // Track cumulative position for paragraphs
-        let mut cumulative_pos = 0;
+        self.deadline.check()?;
+        let mut sentence_counts = vec![0; self.paragraphs.len()];
+        for sentence in self.sentences {
+            if let Some(count) = sentence_counts.get_mut(sentence.paragraph) {
+                *count += 1;
+            }
+        }
 
-        for (i, paragraph) in self.paragraphs.iter().enumerate() {
-            let para_start = cumulative_pos;
-            let para_end = cumulative_pos + paragraph.len();
+        let per_paragraph = self.map_ordered(self.paragraphs, |i, paragraph| -> Result<Option<ComplexParagraph>> {
+            let para_start = self.paragraph_starts[i];
+            let para_end = para_start + paragraph.len();
             
-            let sentences = splitter.split(paragraph)?;
+            let sentence_count = sentence_counts[i];
             let words: Vec<String> = WORD_PATTERN
                 .find_iter(&paragraph.to_lowercase())
                 .map(|m| m.as_str().to_string())
                 .collect();
 
-            if !sentences.is_empty() && !words.is_empty() {
-                let avg_sentence_length = words.len() as f64 / sentences.len() as f64;
-                let syllables: usize = words.iter().map(|w| count_syllables(w)).sum();
-                let avg_syllables = syllables as f64 / words.len() as f64;
-
-                if avg_sentence_length > 20.0 && avg_syllables > 1.8 {
-                    complex_paragraphs.push(ComplexParagraph {
-                        paragraph_num: i + 1,
-                        avg_sentence_length: (avg_sentence_length * 10.0).round() / 10.0,
-                        avg_syllables: (avg_syllables * 100.0).round() / 100.0,
-                        start_index: para_start,
-                        end_index: para_end,
-                        length: paragraph.len(),
-                    });
-                }
+            if sentence_count == 0 || words.is_empty() {
+                return Ok(None);
             }
-            
-            cumulative_pos = para_end + 2; // +2 for paragraph separator (usually \n\n)
+
+            let avg_sentence_length = words.len() as f64 / sentence_count as f64;
+            let syllables: usize = words.iter().map(|w| count_syllables(w)).sum();
+            let avg_syllables = syllables as f64 / words.len() as f64;
+
+            if avg_sentence_length > self.thresholds.complex_paragraph_sentence_length
+                && avg_syllables > self.thresholds.complex_paragraph_syllables
+            {
+                Ok(Some(ComplexParagraph {
+                    paragraph_num: i + 1,
+                    avg_sentence_length: (avg_sentence_length * 10.0).round() / 10.0,
+                    avg_syllables: (avg_syllables * 100.0).round() / 100.0,
+                    start_index: para_start,
+                    end_index: para_end,
+                    length: paragraph.len(),
+                    span: self.index.span(para_start, para_end),
+                }))
+            } else {
+                Ok(None)
+            }
+        })?;
+
+        let mut complex_paragraphs = Vec::new();
+        for paragraph in per_paragraph {
+            complex_paragraphs.extend(paragraph?);
         }
 
         let total_paragraphs = self.paragraphs.len();
@@ -906,10 +983,11 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 15: CONJUNCTION STARTS ==========
     pub fn analyze_conjunction_starts(&self) -> Result<ConjunctionStartsReport> {
+        self.deadline.check()?;
         let mut conjunction_starts = 0;
 
         for sentence in self.sentences {
-            if let Some(caps) = FIRST_WORD.captures(&sentence.to_lowercase()) {
+            if let Some(caps) = FIRST_WORD.captures(&sentence.text.to_lowercase()) {
                 if let Some(first_word) = caps.get(1) {
                     if CONJUNCTIONS.contains(first_word.as_str()) {
                         conjunction_starts += 1;
@@ -932,21 +1010,26 @@ impl<'a> ComprehensiveAnalyzer<'a> {
 
     // ========== FEATURE 16-17: ADVERBS & HIDDEN VERBS (Style Report) ==========
     pub fn analyze_style(&self) -> Result<StyleReport> {
-        // Count adverbs
-        let adverb_count = ADVERBS.find_iter(&self.text.to_lowercase()).count();
-
-        // Find hidden verbs
-        let mut hidden_verbs = Vec::new();
-        let text_lower = self.text.to_lowercase();
-        for (noun, verb) in HIDDEN_VERBS.iter() {
-            let count = text_lower.matches(noun).count();
-            if count > 0 {
-                hidden_verbs.push(format!(
-                    "'{}' appears {} time(s) - consider using '{}'",
-                    noun, count, verb
-                ));
-            }
-        }
+        self.deadline.check()?;
+        let tagger = PosTagger::new();
+        let tagged = self.map_ordered(self.sentences, |_, sentence| tagger.tag(&sentence.text))?;
+        let tokens = || tagged.iter().flatten();
+
+        // Count -ly adverbs, leaving out words such as "family" and "early"
+        let adverb_count = tokens()
+            .filter(|t| t.tag == Tag::Adverb && t.lower.ends_with("ly"))
+            .count();
+
+        // Find hidden verbs: "make a decision", "conduct an investigation of"
+        let detector = HiddenVerbDetector::new();
+        let hidden_verbs = self
+            .sentences
+            .iter()
+            .zip(&tagged)
+            .flat_map(|(sentence, tokens)| {
+                detector.detect_in(tokens, &sentence.text, sentence.span.byte_start, &self.index)
+            })
+            .collect();
 
         Ok(StyleReport {
             passive_voice_count: 0, // Will be filled by passive voice detector
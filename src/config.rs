@@ -1,7 +1,8 @@
 use crate::error::{AnalysisError, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -10,6 +11,14 @@ pub struct Config {
     pub thresholds: ThresholdSettings,
     pub features: FeatureToggles,
     pub output: OutputSettings,
+    #[serde(default)]
+    pub rules: RuleSettings,
+    #[serde(default)]
+    pub spelling: SpellingSettings,
+    #[serde(default)]
+    pub glossary: Glossary,
+    #[serde(default)]
+    pub readability: ReadabilitySettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +85,139 @@ pub struct FeatureToggles {
     pub echo_detection: bool,
 }
 
+/// Grammar rules to turn on or off, by rule ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSettings {
+    /// Rules that never run; takes precedence over `enabled`
+    pub disabled: Vec<String>,
+    /// Rules that are off by default but should run
+    pub enabled: Vec<String>,
+}
+
+/// Spell checking against a Hunspell dictionary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpellingSettings {
+    pub enabled: bool,
+    /// Dictionary name, found as `<language>.aff` and `<language>.dic` in the dictionary directories
+    pub language: String,
+    /// Path of a .dic file, with its .aff beside it, to use instead of `language`
+    pub dictionary: Option<PathBuf>,
+    /// Directories searched for `language` before the bundled and system ones
+    pub dictionary_dirs: Vec<PathBuf>,
+    /// Words accepted in addition to the dictionary, matched case-insensitively
+    pub words: Vec<String>,
+    /// Most suggestions offered per misspelled word
+    pub max_suggestions: usize,
+}
+
+impl Default for SpellingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: "en_US".to_string(),
+            dictionary: None,
+            dictionary_dirs: Vec::new(),
+            words: Vec::new(),
+            max_suggestions: 5,
+        }
+    }
+}
+
+/// Grade levels from which a sentence or paragraph counts as hard to read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadabilitySettings {
+    /// Consensus grade level from which a passage is hard
+    pub hard_grade: f64,
+    /// Consensus grade level from which a passage is very hard
+    pub very_hard_grade: f64,
+}
+
+impl Default for ReadabilitySettings {
+    fn default() -> Self {
+        Self {
+            hard_grade: 10.0,
+            very_hard_grade: 14.0,
+        }
+    }
+}
+
+/// Project terms: accepted words, banned terms and required capitalizations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Glossary {
+    /// YAML or TOML file with more entries, relative to the configuration file
+    pub file: Option<PathBuf>,
+    /// Words to accept as spelled, such as product names
+    pub accepted: Vec<String>,
+    /// Banned terms and the terms to use instead, e.g. `e-mail: email`
+    pub banned: BTreeMap<String, String>,
+    /// Terms that must be written exactly as given, e.g. `GitHub`
+    pub capitalization: Vec<String>,
+}
+
+impl Glossary {
+    /// Load a glossary file, as TOML if its extension is `.toml` and YAML otherwise
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| AnalysisError::ConfigError(format!("Failed to read glossary file: {}", e)))?;
+
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content)
+                .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse TOML glossary: {}", e)))
+        } else {
+            serde_yaml::from_str(&content)
+                .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse YAML glossary: {}", e)))
+        }
+    }
+
+    /// Add the entries of `file`, if set, to the ones given inline
+    ///
+    /// A relative path is taken from `base_dir`, the directory of the configuration file.
+    pub fn load_file(&mut self, base_dir: &Path) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let loaded = Self::from_file(base_dir.join(file))?;
+        for word in loaded.accepted {
+            if !self.accepted.contains(&word) {
+                self.accepted.push(word);
+            }
+        }
+        for term in loaded.capitalization {
+            if !self.capitalization.contains(&term) {
+                self.capitalization.push(term);
+            }
+        }
+        self.banned.extend(loaded.banned);
+        Ok(())
+    }
+
+    /// Whether there are terms to enforce
+    pub fn has_rules(&self) -> bool {
+        !self.banned.is_empty() || !self.capitalization.is_empty()
+    }
+
+    /// Words the glossary vouches for: accepted words, required capitalizations and preferred terms
+    pub fn known_words(&self) -> impl Iterator<Item = &str> {
+        self.accepted
+            .iter()
+            .chain(&self.capitalization)
+            .chain(self.banned.values())
+            .flat_map(|term| term.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’'))
+            .filter(|word| !word.is_empty())
+    }
+
+    /// Whether `word` is one of `known_words`, in any case
+    pub fn knows(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.known_words().any(|known| known.to_lowercase() == word)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputSettings {
     pub format: OutputFormat,
@@ -142,6 +284,10 @@ impl Default for Config {
                 color: true,
                 show_progress: true,
             },
+            rules: RuleSettings::default(),
+            spelling: SpellingSettings::default(),
+            glossary: Glossary::default(),
+            readability: ReadabilitySettings::default(),
         }
     }
 }
@@ -152,8 +298,9 @@ impl Config {
         let content = fs::read_to_string(path.as_ref())
             .map_err(|e| AnalysisError::ConfigError(format!("Failed to read config file: {}", e)))?;
         
-        serde_yaml::from_str(&content)
-            .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse YAML config: {}", e)))
+        let config: Self = serde_yaml::from_str(&content)
+            .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse YAML config: {}", e)))?;
+        config.with_glossary_file(path.as_ref())
     }
 
     /// Load configuration from TOML file
@@ -161,8 +308,16 @@ impl Config {
         let content = fs::read_to_string(path.as_ref())
             .map_err(|e| AnalysisError::ConfigError(format!("Failed to read config file: {}", e)))?;
         
-        toml::from_str(&content)
-            .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse TOML config: {}", e)))
+        let config: Self = toml::from_str(&content)
+            .map_err(|e| AnalysisError::ConfigError(format!("Failed to parse TOML config: {}", e)))?;
+        config.with_glossary_file(path.as_ref())
+    }
+
+    /// Merge in the glossary file named by the configuration file at `path`
+    fn with_glossary_file(mut self, path: &Path) -> Result<Self> {
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        self.glossary.load_file(base_dir)?;
+        Ok(self)
     }
 
     /// Save configuration to YAML file
@@ -272,4 +427,22 @@ mod tests {
         
         assert_eq!(config.validation.min_words, loaded.validation.min_words);
     }
+
+    #[test]
+    fn test_glossary_file_is_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut glossary = fs::File::create(dir.path().join("glossary.toml")).unwrap();
+        writeln!(glossary, "accepted = [\"Kubernetes\"]\ncapitalization = [\"GitHub\"]\n\n[banned]\ne-mail = \"email\"").unwrap();
+
+        let mut config = Config::default();
+        config.glossary.file = Some(PathBuf::from("glossary.toml"));
+        config.glossary.accepted.push("Acme".to_string());
+        let path = dir.path().join("config.yaml");
+        config.save_yaml(&path).unwrap();
+
+        let loaded = Config::from_yaml(&path).unwrap();
+        assert_eq!(loaded.glossary.accepted, vec!["Acme", "Kubernetes"]);
+        assert_eq!(loaded.glossary.banned["e-mail"], "email");
+        assert!(loaded.glossary.knows("github"));
+    }
 }
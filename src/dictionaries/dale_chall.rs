@@ -0,0 +1,69 @@
+// Dale-Chall familiar words
+// About 3,000 words most fourth graders know, used by the Dale-Chall and
+// Spache readability formulas
+
+use crate::dictionaries::morphology::{noun_lemma, undouble, verb_lemma};
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+
+lazy_static! {
+    /// The familiar words, lowercased
+    pub static ref DALE_CHALL_WORDS: HashSet<&'static str> =
+        include_str!("dale_chall.txt").split_whitespace().collect();
+}
+
+/// Regular endings added to familiar words, and what they replace
+const ENDINGS: &[(&str, &[&str])] = &[
+    ("'s", &[""]),
+    ("ies", &["y"]),
+    ("es", &["", "e"]),
+    ("s", &[""]),
+    ("ied", &["y"]),
+    ("ed", &["", "e"]),
+    ("ing", &["", "e"]),
+    ("ier", &["y"]),
+    ("iest", &["y"]),
+    ("er", &["", "e"]),
+    ("est", &["", "e"]),
+];
+
+/// Whether `word` counts as familiar under the Dale-Chall rules
+///
+/// Besides the listed words, their regular plurals, possessives, verb forms
+/// and comparatives are familiar ("houses", "stopped", "happier"), as are
+/// irregular forms the morphology tables know ("ran", "children").
+pub fn is_familiar(word: &str) -> bool {
+    let word = word.to_lowercase();
+    if DALE_CHALL_WORDS.contains(word.as_str()) {
+        return true;
+    }
+    let inflected = ENDINGS.iter().any(|&(ending, replacements)| {
+        word.strip_suffix(ending).is_some_and(|stem| {
+            replacements.iter().any(|r| {
+                DALE_CHALL_WORDS.contains(format!("{}{}", stem, r).as_str())
+                    || DALE_CHALL_WORDS.contains(undouble(stem).as_str())
+            })
+        })
+    });
+    inflected
+        || [verb_lemma(&word), noun_lemma(&word)]
+            .into_iter()
+            .flatten()
+            .any(|base| DALE_CHALL_WORDS.contains(base.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_familiar_words_and_their_forms() {
+        assert!(DALE_CHALL_WORDS.len() > 2900);
+        for word in ["house", "Houses", "stopped", "carrying", "happier", "ran", "children", "dog's"] {
+            assert!(is_familiar(word), "{}", word);
+        }
+        for word in ["nominalization", "readability", "algorithm", "ubiquitous"] {
+            assert!(!is_familiar(word), "{}", word);
+        }
+    }
+}
@@ -1,10 +1,19 @@
 pub mod abbreviations;
+pub mod dale_chall;
 pub mod irregular_verbs;
+pub mod morphology;
+pub mod pos_lexicon;
 pub mod syllable_dict;
 
 pub use abbreviations::{is_abbreviation, ends_with_abbreviation, ABBREVIATIONS};
+pub use dale_chall::{is_familiar, DALE_CHALL_WORDS};
 pub use irregular_verbs::{
     is_irregular_past_participle, is_adjective_exception, is_linking_verb,
-    IRREGULAR_PAST_PARTICIPLES, ADJECTIVE_EXCEPTIONS, LINKING_VERBS,
+    IRREGULAR_PAST_PARTICIPLES, IRREGULAR_VERBS, ADJECTIVE_EXCEPTIONS, LINKING_VERBS,
 };
+pub use morphology::{
+    lemma, nominalized_verb, noun_lemma, past_tense, plural, present_tense, verb_forms, verb_lemma,
+    Agreement, Inflection, VerbForms,
+};
+pub use pos_lexicon::{lexicon_tags, is_verb_base, POS_LEXICON};
 pub use syllable_dict::{count_syllables, lookup_syllables, estimate_syllables, SYLLABLE_DICT};
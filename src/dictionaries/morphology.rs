@@ -0,0 +1,567 @@
+use crate::dictionaries::irregular_verbs::IRREGULAR_VERBS;
+use crate::dictionaries::pos_lexicon::{is_verb_base, lexicon_tags};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// Nouns with irregular plurals as (singular, plural)
+const IRREGULAR_PLURALS: &[(&str, &str)] = &[
+    ("child", "children"), ("man", "men"), ("woman", "women"), ("person", "people"),
+    ("foot", "feet"), ("tooth", "teeth"), ("goose", "geese"), ("mouse", "mice"), ("ox", "oxen"),
+    ("criterion", "criteria"), ("phenomenon", "phenomena"), ("analysis", "analyses"),
+    ("basis", "bases"), ("crisis", "crises"), ("thesis", "theses"), ("hypothesis", "hypotheses"),
+    ("diagnosis", "diagnoses"), ("index", "indices"), ("appendix", "appendices"),
+    ("matrix", "matrices"), ("life", "lives"), ("wife", "wives"), ("knife", "knives"),
+    ("leaf", "leaves"), ("half", "halves"), ("self", "selves"), ("shelf", "shelves"),
+    ("thief", "thieves"), ("wolf", "wolves"), ("calf", "calves"),
+];
+
+/// Nominalizations whose verb the suffix rules cannot recover, as (noun, verb)
+const IRREGULAR_NOMINALIZATIONS: &[(&str, &str)] = &[
+    ("explanation", "explain"), ("application", "apply"), ("implication", "imply"),
+    ("repetition", "repeat"), ("production", "produce"), ("reduction", "reduce"),
+    ("introduction", "introduce"), ("provision", "provide"), ("intervention", "intervene"),
+    ("maintenance", "maintain"), ("pronunciation", "pronounce"), ("response", "respond"),
+    ("defense", "defend"), ("complaint", "complain"), ("choice", "choose"), ("proof", "prove"),
+    ("belief", "believe"), ("success", "succeed"), ("loss", "lose"), ("growth", "grow"),
+    ("knowledge", "know"), ("attention", "attend"), ("comparison", "compare"),
+    ("behavior", "behave"), ("delivery", "deliver"), ("discovery", "discover"),
+    ("recovery", "recover"), ("inquiry", "inquire"), ("emphasis", "emphasize"),
+];
+/// Nouns that only look derived from a verb: "comment" is not a "coming"
+const NOT_NOMINALIZATIONS: &[&str] = &["comment", "department", "apartment", "creature", "signal", "moment"];
+
+/// Derivational suffixes of nouns made from verbs, and the verb endings that replace them
+///
+/// Longer suffixes come first. A candidate verb counts only if the lexicon knows it.
+const NOMINAL_SUFFIXES: &[(&str, &[&str])] = &[
+    ("ization", &["ize"]),
+    ("ation", &["ate", "e", ""]),
+    ("mption", &["me"]),
+    ("ption", &["pt", "be"]),
+    ("ution", &["ute", "ve"]),
+    ("ition", &["ite", "e"]),
+    ("ssion", &["ss", "t"]),
+    ("ction", &["ct"]),
+    ("sion", &["de", "d", "se"]),
+    ("tion", &["te", "t"]),
+    ("ment", &["", "e"]),
+    ("ance", &["", "e"]),
+    ("ence", &["", "e"]),
+    ("ysis", &["yze"]),
+    ("ure", &["", "e"]),
+    ("al", &["", "e"]),
+];
+
+/// Verbs of more than one syllable that double their final consonant: "admitted", "occurring"
+const DOUBLING_VERBS: &[&str] = &[
+    "admit", "commit", "permit", "submit", "omit", "emit", "transmit", "occur", "recur", "incur",
+    "prefer", "refer", "confer", "defer", "deter", "transfer", "control", "patrol", "compel",
+    "expel", "propel", "rebel", "regret", "equip",
+];
+
+lazy_static! {
+    /// Irregular past and participle forms, and the inflections of "be", "have" and "do", to their base
+    ///
+    /// Forms that are themselves the base of another irregular verb ("lay",
+    /// "found") are left out.
+    static ref IRREGULAR_LEMMAS: HashMap<&'static str, &'static str> = {
+        let mut map = HashMap::new();
+        for word in ["am", "is", "are", "was", "were", "been", "being", "'m", "'re"] {
+            map.insert(word, "be");
+        }
+        for word in ["has", "had", "having", "'ve"] {
+            map.insert(word, "have");
+        }
+        for word in ["does", "did", "done", "doing"] {
+            map.insert(word, "do");
+        }
+        map.insert("goes", "go");
+        let bases: Vec<&str> = IRREGULAR_VERBS.iter().map(|&(base, _, _)| base).collect();
+        for &(base, past, participle) in IRREGULAR_VERBS.iter() {
+            for form in [past, participle] {
+                if !bases.contains(&form) {
+                    map.entry(form).or_insert(base);
+                }
+            }
+        }
+        map
+    };
+
+    /// Irregular verbs by base as (past, participle)
+    static ref IRREGULAR_FORMS: HashMap<&'static str, (&'static str, &'static str)> =
+        IRREGULAR_VERBS.iter().map(|&(base, past, participle)| (base, (past, participle))).collect();
+
+    /// Irregular plurals to their singular
+    static ref IRREGULAR_SINGULARS: HashMap<&'static str, &'static str> =
+        IRREGULAR_PLURALS.iter().map(|&(singular, plural)| (plural, singular)).collect();
+}
+
+/// Regular inflection of a verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inflection {
+    /// "-s": "walks"
+    ThirdPerson,
+    /// "-ed", both past tense and past participle: "walked"
+    Past,
+    /// "-ing": "walking"
+    PresentParticiple,
+}
+
+/// Person and number of a subject, which a finite verb agrees with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agreement {
+    /// "I"
+    FirstSingular,
+    /// "he", "she", "it" and singular nouns
+    ThirdSingular,
+    /// "we", "you", "they" and plural nouns
+    Plural,
+}
+
+impl Agreement {
+    /// Agreement required by a lowercase subject word, given whether it is a plural noun
+    pub fn of_subject(word: &str, plural_noun: bool) -> Self {
+        match word {
+            "i" | "me" => Agreement::FirstSingular,
+            "we" | "us" | "you" | "they" | "them" => Agreement::Plural,
+            _ if plural_noun => Agreement::Plural,
+            _ => Agreement::ThirdSingular,
+        }
+    }
+}
+
+/// Every inflected form of a verb
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerbForms {
+    pub base: String,
+    /// Present tense, third person singular
+    pub third_person: String,
+    pub past: String,
+    pub past_participle: String,
+    pub present_participle: String,
+}
+
+/// Inflected forms of the verb with base form `base`: "run" → "runs", "ran", "run", "running"
+///
+/// Verbs missing from the irregular table are inflected by spelling rules.
+pub fn verb_forms(base: &str) -> VerbForms {
+    let base = base.to_lowercase();
+    let (past, past_participle) = match base.as_str() {
+        "be" => ("was".to_string(), "been".to_string()),
+        _ => match IRREGULAR_FORMS.get(base.as_str()) {
+            Some(&(past, participle)) => (past.to_string(), participle.to_string()),
+            None => {
+                let past = regular_past(&base);
+                (past.clone(), past)
+            }
+        },
+    };
+    VerbForms {
+        third_person: third_person_singular(&base),
+        present_participle: present_participle(&base),
+        past,
+        past_participle,
+        base,
+    }
+}
+
+/// Third person singular present of a base form: "throw" → "throws", "carry" → "carries"
+pub fn third_person_singular(base: &str) -> String {
+    match base {
+        "be" => return "is".to_string(),
+        "have" => return "has".to_string(),
+        _ => {}
+    }
+    let mut chars = base.chars().rev();
+    let (last, before) = (chars.next(), chars.next());
+    if last == Some('y') && before.is_some_and(|c| !is_vowel(c)) {
+        format!("{}ies", &base[..base.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh", "o"].iter().any(|end| base.ends_with(end)) {
+        format!("{}es", base)
+    } else {
+        format!("{}s", base)
+    }
+}
+
+/// "-ed" form of a regular verb: "stop" → "stopped", "carry" → "carried", "agree" → "agreed"
+fn regular_past(base: &str) -> String {
+    let mut chars = base.chars().rev();
+    let (last, before) = (chars.next(), chars.next());
+    if last == Some('e') {
+        format!("{}d", base)
+    } else if last == Some('y') && before.is_some_and(|c| !is_vowel(c)) {
+        format!("{}ied", &base[..base.len() - 1])
+    } else if doubles_final_consonant(base) {
+        format!("{}{}ed", base, last.unwrap_or_default())
+    } else {
+        format!("{}ed", base)
+    }
+}
+
+/// "-ing" form of a verb: "run" → "running", "make" → "making", "die" → "dying", "see" → "seeing"
+fn present_participle(base: &str) -> String {
+    if let Some(stem) = base.strip_suffix("ie") {
+        return format!("{}ying", stem);
+    }
+    if let Some(stem) = base.strip_suffix('e') {
+        if base != "be" && !stem.ends_with(['e', 'o', 'y']) {
+            return format!("{}ing", stem);
+        }
+    }
+    match base.chars().next_back() {
+        Some(last) if doubles_final_consonant(base) => format!("{}{}ing", base, last),
+        _ => format!("{}ing", base),
+    }
+}
+
+/// Whether a verb doubles its final consonant before "-ed" and "-ing"
+///
+/// One-syllable verbs ending in a single vowel and consonant do ("stop",
+/// "plan"); longer ones only when stressed on the last syllable, which
+/// spelling cannot tell, so those are listed.
+fn doubles_final_consonant(base: &str) -> bool {
+    if DOUBLING_VERBS.contains(&base) {
+        return true;
+    }
+    let chars: Vec<char> = base.chars().collect();
+    match chars.as_slice() {
+        [.., a, b, c] => {
+            vowel_groups(base) == 1
+                && !is_vowel(*a)
+                && is_vowel(*b)
+                && !is_vowel(*c)
+                && !matches!(c, 'w' | 'x' | 'y')
+                && *a != 'q'
+        }
+        _ => false,
+    }
+}
+
+/// Base and inflection of a regularly inflected form of a known verb: "stopped" → ("stop", Past)
+///
+/// Only bases the lexicon knows are accepted, so "thing" and "red" are not
+/// taken for inflections.
+pub fn regular_inflection(word: &str) -> Option<(String, Inflection)> {
+    let known = |stems: Vec<String>| stems.into_iter().find(|s| is_verb_base(s));
+
+    if let Some(stem) = word.strip_suffix("ing") {
+        let mut stems = vec![stem.to_string(), format!("{}e", stem), undouble(stem)];
+        if let Some(s) = stem.strip_suffix('y') {
+            stems.push(format!("{}ie", s));
+        }
+        return known(stems).map(|base| (base, Inflection::PresentParticiple));
+    }
+    if let Some(stem) = word.strip_suffix("ed") {
+        let mut stems = vec![stem.to_string(), format!("{}e", stem), undouble(stem)];
+        if let Some(s) = stem.strip_suffix('i') {
+            stems.push(format!("{}y", s));
+        }
+        return known(stems).map(|base| (base, Inflection::Past));
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        let mut stems = vec![stem.to_string()];
+        if let Some(s) = stem.strip_suffix('e') {
+            stems.push(s.to_string());
+        }
+        if let Some(s) = stem.strip_suffix("ie") {
+            stems.push(format!("{}y", s));
+        }
+        return known(stems).map(|base| (base, Inflection::ThirdPerson));
+    }
+    None
+}
+
+/// "stopp" → "stop", for doubled final consonants before -ed and -ing
+pub(crate) fn undouble(stem: &str) -> String {
+    let bytes = stem.as_bytes();
+    match bytes {
+        [.., a, b] if a == b && b.is_ascii_alphabetic() && !b"aeiouy".contains(b) => stem[..stem.len() - 1].to_string(),
+        _ => stem.to_string(),
+    }
+}
+
+/// Base form of a known verb from any of its forms: "ran", "running", "runs" → "run"
+pub fn verb_lemma(word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    if let Some(&base) = IRREGULAR_LEMMAS.get(word.as_str()) {
+        return Some(base.to_string());
+    }
+    if is_verb_base(&word) {
+        return Some(word);
+    }
+    regular_inflection(&word).map(|(base, _)| base)
+}
+
+/// Base form of the verb with past participle `participle`: "thrown" → "throw", "approved" → "approve"
+///
+/// Unlike `verb_lemma`, this also undoes "-ed" on verbs the lexicon does not
+/// know, so the result for those is a best guess.
+pub fn participle_base(participle: &str) -> Option<String> {
+    let participle = participle.to_lowercase();
+    if let Some(base) = verb_lemma(&participle) {
+        return Some(base);
+    }
+
+    let stem = participle.strip_suffix("ed").filter(|s| s.len() > 1)?;
+    if stem.ends_with('e') {
+        return Some(format!("{}e", stem));
+    }
+    // "carried" → "carry"
+    if let Some(root) = stem.strip_suffix('i') {
+        return Some(format!("{}y", root));
+    }
+    // "stopped" → "stop", but "passed" → "pass"
+    let mut chars = stem.chars().rev();
+    if let (Some(last), Some(before)) = (chars.next(), chars.next()) {
+        if last == before && !is_vowel(last) && !matches!(last, 'l' | 's' | 'f' | 'z') {
+            return Some(stem[..stem.len() - 1].to_string());
+        }
+    }
+    if needs_silent_e(stem) {
+        return Some(format!("{}e", stem));
+    }
+    Some(stem.to_string())
+}
+
+/// Whether a stem left by removing "-ed" lost a silent "e": "us" (used), "creat" (created), "approv" (approved)
+fn needs_silent_e(stem: &str) -> bool {
+    let mut chars = stem.chars().rev();
+    let (last, before) = (chars.next(), chars.next());
+    let single_syllable_closed = vowel_groups(stem) == 1
+        && before.is_some_and(is_vowel)
+        && last.is_some_and(|c| !is_vowel(c) && !matches!(c, 'w' | 'x' | 'y'));
+
+    single_syllable_closed
+        || matches!(last, Some('v' | 'u' | 'z' | 'c'))
+        || ["at", "ir", "ur", "ut", "iz", "yz", "ng"].iter().any(|end| stem.ends_with(end))
+}
+
+/// Form of a verb in the simple present: "sat" → "sits" or "sit", "were" → "are"
+///
+/// `word` may be any form of a known verb.
+pub fn present_tense(word: &str, agreement: Agreement) -> Option<String> {
+    let base = verb_lemma(word)?;
+    Some(match (base.as_str(), agreement) {
+        ("be", Agreement::FirstSingular) => "am".to_string(),
+        ("be", Agreement::ThirdSingular) => "is".to_string(),
+        ("be", Agreement::Plural) => "are".to_string(),
+        (_, Agreement::ThirdSingular) => third_person_singular(&base),
+        _ => base,
+    })
+}
+
+/// Form of a verb in the simple past: "sits" → "sat", "are" → "were"
+///
+/// `word` may be any form of a known verb.
+pub fn past_tense(word: &str, agreement: Agreement) -> Option<String> {
+    let base = verb_lemma(word)?;
+    Some(match (base.as_str(), agreement) {
+        ("be", Agreement::Plural) => "were".to_string(),
+        _ => verb_forms(&base).past,
+    })
+}
+
+/// Plural of a singular noun: "report" → "reports", "child" → "children", "city" → "cities"
+pub fn plural(noun: &str) -> String {
+    let noun = noun.to_lowercase();
+    if let Some(&(_, plural)) = IRREGULAR_PLURALS.iter().find(|&&(singular, _)| singular == noun) {
+        return plural.to_string();
+    }
+    let mut chars = noun.chars().rev();
+    let (last, before) = (chars.next(), chars.next());
+    if last == Some('y') && before.is_some_and(|c| !is_vowel(c)) {
+        format!("{}ies", &noun[..noun.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|end| noun.ends_with(end)) {
+        format!("{}es", noun)
+    } else {
+        format!("{}s", noun)
+    }
+}
+
+/// Singular of a plural noun: "reports" → "report", "children" → "child", "cities" → "city"
+///
+/// Words the lexicon lists as something other than a plural ("news",
+/// "always", "its") and words ending in "ss", "us", "is", "as" or "os" are
+/// not taken for plurals.
+pub fn noun_lemma(word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    if let Some(&singular) = IRREGULAR_SINGULARS.get(word.as_str()) {
+        return Some(singular.to_string());
+    }
+    let listed = lexicon_tags(&word).is_some_and(|tags| !tags.contains(&"NNS"));
+    if listed || word.chars().count() <= 3 || ["ss", "us", "is", "as", "os", "'s"].iter().any(|end| word.ends_with(end)) {
+        return None;
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        return Some(format!("{}y", stem));
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        if ["s", "x", "z", "ch", "sh"].iter().any(|end| stem.ends_with(end)) {
+            return Some(stem.to_string());
+        }
+    }
+    word.strip_suffix('s').map(str::to_string)
+}
+
+/// Dictionary form a word is grouped under: "ran" and "running" → "run", "children" → "child"
+///
+/// Known verb forms come first, then noun plurals; anything else is returned lowercased.
+pub fn lemma(word: &str) -> String {
+    verb_lemma(word)
+        .or_else(|| noun_lemma(word))
+        .unwrap_or_else(|| word.to_lowercase())
+}
+
+/// Verb a noun was derived from: "decision" → "decide", "investigation" → "investigate", "assistance" → "assist"
+///
+/// Plurals are accepted ("decisions"). Only verbs the lexicon knows are returned.
+pub fn nominalized_verb(noun: &str) -> Option<String> {
+    let noun = noun.to_lowercase();
+    let noun = noun_lemma(&noun).unwrap_or(noun);
+    if let Some(&(_, verb)) = IRREGULAR_NOMINALIZATIONS.iter().find(|&&(n, _)| n == noun) {
+        return Some(verb.to_string());
+    }
+    if NOT_NOMINALIZATIONS.contains(&noun.as_str()) {
+        return None;
+    }
+
+    for &(suffix, endings) in NOMINAL_SUFFIXES {
+        let Some(stem) = noun.strip_suffix(suffix) else {
+            continue;
+        };
+        if stem.chars().count() < 3 {
+            continue;
+        }
+        let mut stems = vec![stem.to_string(), undouble(stem)];
+        if let Some(s) = stem.strip_suffix('i') {
+            stems.push(format!("{}y", s));
+        }
+        let candidate = endings
+            .iter()
+            .flat_map(|ending| stems.iter().map(move |stem| format!("{}{}", stem, ending)))
+            .find(|verb| is_verb_base(verb));
+        if candidate.is_some() {
+            return candidate;
+        }
+    }
+    None
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Number of runs of vowels, roughly the syllables of a short stem
+fn vowel_groups(word: &str) -> usize {
+    let mut groups = 0;
+    let mut previous = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous {
+            groups += 1;
+        }
+        previous = vowel;
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verb_forms() {
+        let run = verb_forms("run");
+        assert_eq!(
+            (run.third_person.as_str(), run.past.as_str(), run.past_participle.as_str(), run.present_participle.as_str()),
+            ("runs", "ran", "run", "running")
+        );
+        let stop = verb_forms("stop");
+        assert_eq!((stop.past.as_str(), stop.present_participle.as_str()), ("stopped", "stopping"));
+        assert_eq!(verb_forms("carry").past, "carried");
+        assert_eq!(verb_forms("make").present_participle, "making");
+        assert_eq!(verb_forms("die").present_participle, "dying");
+        assert_eq!(verb_forms("agree").present_participle, "agreeing");
+        assert_eq!(verb_forms("visit").past, "visited");
+        assert_eq!(verb_forms("admit").past, "admitted");
+        assert_eq!(third_person_singular("watch"), "watches");
+        assert_eq!(third_person_singular("play"), "plays");
+    }
+
+    #[test]
+    fn test_lemmas() {
+        for word in ["run", "runs", "ran", "running"] {
+            assert_eq!(lemma(word), "run", "{}", word);
+        }
+        assert_eq!(lemma("was"), "be");
+        assert_eq!(lemma("written"), "write");
+        assert_eq!(lemma("Decided"), "decide");
+        assert_eq!(lemma("children"), "child");
+        assert_eq!(lemma("cities"), "city");
+        assert_eq!(lemma("boxes"), "box");
+        assert_eq!(lemma("news"), "news");
+        assert_eq!(lemma("always"), "always");
+        assert_eq!(lemma("thing"), "thing");
+        assert_eq!(plural("analysis"), "analyses");
+        assert_eq!(plural("church"), "churches");
+    }
+
+    #[test]
+    fn test_participle_bases() {
+        assert_eq!(participle_base("thrown").as_deref(), Some("throw"));
+        assert_eq!(participle_base("found").as_deref(), Some("find"));
+        assert_eq!(participle_base("approved").as_deref(), Some("approve"));
+        assert_eq!(participle_base("carried").as_deref(), Some("carry"));
+        assert_eq!(participle_base("stopped").as_deref(), Some("stop"));
+        assert_eq!(participle_base("passed").as_deref(), Some("pass"));
+        assert_eq!(participle_base("created").as_deref(), Some("create"));
+        assert_eq!(participle_base("used").as_deref(), Some("use"));
+        assert_eq!(participle_base("opened").as_deref(), Some("open"));
+        assert_eq!(participle_base("agreed").as_deref(), Some("agree"));
+        assert!(participle_base("red").is_none());
+    }
+
+    #[test]
+    fn test_tense_changes() {
+        assert_eq!(past_tense("sits", Agreement::ThirdSingular).as_deref(), Some("sat"));
+        assert_eq!(past_tense("are", Agreement::Plural).as_deref(), Some("were"));
+        assert_eq!(past_tense("am", Agreement::FirstSingular).as_deref(), Some("was"));
+        assert_eq!(present_tense("walked", Agreement::ThirdSingular).as_deref(), Some("walks"));
+        assert_eq!(present_tense("walked", Agreement::Plural).as_deref(), Some("walk"));
+        assert_eq!(present_tense("was", Agreement::FirstSingular).as_deref(), Some("am"));
+        assert_eq!(present_tense("had", Agreement::ThirdSingular).as_deref(), Some("has"));
+    }
+
+    #[test]
+    fn test_nominalizations() {
+        let verbs = [
+            ("decision", "decide"),
+            ("decisions", "decide"),
+            ("conclusion", "conclude"),
+            ("investigation", "investigate"),
+            ("examination", "examine"),
+            ("consideration", "consider"),
+            ("implementation", "implement"),
+            ("assistance", "assist"),
+            ("development", "develop"),
+            ("argument", "argue"),
+            ("discussion", "discuss"),
+            ("suggestion", "suggest"),
+            ("description", "describe"),
+            ("assumption", "assume"),
+            ("analysis", "analyze"),
+            ("preference", "prefer"),
+            ("occurrence", "occur"),
+            ("arrival", "arrive"),
+            ("failure", "fail"),
+            ("reliance", "rely"),
+            ("explanation", "explain"),
+        ];
+        for (noun, verb) in verbs {
+            assert_eq!(nominalized_verb(noun).as_deref(), Some(verb), "{}", noun);
+        }
+        for noun in ["nation", "animal", "moment", "sentence", "nature", "station"] {
+            assert_eq!(nominalized_verb(noun), None, "{}", noun);
+        }
+    }
+}
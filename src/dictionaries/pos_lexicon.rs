@@ -0,0 +1,185 @@
+use crate::dictionaries::irregular_verbs::IRREGULAR_VERBS;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// Part-of-speech lexicon entries as (tags, words)
+///
+/// Tags use Penn Treebank codes, plus `CS` for subordinating conjunctions.
+/// A word listed under several entries gets every tag, most likely first, so
+/// an ambiguous word is listed first under its most common reading.
+/// Inflections of the verbs listed under `VB` are derived by the tagger.
+const LEXICON_ENTRIES: &[(&str, &str)] = &[
+    // Closed classes
+    ("DT", "the a an this these those every each either neither some any no all both \
+            another such what whatever which whichever whose my your our their its"),
+    ("DT PRP", "his her"),
+    ("DT CS PRP", "that"),
+    ("DT PRP CD", "one"),
+    ("PRP", "i me you he him she it we us they them myself yourself himself herself \
+             itself ourselves yourselves themselves mine yours hers ours theirs \
+             someone somebody something anyone anybody anything everyone everybody \
+             everything nobody nothing none whoever whomever"),
+    ("PRP CS", "who whom"),
+    ("DT JJ PRP", "many much few several"),
+    ("IN", "of in on at by for with about against between into through during above below \
+            from over under out off within without along across behind beyond near \
+            among around toward towards upon onto via per despite inside outside \
+            unlike except beside besides throughout underneath amid amongst"),
+    ("IN CS", "before after since until till as"),
+    ("IN RB", "up down"),
+    ("IN VB", "like"),
+    ("TO", "to"),
+    ("CC", "and or but nor"),
+    ("CC RB", "so yet"),
+    ("CS", "because although though while whereas if unless whether when whenever \
+            where wherever once than how why"),
+    ("MD", "can could may might must shall should will would ought ca wo sha"),
+    ("CD", "two three four five six seven eight nine ten eleven twelve twenty thirty \
+            forty fifty hundred thousand million billion zero"),
+    ("UH", "oh ah hey hello hi wow yes ok okay please"),
+
+    // Auxiliaries
+    ("VB", "be"),
+    ("VBP", "am are"),
+    ("VBZ", "is has does"),
+    ("VBD", "was were"),
+    ("VBN", "been"),
+    ("VBG", "being having doing"),
+    ("VBP VB", "have do"),
+
+    // Adverbs without the -ly suffix
+    ("RB", "not never always often sometimes usually seldom very too also just still \
+            already even ever here now then soon later today tomorrow yesterday again \
+            almost quite rather perhaps maybe instead else away back ago twice enough \
+            indeed however therefore thus moreover furthermore nevertheless nonetheless \
+            meanwhile otherwise hence anyway somewhat together forward tonight \
+            afterwards everywhere somewhere anywhere nowhere seldom abroad"),
+    ("RB DT", "only"),
+    ("RB PRP", "there"),
+    ("RB JJ", "more most less least well"),
+    ("JJ RB", "far fast hard early late long pretty"),
+    ("JJ NN RB", "right"),
+
+    // Words ending in -ly that are not adverbs
+    ("JJ", "friendly lovely lonely likely unlikely ugly silly holy costly deadly elderly \
+            lively orderly timely curly chilly jolly cowardly scholarly heavenly \
+            melancholy manly worldly homely wobbly smelly prickly sickly"),
+    ("JJ RB", "daily weekly monthly yearly hourly nightly"),
+    ("NN", "family assembly ally belly bully jelly rally anomaly monopoly lily butterfly \
+            italy july ply"),
+    ("NN VB", "supply reply fly"),
+    ("VB", "apply rely comply imply multiply"),
+
+    // Common adjectives
+    ("JJ", "good new old great big small large little high low young other own same \
+            different important able bad best better worse worst sure free full whole \
+            true real clear certain public private strong major local social national \
+            special hot cold warm cool simple easy difficult possible available red blue \
+            green black white dark happy sad nice fine rich poor short tall wide deep \
+            ready similar common recent main serious single entire final general human \
+            natural open happy aware afraid alone alive"),
+    ("JJ NN", "next last first second third"),
+
+    // Nouns that end in -s but are singular, and irregular plurals
+    ("NN", "bus class news series species analysis basis crisis thesis status lens gas \
+            success business address boss glass loss mess dress chess physics \
+            mathematics economics politics ethics campus virus bonus census circus \
+            focus genius radius chaos"),
+    ("NNS", "people children men women feet teeth mice geese criteria phenomena"),
+
+    // Words that are commonly both nouns and verbs
+    ("NN VB", "answer attack attempt call care change charge check claim control cook \
+               copy count cover cross damage dance design doubt drop end experience face \
+               fill fix force form guess hope increase influence kick kiss knock laugh \
+               limit look love mark match matter mind move need notice offer order pass \
+               place plan play present promise push reach record report rest result \
+               return review roll share shout sign smile sound start stay stop study \
+               support surprise talk test touch train travel turn use visit vote wait \
+               walk want watch wish wonder work worry help release process request \
+               access need fund focus bet cost cut hit run set break lead drink catch \
+               fight fall ride swim hold win deal light show sleep speed spread conduct"),
+
+    // Common regular verbs
+    ("VB", "accept achieve add admit affect agree allow announce appear argue arrange \
+            arrive ask assume attend avoid believe belong borrow cause clean close collect \
+            compare complain complete concern confirm connect consider contain continue \
+            correct create cry decide declare defend deliver depend describe destroy \
+            determine develop die disappear discover discuss divide earn encourage enjoy \
+            ensure enter establish examine exist expect explain express extend fail \
+            finish follow gather generate happen hate identify ignore imagine implement \
+            improve include indicate inform insist install intend introduce invent invite \
+            involve join jump kill last learn like listen live manage marry measure \
+            mention miss notice observe obtain occur open organize own perform pick \
+            prefer prepare prevent produce protect provide publish pull raise realize \
+            receive recognize reduce refer reflect refuse relate remain remember remove \
+            repeat replace represent require reveal save seem serve settle solve succeed \
+            suffer suggest suppose thank treat try visit warn wash"),
+    ("VB", "acknowledge adjust adopt analyze approve assess assign assist behave calculate \
+            clarify communicate conclude consume coordinate define demonstrate deny depart \
+            distribute eliminate emphasize enhance evaluate expand facilitate inquire \
+            interpret intervene investigate locate maintain modify negotiate notify operate \
+            participate perform preserve recommend recover resolve respond revise select \
+            specify submit translate utilize validate verify"),
+];
+
+lazy_static! {
+    /// Tags for each known word, most likely first
+    pub static ref POS_LEXICON: HashMap<&'static str, Vec<&'static str>> = {
+        let mut map: HashMap<&'static str, Vec<&'static str>> = HashMap::new();
+        let mut add = |word: &'static str, tag: &'static str| {
+            let tags = map.entry(word).or_default();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        };
+
+        for (tags, words) in LEXICON_ENTRIES {
+            for word in words.split_whitespace() {
+                for tag in tags.split_whitespace() {
+                    add(word, tag);
+                }
+            }
+        }
+
+        for &(base, past, participle) in IRREGULAR_VERBS.iter() {
+            add(base, "VB");
+            add(past, "VBD");
+            add(participle, "VBN");
+        }
+        // Third person forms the tagger cannot derive
+        add("goes", "VBZ");
+
+        map
+    };
+}
+
+/// Tags of a lowercase word, most likely first
+pub fn lexicon_tags(word: &str) -> Option<&'static [&'static str]> {
+    POS_LEXICON.get(word).map(|tags| tags.as_slice())
+}
+
+/// Check if a lowercase word is the base form of a known verb
+pub fn is_verb_base(word: &str) -> bool {
+    lexicon_tags(word).is_some_and(|tags| tags.contains(&"VB"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lexicon_lookup() {
+        assert_eq!(lexicon_tags("the"), Some(&["DT"][..]));
+        assert_eq!(lexicon_tags("work"), Some(&["NN", "VB"][..]));
+        assert_eq!(lexicon_tags("written"), Some(&["VBN"][..]));
+        assert_eq!(lexicon_tags("read"), Some(&["VB", "VBD", "VBN"][..]));
+        assert!(lexicon_tags("xylophonist").is_none());
+    }
+
+    #[test]
+    fn test_verb_bases() {
+        assert!(is_verb_base("decide"));
+        assert!(is_verb_base("throw"));
+        assert!(!is_verb_base("family"));
+    }
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Custom error types for the text analyzer
@@ -42,6 +43,9 @@ pub enum AnalysisError {
 
     #[error("Processing error: {0}")]
     ProcessingError(String),
+
+    #[error("Dictionary error: {0}")]
+    DictionaryError(String),
 }
 
 pub type Result<T> = std::result::Result<T, AnalysisError>;
@@ -116,6 +120,11 @@ impl ValidationConfig {
         Ok(())
     }
 
+    /// Start the analysis deadline for this configuration
+    pub fn deadline(&self) -> Deadline {
+        Deadline::after(self.timeout_seconds)
+    }
+
     /// Validate file path
     pub fn validate_path(&self, path: &PathBuf) -> Result<()> {
         if !path.exists() {
@@ -133,6 +142,45 @@ impl ValidationConfig {
     }
 }
 
+/// Cooperative analysis deadline derived from `timeout_seconds`
+///
+/// Long-running loops call `check` periodically and stop with
+/// `AnalysisError::TimeoutError` once the deadline has passed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Deadline {
+    expires_at: Option<Instant>,
+    timeout_seconds: u64,
+}
+
+impl Deadline {
+    /// Deadline `timeout_seconds` from now (0 disables it)
+    pub fn after(timeout_seconds: u64) -> Self {
+        Self {
+            expires_at: (timeout_seconds > 0)
+                .then(|| Instant::now() + Duration::from_secs(timeout_seconds)),
+            timeout_seconds,
+        }
+    }
+
+    /// A deadline that never expires
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Fail with `TimeoutError` if the deadline has passed
+    pub fn check(&self) -> Result<()> {
+        if self.is_expired() {
+            return Err(AnalysisError::TimeoutError(self.timeout_seconds));
+        }
+        Ok(())
+    }
+}
+
 trait Utf8Validator {
     fn is_utf8_valid(&self) -> bool;
 }
@@ -171,6 +219,19 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_deadline() {
+        assert!(Deadline::none().check().is_ok());
+        assert!(Deadline::after(0).check().is_ok());
+        assert!(Deadline::after(60).check().is_ok());
+
+        let expired = Deadline {
+            expires_at: Some(Instant::now()),
+            timeout_seconds: 5,
+        };
+        assert!(matches!(expired.check(), Err(AnalysisError::TimeoutError(5))));
+    }
+
     #[test]
     fn test_file_size_validation() {
         let config = ValidationConfig {
@@ -0,0 +1,229 @@
+// Fixes
+// Structured edits attached to findings, and applying them to the original text
+
+use crate::span::Span;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// How safe a fix is to apply without review, safest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// Certainly what the writer meant; applied by `--fix`
+    MachineApplicable,
+    /// Probably right, but the writer should confirm
+    MaybeIncorrect,
+}
+
+/// Replace the text at `span` with `replacement` (an empty span inserts)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+/// A suggested correction: edits that are applied together or not at all
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    pub message: String,
+    pub edits: Vec<Edit>,
+    pub applicability: Applicability,
+}
+
+impl Fix {
+    /// Fix made of a single edit
+    pub fn replace(
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            edits: vec![Edit {
+                span,
+                replacement: replacement.into(),
+            }],
+            applicability,
+        }
+    }
+}
+
+/// Text after applying fixes, and the edits that made it
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    pub text: String,
+    /// Number of fixes applied
+    pub applied: usize,
+    /// Number of fixes skipped for overlapping an earlier fix or not fitting the text
+    pub skipped: usize,
+    /// Applied edits, ordered by position in the original text
+    pub edits: Vec<Edit>,
+}
+
+/// Apply `fixes` to `text`, in order, skipping any that conflict with one already accepted
+///
+/// Two edits conflict when their ranges overlap or when both insert at the
+/// same position. Edits must fall on char boundaries of `text`.
+pub fn apply_fixes<'f>(text: &str, fixes: impl IntoIterator<Item = &'f Fix>) -> FixOutcome {
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut applied = 0;
+    let mut skipped = 0;
+
+    for fix in fixes {
+        let fits = fix.edits.iter().all(|e| {
+            let (start, end) = (e.span.byte_start, e.span.byte_end);
+            start <= end && end <= text.len() && text.is_char_boundary(start) && text.is_char_boundary(end)
+        });
+        let conflicts = fix.edits.iter().enumerate().any(|(i, e)| {
+            accepted.iter().chain(&fix.edits[..i]).any(|other| conflict(e, other))
+        });
+        if fits && !conflicts {
+            accepted.extend(fix.edits.iter().cloned());
+            applied += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+
+    accepted.sort_by_key(|e| (e.span.byte_start, e.span.byte_end));
+    let mut fixed = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for edit in &accepted {
+        fixed.push_str(&text[cursor..edit.span.byte_start]);
+        fixed.push_str(&edit.replacement);
+        cursor = edit.span.byte_end;
+    }
+    fixed.push_str(&text[cursor..]);
+
+    FixOutcome {
+        text: fixed,
+        applied,
+        skipped,
+        edits: accepted,
+    }
+}
+
+fn conflict(a: &Edit, b: &Edit) -> bool {
+    let (a, b) = (a.span.byte_range(), b.span.byte_range());
+    (a.start < b.end && b.start < a.end) || (a.is_empty() && b.is_empty() && a.start == b.start)
+}
+
+impl FixOutcome {
+    /// Unified diff (without context lines) from `original` to the fixed text
+    pub fn unified_diff(&self, original: &str, path: &str) -> String {
+        let mut diff = String::new();
+        if self.edits.is_empty() {
+            return diff;
+        }
+        let _ = writeln!(diff, "--- a/{}\n+++ b/{}", path, path);
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(original.match_indices('\n').map(|(i, _)| i + 1))
+            .filter(|&s| s < original.len() || s == 0)
+            .collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&s| s <= offset).saturating_sub(1);
+        let line_start = |line: usize| line_starts.get(line).copied().unwrap_or(original.len());
+
+        // Group edits into runs of touched lines
+        let mut hunks: Vec<(usize, usize, Vec<&Edit>)> = Vec::new();
+        for edit in &self.edits {
+            let first = line_of(edit.span.byte_start);
+            let last = line_of(edit.span.byte_end.saturating_sub(1).max(edit.span.byte_start));
+            match hunks.last_mut() {
+                Some((_, hunk_last, edits)) if first <= *hunk_last + 1 => {
+                    *hunk_last = (*hunk_last).max(last);
+                    edits.push(edit);
+                }
+                _ => hunks.push((first, last, vec![edit])),
+            }
+        }
+
+        let mut delta = 0isize;
+        for (first, last, edits) in hunks {
+            let (old_start, old_end) = (line_start(first), line_start(last + 1));
+            let hunk_delta: isize = edits
+                .iter()
+                .map(|e| e.replacement.len() as isize - e.span.byte_range().len() as isize)
+                .sum();
+            let new_start = (old_start as isize + delta) as usize;
+            let new_end = (old_end as isize + delta + hunk_delta) as usize;
+            delta += hunk_delta;
+
+            let old_lines: Vec<&str> = original[old_start..old_end].split_inclusive('\n').collect();
+            let new_lines: Vec<&str> = self.text[new_start..new_end].split_inclusive('\n').collect();
+            let new_first = self.text[..new_start].matches('\n').count();
+            let _ = writeln!(
+                diff,
+                "@@ -{},{} +{},{} @@",
+                first + 1,
+                old_lines.len(),
+                new_first + 1,
+                new_lines.len()
+            );
+            for (sign, lines) in [('-', &old_lines), ('+', &new_lines)] {
+                for line in lines.iter() {
+                    diff.push(sign);
+                    diff.push_str(line);
+                    if !line.ends_with('\n') {
+                        diff.push_str("\n\\ No newline at end of file\n");
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// `replacement` with its first letter capitalized if `original`'s is
+pub fn match_case(replacement: &str, original: &str) -> String {
+    let capitalized = original.chars().next().is_some_and(char::is_uppercase);
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) if capitalized => first.to_uppercase().chain(chars).collect(),
+        _ => replacement.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(text: &str, start: usize, end: usize, replacement: &str) -> Fix {
+        Fix::replace("test", Span::in_text(text, start, end), replacement, Applicability::MachineApplicable)
+    }
+
+    #[test]
+    fn test_apply_non_overlapping_fixes() {
+        let text = "I seen alot  of them";
+        let fixes = vec![
+            fix(text, 7, 11, "a lot"),
+            fix(text, 11, 13, " "),
+            fix(text, 9, 12, "overlap"),
+            fix(text, 20, 20, "."),
+        ];
+        let outcome = apply_fixes(text, &fixes);
+
+        assert_eq!(outcome.text, "I seen a lot of them.");
+        assert_eq!((outcome.applied, outcome.skipped), (3, 1));
+    }
+
+    #[test]
+    fn test_unified_diff_covers_changed_lines() {
+        let text = "First line is fine.\nSecond has alot\nThird is fine.\n";
+        let start = text.find("alot").unwrap();
+        let fixes = vec![fix(text, start, start + 4, "a lot"), fix(text, start + 4, start + 4, ".")];
+        let outcome = apply_fixes(text, &fixes);
+
+        assert_eq!(
+            outcome.unified_diff(text, "notes.txt"),
+            "--- a/notes.txt\n+++ b/notes.txt\n@@ -2,1 +2,1 @@\n-Second has alot\n+Second has a lot.\n"
+        );
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("a lot", "Alot"), "A lot");
+        assert_eq!(match_case("humankind", "mankind"), "humankind");
+    }
+}
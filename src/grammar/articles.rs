@@ -0,0 +1,150 @@
+// Indefinite articles
+// "a" or "an" by how the next word is pronounced, not how it is spelled
+
+use crate::fix::{match_case, Applicability};
+use crate::grammar::rules::{Rule, RuleCategory, SentenceContext};
+use crate::grammar::{GrammarIssue, GrammarIssueType, Severity, Tag};
+
+/// Beginnings of words spelled with a vowel but said with a consonant: "a user", "a one-time"
+const CONSONANT_SOUND_PREFIXES: &[&str] = &[
+    "uni", "use", "usa", "usu", "uti", "ute", "uri", "ura", "uro", "ubi", "uku", "uto", "unan", "eu", "ewe", "ouija",
+];
+/// Words among the above that start with a vowel sound after all: "an unimportant"
+const VOWEL_SOUND_EXCEPTIONS: &[&str] = &["unim", "unin", "unid", "uniro"];
+/// Beginnings of words spelled with a consonant but said with a vowel: "an hour"
+const VOWEL_SOUND_PREFIXES: &[&str] = &["hour", "honest", "honor", "honour", "heir"];
+/// Acronyms read as words rather than letter by letter: "a NASA mission"
+const WORD_ACRONYMS: &[&str] = &[
+    "nasa", "nato", "unesco", "unicef", "laser", "radar", "scuba", "fema", "osha", "opec", "gif", "jpeg", "ram",
+    "rom", "pin", "sim", "lan", "wan", "ascii", "ansi",
+];
+/// Letters whose names start with a vowel sound: "an F", "an MRI"
+const VOWEL_SOUND_LETTERS: &str = "aefhilmnorsx";
+
+/// Whether `word` starts with a vowel sound, and so takes "an"
+///
+/// Acronyms are read letter by letter unless listed in `WORD_ACRONYMS`;
+/// numbers are read as words ("an 8", "an 11-year-old").
+pub fn starts_with_vowel_sound(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    let Some(first) = lower.chars().next() else {
+        return false;
+    };
+
+    if first.is_ascii_digit() {
+        let digits: String = word.chars().take_while(char::is_ascii_digit).collect();
+        return first == '8' || digits == "11" || digits == "18";
+    }
+    let single_letter = word.chars().filter(|c| c.is_alphabetic()).count() == 1;
+    if single_letter || (is_acronym(word) && !WORD_ACRONYMS.contains(&lower.as_str())) {
+        return VOWEL_SOUND_LETTERS.contains(first);
+    }
+
+    if VOWEL_SOUND_PREFIXES.iter().any(|p| lower.starts_with(p)) {
+        return true;
+    }
+    if lower == "one" || lower == "once" || lower.starts_with("one-") {
+        return false;
+    }
+    if CONSONANT_SOUND_PREFIXES.iter().any(|p| lower.starts_with(p))
+        && !VOWEL_SOUND_EXCEPTIONS.iter().any(|p| lower.starts_with(p))
+    {
+        return false;
+    }
+    "aeiou".contains(first)
+}
+
+/// Whether every letter of a word of two or more letters is a capital, as in "MRI"
+fn is_acronym(word: &str) -> bool {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+    letters.clone().nth(1).is_some() && letters.all(char::is_uppercase)
+}
+
+/// "a" before a vowel sound or "an" before a consonant sound
+pub struct IndefiniteArticle;
+
+impl Rule for IndefiniteArticle {
+    fn id(&self) -> &str {
+        "indefinite-article"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Grammar
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let tokens = ctx.tokens;
+        let mut issues = Vec::new();
+
+        for (i, pair) in tokens.windows(2).enumerate() {
+            let (article, word) = (&pair[0], &pair[1]);
+            // "Plan A or B" names a letter rather than using an article
+            if !article.is_any_of(&["a", "an"]) || (article.text == "A" && i > 0) {
+                continue;
+            }
+            if word.tag == Tag::Punctuation {
+                continue;
+            }
+
+            let expected = if starts_with_vowel_sound(&word.text) { "an" } else { "a" };
+            if article.lower == expected {
+                continue;
+            }
+            let replacement = match_case(expected, &article.text);
+            // Acronyms may be read either way
+            let applicability = if is_acronym(&word.text) { Applicability::MaybeIncorrect } else { Applicability::MachineApplicable };
+
+            let range = article.start..article.end;
+            let mut issue = ctx.issue(
+                self,
+                GrammarIssueType::IndefiniteArticle,
+                format!("Use \"{}\" before \"{}\"", expected, word.text),
+                range.clone(),
+            );
+            issue.fixes.push(ctx.fix(format!("Replace with \"{}\"", replacement), range, replacement, applicability));
+            issues.push(issue);
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::GrammarChecker;
+
+    #[test]
+    fn test_vowel_sounds() {
+        for word in ["apple", "hour", "honest", "MRI", "FBI", "8", "11", "X", "unimportant"] {
+            assert!(starts_with_vowel_sound(word), "{}", word);
+        }
+        for word in ["user", "university", "one-time", "European", "NASA", "house", "12", "U", "unique"] {
+            assert!(!starts_with_vowel_sound(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_articles_follow_pronunciation() {
+        let sentences = vec![
+            "It took a hour to install an user account.".to_string(),
+            "She ordered an MRI and a one-time scan.".to_string(),
+            "A apple a day keeps plan A in reach.".to_string(),
+        ];
+        let issues: Vec<GrammarIssue> = GrammarChecker::new()
+            .check(&sentences)
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.issue_type == GrammarIssueType::IndefiniteArticle)
+            .collect();
+        let fixes: Vec<(usize, &str)> = issues
+            .iter()
+            .map(|i| (i.sentence_num, i.fixes[0].edits[0].replacement.as_str()))
+            .collect();
+
+        assert_eq!(fixes, vec![(1, "an"), (1, "a"), (3, "An")]);
+    }
+}
@@ -1,45 +1,30 @@
-use crate::error::Result;
-use regex::Regex;
-use lazy_static::lazy_static;
-
-lazy_static! {
-    static ref SUBJECT_VERB_PATTERNS: Vec<(Regex, &'static str)> = vec![
-        // Singular subject with plural verb
-        (Regex::new(r"\b(he|she|it)\s+(are|were|have)\b").unwrap(),
-         "Singular subject with plural verb"),
-        (Regex::new(r"\b(the\s+\w+)\s+(are|were|have)\b").unwrap(),
-         "Possible singular subject with plural verb"),
-        
-        // Plural subject with singular verb  
-        (Regex::new(r"\b(they|we|you)\s+(is|was|has)\b").unwrap(),
-         "Plural subject with singular verb"),
-        (Regex::new(r"\b(the\s+\w+s)\s+(is|was|has)\b").unwrap(),
-         "Possible plural subject with singular verb"),
-    ];
-    
-    static ref DOUBLE_NEGATIVE: Regex = Regex::new(
-        r"\b(don't|doesn't|didn't|won't|can't|couldn't|shouldn't|wouldn't)\s+\w+\s+(no|nothing|nobody|never|nowhere|neither)\b"
-    ).unwrap();
-    
-    static ref RUN_ON_INDICATORS: Regex = Regex::new(
-        r",\s+(and|but|or|so)\s+\w+\s+\w+\s+,\s+(and|but|or|so)"
-    ).unwrap();
-    
-    static ref DOUBLE_SPACE: Regex = Regex::new(r"  +").unwrap();
-}
+use crate::config::{Config, RuleSettings};
+use crate::error::{Deadline, Result};
+use crate::fix::Fix;
+use crate::grammar::rules::{Rule, RuleRegistry, SentenceContext};
+use crate::grammar::{GlossaryRule, PosTagger, SentenceSpan};
+use crate::span::{Lowercased, OffsetIndex, Span};
+use crate::spelling::{SpellChecker, SpellingRule};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrammarIssue {
     pub issue_type: GrammarIssueType,
+    /// ID of the rule that reported the issue
+    pub rule_id: String,
     pub message: String,
     pub sentence_num: usize,
     pub severity: Severity,
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
+    /// Suggested corrections, best first
+    pub fixes: Vec<Fix>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GrammarIssueType {
     SubjectVerbAgreement,
     DoubleNegative,
@@ -50,28 +35,37 @@ pub enum GrammarIssueType {
     MissingPunctuation,
     PronoCase,
     VerbTense,
+    /// A word mistaken for a similar one, like "their" for "there"
+    ConfusedWord,
+    /// "a" where "an" is needed, or the other way round
+    IndefiniteArticle,
+    /// The same word twice in a row, as in "the the"
+    RepeatedWord,
+    /// A word the spelling dictionary does not know
+    Spelling,
+    /// A banned or miscapitalized term from the project glossary
+    Glossary,
+    /// Reported by a rule outside this crate
+    Other,
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize)]
 pub enum Severity {
     Low,
     Medium,
     High,
 }
 
+/// Runs the active grammar rules over each sentence
+#[derive(Clone)]
 pub struct GrammarChecker {
-    check_subject_verb: bool,
-    check_double_negatives: bool,
-    check_run_ons: bool,
+    rules: Vec<Arc<dyn Rule>>,
+    tagger: PosTagger,
 }
 
 impl Default for GrammarChecker {
     fn default() -> Self {
-        Self {
-            check_subject_verb: true,
-            check_double_negatives: true,
-            check_run_ons: true,
-        }
+        Self::with_rules(&RuleRegistry::default(), &RuleSettings::default())
     }
 }
 
@@ -80,149 +74,111 @@ impl GrammarChecker {
         Self::default()
     }
 
-    pub fn check(&self, sentences: &[String]) -> Result<Vec<GrammarIssue>> {
-        let mut issues = Vec::new();
-        
-        // Reconstruct approximate positions (best effort without original text)
-        let mut cumulative_pos = 0;
-
-        for (i, sentence) in sentences.iter().enumerate() {
-            let sentence_num = i + 1;
-            let lower = sentence.to_lowercase();
-            let sentence_start = cumulative_pos;
-            let sentence_end = cumulative_pos + sentence.len();
-
-            // Check for double spaces
-            if let Some(mat) = DOUBLE_SPACE.find(sentence) {
-                issues.push(GrammarIssue {
-                    issue_type: GrammarIssueType::DoubleSpace,
-                    message: "Double space detected".to_string(),
-                    sentence_num,
-                    severity: Severity::Low,
-                    start_index: sentence_start + mat.start(),
-                    end_index: sentence_start + mat.end(),
-                    length: mat.end() - mat.start(),
-                });
-            }
-
-            // Check for missing end punctuation
-            if let Some(last_char) = sentence.trim().chars().last() {
-                if !matches!(last_char, '.' | '!' | '?') {
-                    let trimmed_len = sentence.trim().len();
-                    issues.push(GrammarIssue {
-                        issue_type: GrammarIssueType::MissingPunctuation,
-                        message: "Missing end punctuation".to_string(),
-                        sentence_num,
-                        severity: Severity::Medium,
-                        start_index: sentence_start + trimmed_len,
-                        end_index: sentence_end,
-                        length: sentence_end - (sentence_start + trimmed_len),
-                    });
-                }
-            }
+    /// Checker running the built-in rules enabled in `config.rules`, and
+    /// spell checking when `config.spelling` is enabled
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut registry = RuleRegistry::default();
+        if config.spelling.enabled {
+            let checker = SpellChecker::from_settings(&config.spelling)?.with_words(config.glossary.known_words());
+            registry.register(SpellingRule::new(checker));
+        }
+        if config.glossary.has_rules() {
+            registry.register(GlossaryRule::new(&config.glossary));
+        }
+        Ok(Self::with_rules(&registry, &config.rules))
+    }
 
-            // Check for subject-verb agreement
-            if self.check_subject_verb {
-                for (pattern, msg) in SUBJECT_VERB_PATTERNS.iter() {
-                    if let Some(mat) = pattern.find(&lower) {
-                        issues.push(GrammarIssue {
-                            issue_type: GrammarIssueType::SubjectVerbAgreement,
-                            message: msg.to_string(),
-                            sentence_num,
-                            severity: Severity::High,
-                            start_index: sentence_start + mat.start(),
-                            end_index: sentence_start + mat.end(),
-                            length: mat.end() - mat.start(),
-                        });
-                    }
-                }
-            }
+    /// Checker running the rules of `registry` that are active under `settings`
+    pub fn with_rules(registry: &RuleRegistry, settings: &RuleSettings) -> Self {
+        Self {
+            rules: registry.active(settings),
+            tagger: PosTagger::new(),
+        }
+    }
 
-            // Check for double negatives
-            if self.check_double_negatives {
-                if let Some(mat) = DOUBLE_NEGATIVE.find(&lower) {
-                    issues.push(GrammarIssue {
-                        issue_type: GrammarIssueType::DoubleNegative,
-                        message: "Double negative detected".to_string(),
-                        sentence_num,
-                        severity: Severity::High,
-                        start_index: sentence_start + mat.start(),
-                        end_index: sentence_start + mat.end(),
-                        length: mat.end() - mat.start(),
-                    });
-                }
+    /// This checker, also running the rules of `registry` that are active under `settings`
+    ///
+    /// A rule with the ID of one the checker already runs replaces it in place.
+    pub fn with_added_rules(mut self, registry: &RuleRegistry, settings: &RuleSettings) -> Self {
+        for rule in registry.active(settings) {
+            match self.rules.iter_mut().find(|r| r.id() == rule.id()) {
+                Some(existing) => *existing = rule,
+                None => self.rules.push(rule),
             }
+        }
+        self
+    }
 
-            // Check for run-on sentences
-            if self.check_run_ons {
-                if let Some(mat) = RUN_ON_INDICATORS.find(&lower) {
-                    issues.push(GrammarIssue {
-                        issue_type: GrammarIssueType::RunOnSentence,
-                        message: "Possible run-on sentence".to_string(),
-                        sentence_num,
-                        severity: Severity::Medium,
-                        start_index: sentence_start + mat.start(),
-                        end_index: sentence_start + mat.end(),
-                        length: mat.end() - mat.start(),
-                    });
-                }
-            }
+    /// IDs of the rules this checker runs, in order
+    pub fn rule_ids(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|r| r.id())
+    }
 
-            // Check for comma splices
-            if self.check_comma_splice(sentence) {
-                // Find comma position (approximation)
-                if let Some(pos) = sentence.find(',') {
-                    issues.push(GrammarIssue {
-                        issue_type: GrammarIssueType::CommaSplice,
-                        message: "Possible comma splice".to_string(),
-                        sentence_num,
-                        severity: Severity::Medium,
-                        start_index: sentence_start + pos,
-                        end_index: sentence_start + pos + 1,
-                        length: 1,
-                    });
-                } else {
-                    issues.push(GrammarIssue {
-                        issue_type: GrammarIssueType::CommaSplice,
-                        message: "Possible comma splice".to_string(),
-                        sentence_num,
-                        severity: Severity::Medium,
-                        start_index: sentence_start,
-                        end_index: sentence_end,
-                        length: sentence.len(),
-                    });
-                }
-            }
-            
-            // Update cumulative position for next sentence
-            cumulative_pos = sentence_end + 1; // +1 for space/newline separator
-        }
+    pub fn check(&self, sentences: &[String]) -> Result<Vec<GrammarIssue>> {
+        self.check_with_deadline(sentences, &Deadline::none())
+    }
 
-        Ok(issues)
+    /// Check sentences, stopping with `TimeoutError` once `deadline` passes
+    ///
+    /// Without the original text, offsets assume sentences were joined by single spaces.
+    pub fn check_with_deadline(&self, sentences: &[String], deadline: &Deadline) -> Result<Vec<GrammarIssue>> {
+        let text = sentences.join(" ");
+        let index = OffsetIndex::new(&text);
+        let mut start = 0;
+        let spans: Vec<SentenceSpan> = sentences
+            .iter()
+            .map(|sentence| {
+                let span = index.span(start, start + sentence.len());
+                start += sentence.len() + 1;
+                SentenceSpan { text: sentence.clone(), span, paragraph: 0 }
+            })
+            .collect();
+        self.check_spans(&text, &spans, deadline)
     }
 
-    fn check_comma_splice(&self, sentence: &str) -> bool {
-        // Count independent clauses connected by commas
-        let parts: Vec<&str> = sentence.split(',').collect();
-        if parts.len() < 2 {
-            return false;
-        }
+    /// Check sentences split from `text`, with offsets and spans into `text`
+    pub fn check_spans(&self, text: &str, sentences: &[SentenceSpan], deadline: &Deadline) -> Result<Vec<GrammarIssue>> {
+        let mut issues = Vec::new();
+        let index = OffsetIndex::new(text);
 
-        // Check if there are multiple clauses with subjects and verbs
-        let mut clause_count = 0;
-        for part in &parts {
-            if self.has_subject_and_verb(part) {
-                clause_count += 1;
+        let mut first = 0;
+        while first < sentences.len() {
+            let para = sentences[first].paragraph;
+            let paragraph: Vec<&SentenceSpan> = sentences[first..].iter().take_while(|s| s.paragraph == para).collect();
+            let mut analyzed = Vec::with_capacity(paragraph.len());
+            for sentence in &paragraph {
+                deadline.check()?;
+                analyzed.push((Lowercased::new(&sentence.text), self.tagger.tag(&sentence.text)));
             }
-        }
 
-        // If more than 2 independent clauses, likely a comma splice
-        clause_count > 2
-    }
+            let contexts: Vec<SentenceContext> = paragraph
+                .iter()
+                .zip(&analyzed)
+                .enumerate()
+                .map(|(i, (sentence, (lower, tokens)))| {
+                    SentenceContext::new(
+                        &sentence.text,
+                        lower,
+                        first + i + 1,
+                        sentence.paragraph,
+                        sentence.span.byte_start,
+                        tokens,
+                        &index,
+                    )
+                })
+                .collect();
+            for ctx in &contexts {
+                for rule in &self.rules {
+                    issues.extend(rule.check(ctx));
+                }
+            }
+            for rule in &self.rules {
+                issues.extend(rule.check_paragraph(&contexts));
+            }
+            first += paragraph.len();
+        }
 
-    fn has_subject_and_verb(&self, text: &str) -> bool {
-        let words: Vec<&str> = text.split_whitespace().collect();
-        words.len() >= 2 // Simplified check
+        Ok(issues)
     }
 }
 
@@ -240,6 +196,43 @@ mod tests {
         assert!(matches!(issues[0].issue_type, GrammarIssueType::SubjectVerbAgreement));
     }
 
+    #[test]
+    fn test_subject_verb_agreement_uses_tags() {
+        let checker = GrammarChecker::new();
+        let flagged = |text: &str| {
+            checker
+                .check(&[text.to_string()])
+                .unwrap()
+                .iter()
+                .any(|i| i.issue_type == GrammarIssueType::SubjectVerbAgreement)
+        };
+
+        assert!(flagged("The dogs is barking."));
+        assert!(flagged("The report have errors."));
+        // "bus" ends in s but is singular; "Does he have" is a question
+        assert!(!flagged("The bus is late."));
+        assert!(!flagged("Does he have time?"));
+    }
+
+    #[test]
+    fn test_comma_splice() {
+        let checker = GrammarChecker::new();
+        let sentences = vec![
+            "I went home, it was late.".to_string(),
+            "When I got home, it was late.".to_string(),
+            "We bought apples, pears, and plums.".to_string(),
+        ];
+        let issues = checker.check(&sentences).unwrap();
+        let splices: Vec<&GrammarIssue> = issues
+            .iter()
+            .filter(|i| i.issue_type == GrammarIssueType::CommaSplice)
+            .collect();
+
+        assert_eq!(splices.len(), 1);
+        assert_eq!(splices[0].sentence_num, 1);
+        assert_eq!(splices[0].start_index, "I went home".len());
+    }
+
     #[test]
     fn test_double_negative() {
         let checker = GrammarChecker::new();
@@ -268,4 +261,86 @@ mod tests {
         assert!(!issues.is_empty());
         assert!(matches!(issues[0].issue_type, GrammarIssueType::MissingPunctuation));
     }
+
+    fn issues_of(checker: &GrammarChecker, sentences: &[&str], kind: GrammarIssueType) -> Vec<GrammarIssue> {
+        let sentences: Vec<String> = sentences.iter().map(|s| s.to_string()).collect();
+        checker
+            .check(&sentences)
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.issue_type == kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_sentence_fragment() {
+        let checker = GrammarChecker::new();
+        let sentences = [
+            "Because it rained all day.",
+            "A long walk through the quiet park.",
+            "Close the door.",
+            "Because it rained, we stayed inside.",
+            "What a lovely day!",
+        ];
+        let fragments = issues_of(&checker, &sentences, GrammarIssueType::SentenceFragment);
+        let nums: Vec<usize> = fragments.iter().map(|i| i.sentence_num).collect();
+
+        assert_eq!(nums, vec![1, 2]);
+        assert!(fragments[0].message.contains("subordinate clause"));
+        assert!(fragments[1].message.contains("no main verb"));
+    }
+
+    #[test]
+    fn test_pronoun_case() {
+        let checker = GrammarChecker::new();
+        let sentences = ["Me and him went to the store.", "Keep this between you and I.", "She and I agree with them."];
+        let issues = issues_of(&checker, &sentences, GrammarIssueType::PronoCase);
+        let replacements: Vec<(usize, &str)> = issues
+            .iter()
+            .map(|i| (i.sentence_num, i.fixes[0].edits[0].replacement.as_str()))
+            .collect();
+
+        assert_eq!(replacements, vec![(1, "I"), (1, "he"), (2, "me")]);
+    }
+
+    #[test]
+    fn test_tense_shift_within_sentence() {
+        let checker = GrammarChecker::new();
+        let sentences = ["He walked in and sits down.", "She opened the box and smiled."];
+        let issues = issues_of(&checker, &sentences, GrammarIssueType::VerbTense);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].sentence_num, 1);
+        assert_eq!(issues[0].start_index, "He walked in and ".len());
+        assert!(issues[0].message.contains("from past to present"));
+        assert_eq!(issues[0].fixes[0].edits[0].replacement, "sat");
+    }
+
+    #[test]
+    fn test_tense_shift_within_paragraph() {
+        let checker = GrammarChecker::new();
+        let sentences = [
+            "The storm arrived at night.",
+            "Rain hammered the roof for hours.",
+            "We waited in the kitchen.",
+            "The lights flicker and die.",
+        ];
+        let issues = issues_of(&checker, &sentences, GrammarIssueType::VerbTense);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].sentence_num, 4);
+        assert!(issues[0].message.contains("within the paragraph"));
+        assert_eq!(issues[0].fixes[0].edits[0].replacement, "flickered");
+    }
+
+    #[test]
+    fn test_repeated_word() {
+        let checker = GrammarChecker::new();
+        let sentences = ["We checked the the\nthe logs twice.", "By then she had had enough.", "It is what it is is all he said."];
+        let issues = issues_of(&checker, &sentences, GrammarIssueType::RepeatedWord);
+        let ranges: Vec<(usize, usize)> = issues.iter().map(|i| (i.sentence_num, i.start_index)).collect();
+
+        assert_eq!(ranges, vec![(1, "We checked ".len()), (1, "We checked the ".len())]);
+        assert_eq!(issues[1].fixes[0].edits[0].span.byte_range(), "We checked the the".len().."We checked the the\nthe".len());
+    }
 }
@@ -0,0 +1,261 @@
+// Confusable words
+// Pairs like their/there and then/than, each flagged only where the
+// surrounding words show the other one was meant
+
+use crate::dictionaries::pos_lexicon::lexicon_tags;
+use crate::fix::{match_case, Applicability};
+use crate::grammar::rules::{Rule, RuleCategory, SentenceContext};
+use crate::grammar::{GrammarIssue, GrammarIssueType, PosTagger, Severity, Tag, Token};
+
+const BE_FORMS: &[&str] = &["am", "is", "are", "was", "were", "be", "been", "being", "'s", "'re", "'m"];
+const DO_FORMS: &[&str] = &["do", "does", "did"];
+const COMPARATIVES: &[&str] = &["more", "less", "fewer", "rather", "other", "better", "worse", "further"];
+/// Words after which "than" is almost certainly "then"
+const SEQUENCE_WORDS: &[&str] = &["and", "but", ",", "since", "until", "till", "by", "just", "now", "back", "even"];
+
+/// Where a confusable word is taken to be the wrong one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// Wrong anywhere: "could of"
+    Always,
+    /// At the end of a clause: "your welcome."
+    ClauseEnd,
+    /// Before a form of "be": "their is"
+    BeforeBe,
+    /// Before an -ing verb that does not modify a noun: "their going"
+    BeforeGerund,
+    /// Before a noun, when not following a verb: "in there house"
+    BeforeNoun,
+    /// Before a noun that is the subject of the next verb: "it's color is"
+    BeforeSubjectNoun,
+    /// Before an article, "not" or a predicate adjective: "its a", "its cold."
+    BeforePredicate,
+    /// Where a verb goes: after "to", a modal, "do" or a subject pronoun
+    VerbSlot,
+    /// After a determiner or adjective and not taking an object: "the affect of"
+    NounSlot,
+    /// After a form of "be": "is lose"
+    AfterBe,
+    /// After a comparative: "bigger then"
+    AfterComparative,
+    /// Where a time sequence reads: "and than", or at the start
+    Sequence,
+}
+
+/// One direction of a confusable pair
+#[derive(Debug, Clone, Copy)]
+pub struct Confusable {
+    /// Lowercase tokens as the tagger splits them ("it's" is "it", "'s")
+    pub phrase: &'static [&'static str],
+    pub replacement: &'static str,
+    pub cue: Cue,
+}
+
+const fn pair(phrase: &'static [&'static str], replacement: &'static str, cue: Cue) -> Confusable {
+    Confusable { phrase, replacement, cue }
+}
+
+/// Confusable words and phrases, checked in order; the first whose cue holds wins
+pub const CONFUSABLES: &[Confusable] = &[
+    pair(&["could", "of"], "could have", Cue::Always),
+    pair(&["should", "of"], "should have", Cue::Always),
+    pair(&["would", "of"], "would have", Cue::Always),
+    pair(&["must", "of"], "must have", Cue::Always),
+    pair(&["their"], "there", Cue::BeforeBe),
+    pair(&["their"], "they're", Cue::BeforeGerund),
+    pair(&["there"], "their", Cue::BeforeNoun),
+    pair(&["they", "'re"], "their", Cue::BeforeSubjectNoun),
+    pair(&["its"], "it's", Cue::BeforePredicate),
+    pair(&["it", "'s"], "its", Cue::BeforeSubjectNoun),
+    pair(&["your", "welcome"], "you're welcome", Cue::ClauseEnd),
+    pair(&["your"], "you're", Cue::BeforePredicate),
+    pair(&["you", "'re"], "your", Cue::BeforeSubjectNoun),
+    pair(&["effect"], "affect", Cue::VerbSlot),
+    pair(&["effects"], "affects", Cue::VerbSlot),
+    pair(&["affect"], "effect", Cue::NounSlot),
+    pair(&["affects"], "effects", Cue::NounSlot),
+    pair(&["then"], "than", Cue::AfterComparative),
+    pair(&["than"], "then", Cue::Sequence),
+    pair(&["loose"], "lose", Cue::VerbSlot),
+    pair(&["lose"], "loose", Cue::AfterBe),
+];
+
+impl Cue {
+    /// Whether the cue holds for the phrase at `tokens[start..end]`
+    fn holds(self, tokens: &[Token], start: usize, end: usize) -> bool {
+        let tagger = PosTagger::new();
+        let can_be = |token: &Token, tag: Tag| tagger.candidates(&token.lower).contains(&tag);
+        // Closest earlier token that is not an adverb, as in "don't ever lose"
+        let prev = tokens[..start].iter().rev().find(|t| t.tag != Tag::Adverb);
+        let next = tokens.get(end);
+        let after = tokens.get(end + 1);
+        let ends_clause = |t: Option<&Token>| t.map_or(true, |t| t.tag == Tag::Punctuation);
+        let phrase_end = &tokens[end - 1].lower;
+
+        match self {
+            Cue::Always => true,
+            Cue::ClauseEnd => ends_clause(next),
+            Cue::BeforeBe => next.is_some_and(|t| t.is_any_of(&["is", "are", "was", "were"])),
+            Cue::BeforeGerund => {
+                next.is_some_and(|t| t.lower.ends_with("ing") && can_be(t, Tag::Gerund))
+                    && !after.is_some_and(|t| t.tag.is_noun())
+            }
+            Cue::BeforeNoun => {
+                !prev.is_some_and(|t| t.tag.is_verb() || t.tag == Tag::Modal)
+                    && next.is_some_and(|t| matches!(t.tag, Tag::Noun | Tag::PluralNoun))
+                    && !ends_clause(after)
+            }
+            Cue::BeforeSubjectNoun => {
+                next.is_some_and(|t| {
+                    can_be(t, Tag::Noun) && !can_be(t, Tag::Adjective) && !t.lower.ends_with("ing")
+                }) && after.is_some_and(|t| t.tag.is_finite_verb() || t.is_any_of(BE_FORMS))
+            }
+            Cue::BeforePredicate => next.is_some_and(|t| {
+                let predicate_adjective = lexicon_tags(&t.lower) == Some(&["JJ"][..])
+                    && !t.lower.ends_with("est")
+                    && !t.is_any_of(&["own", "other", "same", "only", "better", "worse", "best", "worst"])
+                    && ends_clause(after);
+                let gerund = t.lower.ends_with("ing") && can_be(t, Tag::Gerund) && !after.is_some_and(|a| a.tag.is_noun());
+                t.is_any_of(&["a", "an", "the", "not", "been", "being"]) || predicate_adjective || gerund
+            }),
+            Cue::VerbSlot => {
+                let subjects: &[&str] = if phrase_end.ends_with('s') { &["he", "she", "it"] } else { &["i", "you", "we", "they"] };
+                prev.is_some_and(|t| matches!(t.tag, Tag::To | Tag::Modal) || t.is_any_of(DO_FORMS) || t.is_any_of(subjects))
+            }
+            Cue::NounSlot => {
+                let after_determiner = prev.is_some_and(|t| {
+                    (matches!(t.tag, Tag::Determiner | Tag::Adjective | Tag::Possessive) || t.lower == "side")
+                        && !t.is_any_of(&["this", "that", "these", "those", "which"])
+                });
+                let takes_object = next.is_some_and(|t| {
+                    t.tag.is_noun() || matches!(t.tag, Tag::Determiner | Tag::Pronoun | Tag::Number)
+                });
+                after_determiner && !takes_object
+            }
+            Cue::AfterBe => prev.is_some_and(|t| t.is_any_of(BE_FORMS)),
+            Cue::AfterComparative => prev.is_some_and(|t| t.is_any_of(COMPARATIVES) || is_comparative(&t.lower)),
+            Cue::Sequence => prev.map_or(true, |t| t.is_any_of(SEQUENCE_WORDS)),
+        }
+    }
+}
+
+/// "bigger", "larger", "easier": an adjective with -er
+fn is_comparative(lower: &str) -> bool {
+    let Some(stem) = lower.strip_suffix("er") else {
+        return false;
+    };
+    let mut stems = vec![stem.to_string(), format!("{}e", stem)];
+    if let Some(s) = stem.strip_suffix('i') {
+        stems.push(format!("{}y", s));
+    }
+    let bytes = stem.as_bytes();
+    if let [.., a, b] = bytes {
+        if a == b && b.is_ascii_alphabetic() {
+            stems.push(stem[..stem.len() - 1].to_string());
+        }
+    }
+    stems
+        .iter()
+        .any(|s| lexicon_tags(s).is_some_and(|tags| tags.contains(&"JJ")))
+}
+
+/// Flags confusable words where the context calls for their counterpart
+pub struct ConfusedWords;
+
+impl Rule for ConfusedWords {
+    fn id(&self) -> &str {
+        "confused-words"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Usage
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let tokens = ctx.tokens;
+        let mut issues = Vec::new();
+
+        for start in 0..tokens.len() {
+            let found = CONFUSABLES.iter().find(|c| {
+                let end = start + c.phrase.len();
+                end <= tokens.len()
+                    && tokens[start..end].iter().zip(c.phrase).all(|(t, word)| t.lower == *word)
+                    && c.cue.holds(tokens, start, end)
+            });
+            let Some(confusable) = found else {
+                continue;
+            };
+
+            let range = tokens[start].start..tokens[start + confusable.phrase.len() - 1].end;
+            let original = &ctx.sentence[range.clone()];
+            let replacement = match_case(confusable.replacement, original);
+            let applicability = match confusable.cue {
+                Cue::Always => Applicability::MachineApplicable,
+                _ => Applicability::MaybeIncorrect,
+            };
+
+            let mut issue = ctx.issue(
+                self,
+                GrammarIssueType::ConfusedWord,
+                format!("\"{}\" looks like a mistake for \"{}\"", original, replacement),
+                range.clone(),
+            );
+            issue.fixes.push(ctx.fix(format!("Replace with \"{}\"", replacement), range, replacement, applicability));
+            issues.push(issue);
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::{GrammarChecker, GrammarIssueType};
+
+    /// Replacements suggested for `sentence`
+    fn replacements(sentence: &str) -> Vec<String> {
+        GrammarChecker::new()
+            .check(&[sentence.to_string()])
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.issue_type == GrammarIssueType::ConfusedWord)
+            .map(|i| i.fixes[0].edits[0].replacement.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_confused_words_are_replaced() {
+        assert_eq!(replacements("Their is a problem with the build."), vec!["There"]);
+        assert_eq!(replacements("I think their going to win."), vec!["they're"]);
+        assert_eq!(replacements("They're car was parked outside."), vec!["Their"]);
+        assert_eq!(replacements("It's color is a deep red."), vec!["Its"]);
+        assert_eq!(replacements("Its a good idea."), vec!["It's"]);
+        assert_eq!(replacements("This will effect the schedule."), vec!["affect"]);
+        assert_eq!(replacements("The affect of the change was small."), vec!["effect"]);
+        assert_eq!(replacements("She is taller then her brother."), vec!["than"]);
+        assert_eq!(replacements("We ate and than we left."), vec!["then"]);
+        assert_eq!(replacements("Don't loose your keys."), vec!["lose"]);
+        assert_eq!(replacements("We could of finished sooner."), vec!["could have"]);
+    }
+
+    #[test]
+    fn test_correct_usage_is_not_flagged() {
+        for sentence in [
+            "There is a problem with their build.",
+            "They're going to win.",
+            "It's cold outside.",
+            "The company lost its way.",
+            "You're right about your own plan.",
+            "This affects everyone on the team.",
+            "She is taller than her brother.",
+            "We ate, then we left.",
+            "The knot came loose.",
+            "We could have finished sooner.",
+        ] {
+            assert!(replacements(sentence).is_empty(), "flagged: {}", sentence);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// Glossary
+// Project terms: banned words with their preferred replacements, and
+// names that must keep an exact capitalization
+
+use crate::config::Glossary;
+use crate::fix::{match_case, Applicability};
+use crate::grammar::rules::{Rule, RuleCategory, SentenceContext};
+use crate::grammar::sentence_splitter::verbatim_ranges;
+use crate::grammar::{GrammarIssue, GrammarIssueType, Severity};
+use regex::Regex;
+use std::ops::Range;
+
+/// A glossary term and the text it should read as
+struct Term {
+    /// Matches the term in any case
+    pattern: Regex,
+    /// Exact text to use instead
+    preferred: String,
+    /// Whether the term is banned outright rather than just miscapitalized
+    banned: bool,
+}
+
+/// Flags banned terms and miscapitalized names from the project glossary
+pub struct GlossaryRule {
+    terms: Vec<Term>,
+}
+
+impl GlossaryRule {
+    pub fn new(glossary: &Glossary) -> Self {
+        let term = |text: &str, preferred: &str, banned: bool| Term {
+            pattern: Regex::new(&format!("(?i){}", regex::escape(text))).unwrap(),
+            preferred: preferred.to_string(),
+            banned,
+        };
+        let terms = glossary
+            .banned
+            .iter()
+            .map(|(banned, preferred)| term(banned, preferred, true))
+            .chain(glossary.capitalization.iter().map(|name| term(name, name, false)))
+            .collect();
+        Self { terms }
+    }
+}
+
+/// Whether `range` of `text` is a whole word, not part of a longer one
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let joined = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    !joined(text[..range.start].chars().next_back()) && !joined(text[range.end..].chars().next())
+}
+
+impl Rule for GlossaryRule {
+    fn id(&self) -> &str {
+        "glossary"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Usage
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let sentence = ctx.sentence;
+        // Verbatim text, then each term already reported
+        let mut skipped: Vec<Range<usize>> = verbatim_ranges(sentence);
+        let mut issues = Vec::new();
+
+        for term in &self.terms {
+            for m in term.pattern.find_iter(sentence) {
+                let range = m.range();
+                let found = m.as_str();
+                if found == term.preferred
+                    || !is_whole_word(sentence, &range)
+                    || skipped.iter().any(|r| r.start < range.end && range.start < r.end)
+                {
+                    continue;
+                }
+
+                // Preferred terms in lowercase follow the case of the sentence: "E-mail" becomes "Email"
+                let replacement = if term.banned && !term.preferred.chars().any(char::is_uppercase) {
+                    match_case(&term.preferred, found)
+                } else {
+                    term.preferred.clone()
+                };
+                if found == replacement {
+                    continue;
+                }
+                let message = if term.banned && !found.eq_ignore_ascii_case(&term.preferred) {
+                    format!("Use \"{}\" instead of \"{}\"", replacement, found)
+                } else {
+                    format!("Write \"{}\" as \"{}\"", found, replacement)
+                };
+
+                let mut issue = ctx.issue(self, GrammarIssueType::Glossary, message, range.clone());
+                issue.fixes.push(ctx.fix(
+                    format!("Replace with \"{}\"", replacement),
+                    range.clone(),
+                    replacement,
+                    Applicability::MachineApplicable,
+                ));
+                issues.push(issue);
+                skipped.push(range);
+            }
+        }
+        issues.sort_by_key(|i| i.span.byte_start);
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RuleSettings;
+    use crate::grammar::{GrammarChecker, RuleRegistry};
+
+    fn glossary() -> Glossary {
+        Glossary {
+            banned: [("e-mail", "email"), ("Github", "GitHub"), ("whitelist", "allowlist")]
+                .into_iter()
+                .map(|(banned, preferred)| (banned.to_string(), preferred.to_string()))
+                .collect(),
+            capitalization: vec!["PostgreSQL".to_string(), "macOS".to_string()],
+            ..Glossary::default()
+        }
+    }
+
+    /// Replacements suggested for `sentence`
+    fn replacements(sentence: &str) -> Vec<String> {
+        let mut registry = RuleRegistry::default();
+        registry.register(GlossaryRule::new(&glossary()));
+        GrammarChecker::with_rules(&registry, &RuleSettings::default())
+            .check(&[sentence.to_string()])
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.rule_id == "glossary")
+            .map(|i| i.fixes[0].edits[0].replacement.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_banned_terms_are_replaced() {
+        assert_eq!(replacements("E-mail the team about the Github whitelist."), vec!["Email", "GitHub", "allowlist"]);
+        assert_eq!(replacements("Send an email to the GitHub team."), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_capitalization_is_enforced() {
+        assert_eq!(replacements("We moved from postgresql to PostgreSQL on MacOS."), vec!["PostgreSQL", "macOS"]);
+        assert!(replacements("The postgresqlite fork is untouched.").is_empty());
+    }
+
+    #[test]
+    fn test_verbatim_text_is_skipped() {
+        assert!(replacements("Run `github login` or see https://github.com/e-mail for details.").is_empty());
+    }
+}
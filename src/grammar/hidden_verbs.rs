@@ -0,0 +1,222 @@
+// Hidden verbs
+// A weak verb carrying a nominalization, "make a decision" or "conduct an
+// investigation of", where the verb inside the noun would do: "decide",
+// "investigate"
+
+use crate::dictionaries::morphology::{nominalized_verb, verb_forms, verb_lemma, VerbForms};
+use crate::fix::{match_case, Applicability, Fix};
+use crate::grammar::{PosTagger, Tag, Token};
+use crate::span::{OffsetIndex, Span};
+use serde::{Deserialize, Serialize};
+
+/// Verbs that say little on their own and lean on the noun after them
+const WEAK_VERBS: &[&str] = &[
+    "make", "take", "give", "have", "conduct", "provide", "perform", "offer", "reach", "hold", "do", "undertake",
+];
+/// Weak verbs whose "to" goes with the noun's verb: "provide assistance to the team" → "assist the team"
+const GIVING_VERBS: &[&str] = &["give", "provide", "offer"];
+/// Prepositions the rewrite always drops: "an investigation of the leak" → "investigate the leak"
+const DROPPED_PREPOSITIONS: &[&str] = &["of", "into"];
+/// Auxiliaries that take a past participle: "has made a decision" → "has decided"
+const PERFECT_AUXILIARIES: &[&str] = &["have", "has", "had", "having", "'ve", "'d"];
+
+/// A weak verb and nominalization that could be a single verb
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenVerb {
+    /// The phrase as written: "made a decision", "conduct an investigation of"
+    pub text: String,
+    /// The nominalization: "decision"
+    pub noun: String,
+    /// Base form of the verb hidden in it: "decide"
+    pub verb: String,
+    /// Replaces the phrase with the verb, inflected like the weak verb
+    pub fix: Fix,
+    pub start_index: usize,
+    pub end_index: usize,
+    pub length: usize,
+    pub span: Span,
+}
+
+/// Finds hidden verbs
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HiddenVerbDetector;
+
+impl HiddenVerbDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Hidden verbs in `text`
+    pub fn detect(&self, text: &str) -> Vec<HiddenVerb> {
+        let tokens = PosTagger::new().tag(text);
+        self.detect_in(&tokens, text, 0, &OffsetIndex::new(text))
+    }
+
+    /// Hidden verbs among `tokens`, tagged from `text`
+    ///
+    /// `text` starts at byte `offset` of the document `index` was built for,
+    /// and reported offsets are in that document.
+    pub fn detect_in(&self, tokens: &[Token], text: &str, offset: usize, index: &OffsetIndex) -> Vec<HiddenVerb> {
+        let words: Vec<&Token> = tokens.iter().filter(|t| t.is_word()).collect();
+        let mut found = Vec::new();
+        let mut i = 0;
+
+        while i < words.len() {
+            let Some(hidden) = hidden_verb(text, &words, i) else {
+                i += 1;
+                continue;
+            };
+            let (start, end) = (words[i].start, words[hidden.end - 1].end);
+            let (byte_start, byte_end) = (offset + start, offset + end);
+            let span = index.span(byte_start, byte_end);
+            found.push(HiddenVerb {
+                text: text[start..end].to_string(),
+                noun: words[hidden.noun].text.clone(),
+                fix: Fix::replace(
+                    format!("Use the verb \"{}\"", hidden.rewrite),
+                    span,
+                    hidden.rewrite,
+                    Applicability::MaybeIncorrect,
+                ),
+                verb: hidden.verb,
+                start_index: byte_start,
+                end_index: byte_end,
+                length: byte_end - byte_start,
+                span,
+            });
+            i = hidden.end;
+        }
+        found
+    }
+}
+
+/// A hidden verb starting at a weak verb, by word index
+struct Found {
+    /// Index of the nominalization
+    noun: usize,
+    /// Index just past the phrase
+    end: usize,
+    verb: String,
+    rewrite: String,
+}
+
+/// Hidden verb whose weak verb is word `i`
+fn hidden_verb(text: &str, words: &[&Token], i: usize) -> Option<Found> {
+    let weak = words[i];
+    if !weak.tag.is_verb() {
+        return None;
+    }
+    let weak_base = verb_lemma(&weak.lower).filter(|base| WEAK_VERBS.contains(&base.as_str()))?;
+
+    // "a", "an" or "the", except after "have", which without one usually means owning: "have information"
+    let mut n = i + 1;
+    if words.get(n).is_some_and(|t| t.is_any_of(&["a", "an", "the"])) && joined(text, words, n - 1) {
+        n += 1;
+    } else if weak_base == "have" {
+        return None;
+    }
+    let noun = words.get(n).filter(|t| matches!(t.tag, Tag::Noun | Tag::PluralNoun))?;
+    if !joined(text, words, n - 1) {
+        return None;
+    }
+    // "make a decision maker" is about someone else's decisions
+    if words.get(n + 1).is_some_and(|t| t.tag.is_noun()) && joined(text, words, n) {
+        return None;
+    }
+    let verb = nominalized_verb(&noun.lower).filter(|verb| !WEAK_VERBS.contains(&verb.as_str()))?;
+
+    let mut end = n + 1;
+    if let Some(preposition) = words.get(end).filter(|_| joined(text, words, n)) {
+        let dropped = preposition.is_any_of(DROPPED_PREPOSITIONS)
+            || (preposition.lower == "to" && GIVING_VERBS.contains(&weak_base.as_str()));
+        if dropped {
+            end += 1;
+        }
+    }
+
+    let perfect = i > 0 && words[i - 1].is_any_of(PERFECT_AUXILIARIES) && joined(text, words, i - 1);
+    let inflected = inflect_like(&weak.lower, &verb_forms(&weak_base), &verb_forms(&verb), perfect);
+    Some(Found {
+        noun: n,
+        end,
+        rewrite: match_case(&inflected, &weak.text),
+        verb,
+    })
+}
+
+/// Form of `verb` matching the form `weak` takes of the weak verb: "made" → "decided", "gives" → "considers"
+fn inflect_like(weak: &str, weak_forms: &VerbForms, verb: &VerbForms, perfect: bool) -> String {
+    let form = if weak == weak_forms.base {
+        &verb.base
+    } else if weak == weak_forms.present_participle {
+        &verb.present_participle
+    } else if weak == weak_forms.third_person {
+        &verb.third_person
+    } else if weak == weak_forms.past_participle && (perfect || weak != weak_forms.past) {
+        &verb.past_participle
+    } else {
+        &verb.past
+    };
+    form.clone()
+}
+
+/// Whether only whitespace separates word `i` from the next one
+fn joined(text: &str, words: &[&Token], i: usize) -> bool {
+    words.get(i + 1).is_some_and(|next| text[words[i].end..next.start].trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rewrite and replaced text of each hidden verb in `text`
+    fn rewrites(text: &str) -> Vec<(String, String)> {
+        HiddenVerbDetector::new()
+            .detect(text)
+            .into_iter()
+            .map(|h| (h.text, h.fix.edits[0].replacement.clone()))
+            .collect()
+    }
+
+    fn pair(text: &str, rewrite: &str) -> (String, String) {
+        (text.to_string(), rewrite.to_string())
+    }
+
+    #[test]
+    fn test_weak_verb_phrases_are_rewritten() {
+        assert_eq!(rewrites("We need to make a decision today."), vec![pair("make a decision", "decide")]);
+        assert_eq!(
+            rewrites("The board conducted an investigation of the leak."),
+            vec![pair("conducted an investigation of", "investigated")]
+        );
+        assert_eq!(
+            rewrites("Volunteers provide assistance to new members."),
+            vec![pair("provide assistance to", "assist")]
+        );
+        assert_eq!(
+            rewrites("She has made a recommendation about the budget."),
+            vec![pair("made a recommendation", "recommended")]
+        );
+        assert_eq!(rewrites("Make an analysis of the data."), vec![pair("Make an analysis of", "Analyze")]);
+    }
+
+    #[test]
+    fn test_spans_point_into_the_text() {
+        let text = "First, the committee gives consideration to every request.";
+        let found = HiddenVerbDetector::new().detect(text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(&text[found[0].span.byte_range()], "gives consideration to");
+        assert_eq!((found[0].noun.as_str(), found[0].verb.as_str()), ("consideration", "consider"));
+        assert_eq!(found[0].fix.edits[0].replacement, "considers");
+    }
+
+    #[test]
+    fn test_other_phrases_are_left_alone() {
+        // Nouns on their own, or after verbs that carry meaning
+        assert!(rewrites("Their indecisions cost the team a decision.").is_empty());
+        assert!(rewrites("The decision was made quickly.").is_empty());
+        // Owning rather than doing, and nouns that are not nominalizations
+        assert!(rewrites("We have information about the department.").is_empty());
+        assert!(rewrites("Take a seat and make a comment.").is_empty());
+    }
+}
@@ -1,7 +1,19 @@
 pub mod sentence_splitter;
 pub mod passive_voice;
+pub mod hidden_verbs;
 pub mod checker;
+pub mod rules;
+pub mod pos_tagger;
+pub mod confusables;
+pub mod articles;
+pub mod glossary;
 
-pub use sentence_splitter::SentenceSplitter;
+pub use sentence_splitter::{paragraph_ranges, SentenceSpan, SentenceSplitter};
 pub use passive_voice::{PassiveVoiceDetector, PassiveVoiceMatch};
+pub use hidden_verbs::{HiddenVerb, HiddenVerbDetector};
 pub use checker::{GrammarChecker, GrammarIssue, GrammarIssueType, Severity};
+pub use pos_tagger::{PosTagger, Tag, Token};
+pub use articles::{starts_with_vowel_sound, IndefiniteArticle};
+pub use glossary::GlossaryRule;
+pub use confusables::{Confusable, ConfusedWords, Cue, CONFUSABLES};
+pub use rules::{Rule, RuleCategory, RuleRegistry, SentenceContext};
@@ -1,9 +1,14 @@
 use crate::dictionaries::irregular_verbs::{
     is_irregular_past_participle, is_adjective_exception, is_linking_verb,
 };
-use crate::error::Result;
+use crate::dictionaries::morphology::{participle_base, verb_forms, Agreement, VerbForms};
+use crate::error::{Deadline, Result};
+use crate::fix::{Applicability, Fix};
+use crate::grammar::{PosTagger, Tag, Token};
+use crate::span::{OffsetIndex, Span};
 use regex::Regex;
 use lazy_static::lazy_static;
+use std::ops::Range;
 
 lazy_static! {
     /// Auxiliary verbs that form passive voice
@@ -12,24 +17,45 @@ lazy_static! {
         "get", "gets", "got", "gotten", "getting", // "get" passives
     ];
 
-    /// Regex for regular -ed past participles
-    static ref REGULAR_PARTICIPLE: Regex = Regex::new(r"\b\w+ed\b").unwrap();
-    
     /// Regex for detecting "by" phrases (passive indicator)
     static ref BY_PHRASE: Regex = Regex::new(r"\bby\s+(?:the\s+)?[a-z]+").unwrap();
 }
 
+/// Contracted forms of "be" that can carry a passive: "they're told", "I'm told"
+///
+/// "'s" is left out: before a participle it is usually "has" ("she's finished").
+const CONTRACTED_BE: &[&str] = &["'re", "'m"];
+/// Auxiliaries before "be" or "get": "will be reviewed", "should get fixed"
+const MODALS: &[&str] = &["will", "would", "can", "could", "shall", "should", "may", "might", "must", "'ll", "'d"];
+/// Auxiliaries before "been": "has been written", "it's been decided"
+const HAVE_FORMS: &[&str] = &["have", "has", "had", "having", "'ve", "'s", "'d"];
+/// Auxiliaries before "being": "is being watched"
+const BE_FORMS: &[&str] = &["am", "is", "are", "was", "were", "'s", "'re", "'m"];
+/// Most adverbs allowed inside a verb group, as in "was not always fully tested"
+const MAX_ADVERBS: usize = 3;
+/// Subject pronouns and their object forms, for swapping subject and agent
+const PRONOUN_CASES: &[(&str, &str)] = &[("i", "me"), ("he", "him"), ("she", "her"), ("we", "us"), ("they", "them")];
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PassiveVoiceMatch {
+    /// The whole verb group, from the first auxiliary to the participle
     pub text: String,
     pub confidence: f64, // 0.0 to 1.0
     pub position: usize,
+    /// Auxiliaries of the verb group, lowercased: "was", "has been", "'s been"
     pub auxiliary: String,
     pub participle: String,
     pub has_by_phrase: bool,
+    /// Noun phrase receiving the action: "The ball" in "The ball was thrown by John"
+    pub subject: Option<String>,
+    /// Noun phrase after "by" doing the action: "John"
+    pub agent: Option<String>,
+    /// Active rewrite of the clause from the subject to the agent: "John threw the ball"
+    pub fix: Option<Fix>,
     pub start_index: usize,
     pub end_index: usize,
     pub length: usize,
+    pub span: Span,
 }
 
 pub struct PassiveVoiceDetector {
@@ -51,100 +77,106 @@ impl PassiveVoiceDetector {
 
     /// Detect passive voice in text with confidence scoring
     pub fn detect(&self, text: &str) -> Result<Vec<PassiveVoiceMatch>> {
+        self.detect_with_deadline(text, &Deadline::none())
+    }
+
+    /// Detect passive voice, stopping with `TimeoutError` once `deadline` passes
+    pub fn detect_with_deadline(&self, text: &str, deadline: &Deadline) -> Result<Vec<PassiveVoiceMatch>> {
         let mut matches = Vec::new();
-        let words: Vec<&str> = text.split_whitespace().collect();
-
-        // Build character position map
-        let mut char_positions = Vec::new();
-        let mut search_from = 0;
-        for word in &words {
-            if let Some(pos) = text[search_from..].find(word) {
-                let start = search_from + pos;
-                let end = start + word.len();
-                char_positions.push((start, end));
-                search_from = end;
-            } else {
-                char_positions.push((search_from, search_from));
+        let tokens = PosTagger::new().tag(text);
+        let words: Vec<&Token> = tokens.iter().filter(|t| t.is_word()).collect();
+        let lowered: Vec<&str> = words.iter().map(|t| t.lower.as_str()).collect();
+        let index = OffsetIndex::new(text);
+
+        // `position` counts whitespace-separated words, as it always has
+        let mut word_starts = Vec::new();
+        let mut in_word = false;
+        for (i, c) in text.char_indices() {
+            if !c.is_whitespace() && !in_word {
+                word_starts.push(i);
             }
+            in_word = !c.is_whitespace();
         }
 
-        for i in 0..words.len().saturating_sub(1) {
-            let word1 = words[i].to_lowercase();
-            let word2 = words[i + 1].to_lowercase();
-
-            // Check if word1 is a passive auxiliary
-            if PASSIVE_AUXILIARIES.contains(&word1.as_str()) {
-                // Get the next word (potential participle)
-                let participle = word2.trim_end_matches(|c: char| !c.is_alphanumeric());
-
-                // Check if it's a valid past participle
-                if self.is_likely_past_participle(participle) {
-                    // Calculate confidence
-                    let confidence = self.calculate_confidence(
-                        &word1,
-                        participle,
-                        &words,
-                        i,
-                    );
-
-                    if confidence >= self.min_confidence {
-                        // Check for "by" phrase
-                        let has_by_phrase = self.has_by_phrase_nearby(&words, i);
-
-                        let match_text = format!("{} {}", words[i], words[i + 1]);
-                        let start_index = char_positions[i].0;
-                        let end_index = char_positions[i + 1].1;
-                        let length = end_index - start_index;
-                        
-                        matches.push(PassiveVoiceMatch {
-                            text: match_text,
-                            confidence,
-                            position: i,
-                            auxiliary: word1,
-                            participle: participle.to_string(),
-                            has_by_phrase,
-                            start_index,
-                            end_index,
-                            length,
-                        });
-                    }
-                }
+        for p in 1..words.len() {
+            if p % 1024 == 0 {
+                deadline.check()?;
             }
-        }
-
-        Ok(matches)
-    }
-
-    /// Check if a word is likely a past participle
-    fn is_likely_past_participle(&self, word: &str) -> bool {
-        // Check irregular past participles
-        if is_irregular_past_participle(word) {
-            return true;
-        }
 
-        // Check regular -ed endings
-        if word.ends_with("ed") && word.len() > 3 {
-            // Exclude adjective exceptions
-            if !is_adjective_exception(word) {
-                return true;
+            let participle = words[p];
+            if participle.tag != Tag::PastParticiple {
+                continue;
+            }
+            let Some(group) = verb_group(text, &words, p) else {
+                continue;
+            };
+
+            let passive_auxiliary = &words[group[group.len() - 1]].lower;
+            let compound = group.len() > 1;
+            let confidence =
+                self.calculate_confidence(passive_auxiliary, compound, &participle.lower, &lowered, group[0], p);
+
+            if confidence >= self.min_confidence {
+                let start_index = words[group[0]].start;
+                let end_index = participle.end;
+                let auxiliary: Vec<&str> = group.iter().map(|&i| words[i].lower.as_str()).collect();
+                let subject = subject_phrase(text, &words, group[0]);
+                let agent = agent_phrase(text, &words, p);
+                let fix = match (&subject, &agent) {
+                    (Some(subject), Some(agent)) => active_rewrite(text, &words, &group, p, subject, agent).map(
+                        |(range, rewrite)| {
+                            Fix::replace(
+                                format!("Rewrite in active voice: \"{}\"", rewrite),
+                                index.span(range.start, range.end),
+                                rewrite,
+                                Applicability::MaybeIncorrect,
+                            )
+                        },
+                    ),
+                    _ => None,
+                };
+                let phrase = |range: Option<Range<usize>>| range.map(|r| text[words[r.start].start..words[r.end - 1].end].to_string());
+
+                matches.push(PassiveVoiceMatch {
+                    text: text[start_index..end_index].to_string(),
+                    confidence,
+                    position: word_starts.partition_point(|&s| s <= start_index).saturating_sub(1),
+                    auxiliary: auxiliary.join(" "),
+                    participle: participle.lower.clone(),
+                    has_by_phrase: self.has_by_phrase_nearby(&lowered, p),
+                    subject: phrase(subject),
+                    agent: phrase(agent),
+                    fix,
+                    start_index,
+                    end_index,
+                    length: end_index - start_index,
+                    span: index.span(start_index, end_index),
+                });
             }
         }
 
-        false
+        Ok(matches)
     }
 
     /// Calculate confidence score for passive voice detection
+    ///
+    /// `auxiliary` is the one right before the participle, `compound` tells
+    /// whether others come before it, and the verb group starts at word
+    /// `group_start` and ends with the participle at word `position`.
     fn calculate_confidence(
         &self,
         auxiliary: &str,
+        compound: bool,
         participle: &str,
         words: &[&str],
+        group_start: usize,
         position: usize,
     ) -> f64 {
         let mut confidence: f64 = 0.5; // Base confidence
 
-        // Higher confidence for typical passive auxiliaries
-        if matches!(auxiliary, "was" | "were" | "been") {
+        // Higher confidence for typical passive auxiliaries, and for perfect,
+        // progressive and modal passives ("has been", "is being", "will be")
+        if matches!(auxiliary, "was" | "were" | "been" | "being") || compound {
             confidence += 0.2;
         }
 
@@ -169,11 +201,10 @@ impl PassiveVoiceDetector {
         }
 
         // Check context: is there a subject before the auxiliary?
-        if position > 0 {
-            let prev_word = words[position - 1].to_lowercase();
+        if group_start > 0 {
             // Common subjects increase confidence
             if matches!(
-                prev_word.as_str(),
+                words[group_start - 1],
                 "the" | "a" | "an" | "this" | "that" | "these" | "those" | "my" | "your" | "his" | "her" | "its" | "our" | "their"
             ) {
                 confidence += 0.1;
@@ -184,18 +215,17 @@ impl PassiveVoiceDetector {
         confidence.max(0.0).min(1.0)
     }
 
-    /// Check if there's a "by" phrase nearby (within 5 words)
+    /// Check if there's a "by" phrase within 5 words after the participle at `position`
     fn has_by_phrase_nearby(&self, words: &[&str], position: usize) -> bool {
-        let start = position + 2;
-        let end = (position + 7).min(words.len());
+        let start = position + 1;
+        let end = (position + 6).min(words.len());
 
         for i in start..end {
-            if words[i].to_lowercase() == "by" {
+            if words[i] == "by" {
                 // Make sure it's not just "by" at the end
                 if i + 1 < words.len() {
-                    let next_word = words[i + 1].to_lowercase();
                     // Check if followed by a noun-like word
-                    if !matches!(next_word.as_str(), "the" | "a" | "an") {
+                    if !matches!(words[i + 1], "the" | "a" | "an") {
                         return true;
                     }
                     // Check "by the/a/an NOUN"
@@ -225,6 +255,211 @@ impl PassiveVoiceDetector {
     }
 }
 
+/// Word indexes of the auxiliaries of a passive ending with the participle at `participle`
+///
+/// The auxiliary right before the participle must be a form of "be" or "get";
+/// modals, "have" and "be" before it join the group ("will have been
+/// written", "is being watched"). Adverbs and "not" may sit between any two
+/// of them, but punctuation may not.
+fn verb_group(text: &str, words: &[&Token], participle: usize) -> Option<Vec<usize>> {
+    let auxiliary = previous_in_group(text, words, participle)?;
+    let lower = words[auxiliary].lower.as_str();
+    if !PASSIVE_AUXILIARIES.contains(&lower) && !CONTRACTED_BE.contains(&lower) {
+        return None;
+    }
+
+    let mut group = vec![auxiliary];
+    loop {
+        let first = group[0];
+        let before: &[&str] = match words[first].lower.as_str() {
+            "be" | "get" | "have" | "'ve" => MODALS,
+            "been" | "gotten" => HAVE_FORMS,
+            "being" | "getting" => BE_FORMS,
+            _ => break,
+        };
+        match previous_in_group(text, words, first) {
+            Some(i) if words[i].is_any_of(before) => group.insert(0, i),
+            _ => break,
+        }
+    }
+    Some(group)
+}
+
+/// Index of the closest word before `i` that is not an adverb, if only whitespace and adverbs separate them
+fn previous_in_group(text: &str, words: &[&Token], i: usize) -> Option<usize> {
+    let mut j = i;
+    for _ in 0..=MAX_ADVERBS {
+        if j == 0 || !text[words[j - 1].end..words[j].start].trim().is_empty() {
+            return None;
+        }
+        j -= 1;
+        if words[j].tag != Tag::Adverb {
+            return Some(j);
+        }
+    }
+    None
+}
+
+/// Word range of the noun phrase ending right before word `group_start`, if it opens the clause
+///
+/// Phrases after a preposition or conjunction are left out: in "the cover
+/// of the book was torn" the subject is not "the book".
+fn subject_phrase(text: &str, words: &[&Token], group_start: usize) -> Option<Range<usize>> {
+    let head = group_start.checked_sub(1)?;
+    if !words[head].tag.is_nominal() || !joined(text, words, head) {
+        return None;
+    }
+    let mut start = head;
+    if words[head].tag.is_noun() {
+        while start > 0 && joined(text, words, start - 1) && is_modifier(words[start - 1].tag) {
+            start -= 1;
+        }
+    }
+    let opens_clause = start == 0
+        || !joined(text, words, start - 1)
+        || matches!(words[start - 1].tag, Tag::Subordinator | Tag::Adverb)
+        || words[start - 1].tag.is_verb();
+    opens_clause.then_some(start..group_start)
+}
+
+/// Word range of the noun phrase after a "by" that directly follows the participle at `participle`
+///
+/// Coordinated agents ("by John and Mary") are left out.
+fn agent_phrase(text: &str, words: &[&Token], participle: usize) -> Option<Range<usize>> {
+    let by = participle + 1;
+    if by + 1 >= words.len() || words[by].lower != "by" || !joined(text, words, participle) || !joined(text, words, by) {
+        return None;
+    }
+    let start = by + 1;
+    let mut end = start + 1;
+    if words[start].tag != Tag::Pronoun {
+        while end < words.len() && joined(text, words, end - 1) && is_modifier(words[end].tag) {
+            end += 1;
+        }
+        while end > start && !words[end - 1].tag.is_noun() {
+            end -= 1;
+        }
+        if end == start {
+            return None;
+        }
+    }
+    let coordinated = end < words.len() && joined(text, words, end - 1) && words[end].tag == Tag::Conjunction;
+    (!coordinated).then_some(start..end)
+}
+
+/// Tags that can make up a noun phrase before its head
+fn is_modifier(tag: Tag) -> bool {
+    tag.is_noun() || matches!(tag, Tag::Determiner | Tag::Adjective | Tag::Number | Tag::Possessive)
+}
+
+/// Whether only whitespace separates word `i` from the next one
+fn joined(text: &str, words: &[&Token], i: usize) -> bool {
+    words.get(i + 1).is_some_and(|next| text[words[i].end..next.start].trim().is_empty())
+}
+
+/// Byte range from the subject to the end of the agent, and its active rewrite
+///
+/// The verb takes its tense from the auxiliaries and agrees with the agent:
+/// "was thrown" becomes "threw", "is reviewed" becomes "reviews" or
+/// "review", "has been written" becomes "has written" or "have written" and
+/// "will be sent" becomes "will send". Negated and progressive passives are
+/// left alone, since they need "do" or a gerund.
+fn active_rewrite(
+    text: &str,
+    words: &[&Token],
+    group: &[usize],
+    participle: usize,
+    subject: &Range<usize>,
+    agent: &Range<usize>,
+) -> Option<(Range<usize>, String)> {
+    let auxiliaries: Vec<&str> = group.iter().map(|&i| words[i].lower.as_str()).collect();
+    let adverbs: Vec<&Token> = (group[0]..participle)
+        .filter(|i| !group.contains(i))
+        .map(|i| words[i])
+        .collect();
+    if adverbs.iter().any(|t| t.is_any_of(&["not", "n't"])) {
+        return None;
+    }
+
+    // The agent is the new subject, which the verb agrees with
+    let head = words[agent.end - 1];
+    let agreement = Agreement::of_subject(&head.lower, head.tag == Tag::PluralNoun);
+    let participle_text = &words[participle].lower;
+    let forms = verb_forms(&participle_base(participle_text)?);
+    let present = |forms: &VerbForms| match agreement {
+        Agreement::ThirdSingular => forms.third_person.clone(),
+        _ => forms.base.clone(),
+    };
+
+    let verb = match auxiliaries.as_slice() {
+        ["was" | "were" | "got"] => forms.past.clone(),
+        ["am" | "is" | "are" | "'re" | "'m" | "get" | "gets"] => present(&forms),
+        [modal, "be" | "get"] if MODALS.contains(modal) => format!("{} {}", expand_modal(modal), forms.base),
+        [modal, "have", "been"] if MODALS.contains(modal) => format!("{} have {}", expand_modal(modal), participle_text),
+        ["had" | "'d", "been"] => format!("had {}", participle_text),
+        ["has" | "have" | "'s" | "'ve", "been"] => format!("{} {}", present(&verb_forms("have")), participle_text),
+        _ => return None,
+    };
+
+    let sentence_start = matches!(
+        text[..words[subject.start].start].trim_end().chars().next_back(),
+        None | Some('.' | '!' | '?')
+    );
+    let phrase = |range: &Range<usize>| text[words[range.start].start..words[range.end - 1].end].to_string();
+
+    let mut new_object = phrase(subject);
+    if subject.len() == 1 && words[subject.start].tag == Tag::Pronoun {
+        let lower = &words[subject.start].lower;
+        if let Some(&(_, object)) = PRONOUN_CASES.iter().find(|(s, _)| s == lower) {
+            new_object = object.to_string();
+        } else if sentence_start {
+            new_object = new_object.to_lowercase();
+        }
+    } else if sentence_start && words[subject.start].tag != Tag::ProperNoun {
+        new_object = lowercase_first(&new_object);
+    }
+
+    let mut new_subject = phrase(agent);
+    if agent.len() == 1 && head.tag == Tag::Pronoun {
+        if let Some(&(subject_form, _)) = PRONOUN_CASES.iter().find(|(_, o)| *o == head.lower) {
+            new_subject = subject_form.to_string();
+        }
+    }
+    if sentence_start || new_subject == "i" {
+        new_subject = uppercase_first(&new_subject);
+    }
+
+    let mut rewrite = new_subject;
+    for adverb in adverbs {
+        rewrite.push(' ');
+        rewrite.push_str(&adverb.text);
+    }
+    rewrite.push(' ');
+    rewrite.push_str(&verb);
+    rewrite.push(' ');
+    rewrite.push_str(&new_object);
+    Some((words[subject.start].start..words[agent.end - 1].end, rewrite))
+}
+
+/// Full form of a contracted modal: "'ll" → "will"
+fn expand_modal(modal: &str) -> &str {
+    match modal {
+        "'ll" => "will",
+        "'d" => "would",
+        _ => modal,
+    }
+}
+
+fn uppercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect())
+}
+
+fn lowercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |c| c.to_lowercase().chain(chars).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +527,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_participles_come_from_tags() {
+        let detector = PassiveVoiceDetector::default();
+
+        let matches = detector.detect("The reports were completed, then filed.").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "were completed");
+
+        // "red" ends in -ed but is not a participle
+        assert!(detector.detect("The car was red.").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verb_groups() {
+        let detector = PassiveVoiceDetector::default();
+        let groups = |text: &str| -> Vec<(String, String)> {
+            detector
+                .detect(text)
+                .unwrap()
+                .into_iter()
+                .map(|m| (m.text, m.auxiliary))
+                .collect()
+        };
+
+        let pair = |text: &str, auxiliary: &str| vec![(text.to_string(), auxiliary.to_string())];
+        assert_eq!(groups("The ball was quickly thrown."), pair("was quickly thrown", "was"));
+        assert_eq!(groups("The book has been written."), pair("has been written", "has been"));
+        assert_eq!(groups("The code will be reviewed tomorrow."), pair("will be reviewed", "will be"));
+        assert_eq!(groups("It's been decided already."), pair("'s been decided", "'s been"));
+        assert_eq!(groups("They hate being watched."), pair("being watched", "being"));
+        assert_eq!(groups("The house is being watched."), pair("is being watched", "is being"));
+        assert_eq!(groups("The form wasn't signed by anyone."), pair("wasn't signed", "was"));
+        assert_eq!(groups("It should have been sent."), pair("should have been sent", "should have been"));
+    }
+
+    #[test]
+    fn test_punctuation_and_active_perfects_are_not_passive() {
+        let detector = PassiveVoiceDetector::default();
+
+        assert!(detector.detect("She's finished the report.").unwrap().is_empty());
+        assert!(detector.detect("The plan was, frankly, approved.").unwrap().is_empty());
+        assert!(detector.detect("They have written three books.").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_active_rewrites() {
+        let detector = PassiveVoiceDetector::default();
+        let rewrite = |text: &str| -> Option<String> {
+            let matches = detector.detect(text).unwrap();
+            let fix = matches[0].fix.as_ref()?;
+            Some(crate::fix::apply_fixes(text, [fix]).text)
+        };
+
+        let matches = detector.detect("The ball was thrown by John.").unwrap();
+        assert_eq!(matches[0].subject.as_deref(), Some("The ball"));
+        assert_eq!(matches[0].agent.as_deref(), Some("John"));
+        assert_eq!(rewrite("The ball was thrown by John.").as_deref(), Some("John threw the ball."));
+
+        assert_eq!(rewrite("The report is reviewed by the editors.").as_deref(), Some("The editors review the report."));
+        assert_eq!(rewrite("The report is reviewed by an editor.").as_deref(), Some("An editor reviews the report."));
+        assert_eq!(rewrite("The book has been written by Mary.").as_deref(), Some("Mary has written the book."));
+        assert_eq!(rewrite("The code will be checked by the team.").as_deref(), Some("The team will check the code."));
+        assert_eq!(rewrite("She was seen by them.").as_deref(), Some("They saw her."));
+        assert_eq!(rewrite("I think the ball was quickly caught by him.").as_deref(), Some("I think he quickly caught the ball."));
+    }
+
+    #[test]
+    fn test_unsafe_rewrites_are_skipped() {
+        let detector = PassiveVoiceDetector::default();
+        let fix = |text: &str| detector.detect(text).unwrap()[0].fix.clone();
+
+        // Negation needs "did not", and the subject of "of the book" is "the cover"
+        assert!(fix("The form wasn't signed by the manager.").is_none());
+        assert!(fix("The cover of the book was torn by the dog.").is_none());
+        assert!(fix("The ball was thrown by John and Mary.").is_none());
+        assert!(fix("The ball was thrown.").is_none());
+    }
+
     #[test]
     fn test_confidence_scoring() {
         let detector = PassiveVoiceDetector::default();
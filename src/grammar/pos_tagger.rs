@@ -0,0 +1,475 @@
+// Part-of-speech tagging
+// Lexicon lookup and suffix guesses give each token its possible tags;
+// contextual rules then pick one, left to right
+
+use crate::dictionaries::irregular_verbs::is_adjective_exception;
+use crate::dictionaries::morphology::{regular_inflection, Inflection};
+use crate::dictionaries::pos_lexicon::lexicon_tags;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    /// Numbers, words with inner hyphens or apostrophes, and single symbols
+    static ref TOKEN: Regex = Regex::new(
+        r"\p{N}+(?:[.,:]\p{N}+)+|[\p{L}\p{N}]+(?:[-'’][\p{L}\p{N}]+)*|[^\s\p{L}\p{N}]"
+    ).unwrap();
+}
+
+const BE_FORMS: &[&str] = &["am", "is", "are", "was", "were", "be", "been", "being", "'re", "'m"];
+const GET_FORMS: &[&str] = &["get", "gets", "got", "gotten", "getting"];
+const HAVE_FORMS: &[&str] = &["have", "has", "had", "having", "'ve"];
+const DO_FORMS: &[&str] = &["do", "does", "did"];
+const SUBJECT_PRONOUNS: &[&str] = &["i", "you", "he", "she", "it", "we", "they"];
+
+/// Part-of-speech tag, a reduced Penn Treebank set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Tag {
+    /// Singular or mass noun (NN)
+    Noun,
+    /// NNS
+    PluralNoun,
+    /// NNP
+    ProperNoun,
+    /// Personal, reflexive and indefinite pronouns, and existential "there" (PRP)
+    Pronoun,
+    /// Articles, demonstratives and possessive determiners (DT)
+    Determiner,
+    /// JJ
+    Adjective,
+    /// RB, including the "n't" of contractions
+    Adverb,
+    /// IN
+    Preposition,
+    /// Coordinating conjunction (CC)
+    Conjunction,
+    /// Subordinating conjunction or relative word starting a clause (CS)
+    Subordinator,
+    /// MD
+    Modal,
+    /// Infinitive marker or preposition "to" (TO)
+    To,
+    /// Infinitive or imperative (VB)
+    VerbBase,
+    /// Present tense other than third person singular (VBP)
+    VerbPresent,
+    /// Present tense, third person singular (VBZ)
+    VerbPresent3rd,
+    /// VBD
+    VerbPast,
+    /// Gerund or present participle (VBG)
+    Gerund,
+    /// VBN
+    PastParticiple,
+    /// Possessive "'s" (POS)
+    Possessive,
+    /// CD
+    Number,
+    /// UH
+    Interjection,
+    Punctuation,
+}
+
+impl Tag {
+    /// Tag for a lexicon code such as `NNS`
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "NN" => Tag::Noun,
+            "NNS" => Tag::PluralNoun,
+            "NNP" => Tag::ProperNoun,
+            "PRP" => Tag::Pronoun,
+            "DT" => Tag::Determiner,
+            "JJ" => Tag::Adjective,
+            "RB" => Tag::Adverb,
+            "IN" => Tag::Preposition,
+            "CC" => Tag::Conjunction,
+            "CS" => Tag::Subordinator,
+            "MD" => Tag::Modal,
+            "TO" => Tag::To,
+            "VB" => Tag::VerbBase,
+            "VBP" => Tag::VerbPresent,
+            "VBZ" => Tag::VerbPresent3rd,
+            "VBD" => Tag::VerbPast,
+            "VBG" => Tag::Gerund,
+            "VBN" => Tag::PastParticiple,
+            "POS" => Tag::Possessive,
+            "CD" => Tag::Number,
+            "UH" => Tag::Interjection,
+            _ => return None,
+        })
+    }
+
+    pub fn is_noun(self) -> bool {
+        matches!(self, Tag::Noun | Tag::PluralNoun | Tag::ProperNoun)
+    }
+
+    /// Nouns and pronouns, the words that can head a subject
+    pub fn is_nominal(self) -> bool {
+        self.is_noun() || self == Tag::Pronoun
+    }
+
+    pub fn is_verb(self) -> bool {
+        matches!(
+            self,
+            Tag::VerbBase
+                | Tag::VerbPresent
+                | Tag::VerbPresent3rd
+                | Tag::VerbPast
+                | Tag::Gerund
+                | Tag::PastParticiple
+        )
+    }
+
+    /// Verbs carrying tense, which a clause needs
+    pub fn is_finite_verb(self) -> bool {
+        matches!(self, Tag::VerbPresent | Tag::VerbPresent3rd | Tag::VerbPast | Tag::Modal)
+    }
+}
+
+/// A tagged word or punctuation mark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Token {
+    pub text: String,
+    /// `text` lowercased, with typographic apostrophes made straight
+    pub lower: String,
+    pub tag: Tag,
+    /// Byte offsets in the tagged text
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn is_word(&self) -> bool {
+        self.tag != Tag::Punctuation
+    }
+
+    pub fn is_any_of(&self, words: &[&str]) -> bool {
+        words.contains(&self.lower.as_str())
+    }
+}
+
+/// Tags text with a bundled lexicon and contextual rules
+///
+/// Contractions are split the Penn Treebank way: "don't" becomes "do" and
+/// "n't", "it's" becomes "it" and "'s".
+#[derive(Debug, Clone, Default)]
+pub struct PosTagger;
+
+impl PosTagger {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Tokenize and tag `text`, which may hold several sentences
+    pub fn tag(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut candidates = Vec::new();
+        let mut at_start = true;
+
+        for (start, end) in split_tokens(text) {
+            let word = &text[start..end];
+            let lower = word.to_lowercase().replace('’', "'");
+            let tags = candidate_tags(word, &lower, at_start);
+            if tags[0] == Tag::Punctuation {
+                if matches!(word, "." | "!" | "?") {
+                    at_start = true;
+                }
+            } else {
+                at_start = false;
+            }
+            tokens.push(Token {
+                text: word.to_string(),
+                lower,
+                tag: tags[0],
+                start,
+                end,
+            });
+            candidates.push(tags);
+        }
+
+        let mut sentence_start = 0;
+        let mut finite_seen = false;
+        for i in 0..tokens.len() {
+            let context = Context {
+                prev: tokens[sentence_start..i].iter().rev().find(|t| t.tag != Tag::Adverb),
+                next: tokens.get(i + 1).map(|t| (t.lower.as_str(), candidates[i + 1][0])),
+                at_start: i == sentence_start,
+                finite_seen,
+            };
+            let tag = choose(&candidates[i], &context);
+            tokens[i].tag = tag;
+
+            if tag.is_finite_verb() {
+                finite_seen = true;
+            }
+            match tokens[i].text.as_str() {
+                "." | "!" | "?" => {
+                    sentence_start = i + 1;
+                    finite_seen = false;
+                }
+                "," | ";" | ":" => finite_seen = false,
+                _ if matches!(tag, Tag::Conjunction | Tag::Subordinator) => finite_seen = false,
+                _ => {}
+            }
+        }
+
+        tokens
+    }
+
+    /// Every tag `word` could take out of context, most likely first
+    pub fn candidates(&self, word: &str) -> Vec<Tag> {
+        let lower = word.to_lowercase().replace('’', "'");
+        candidate_tags(word, &lower, false)
+    }
+}
+
+/// What the contextual rules look at around a token
+struct Context<'t> {
+    /// Closest earlier token of the sentence that is not an adverb
+    prev: Option<&'t Token>,
+    /// Lowercased next token and its most likely tag
+    next: Option<(&'t str, Tag)>,
+    at_start: bool,
+    /// Whether the current clause already has a finite verb
+    finite_seen: bool,
+}
+
+/// Byte ranges of tokens, with contractions split off their host word
+fn split_tokens(text: &str) -> Vec<(usize, usize)> {
+    let mut pieces = Vec::new();
+    for m in TOKEN.find_iter(text) {
+        let lower = m.as_str().to_lowercase().replace('’', "'");
+        let clitic = ["n't", "'s", "'re", "'m", "'ve", "'ll", "'d"]
+            .iter()
+            .find(|c| lower.len() > c.len() && lower.ends_with(*c));
+        match clitic {
+            Some(clitic) => {
+                // Count chars rather than bytes: the apostrophe may be typographic
+                let chars = clitic.chars().count();
+                let split = m.as_str().char_indices().rev().nth(chars - 1).map_or(0, |(i, _)| i);
+                pieces.push((m.start(), m.start() + split));
+                pieces.push((m.start() + split, m.end()));
+            }
+            None => pieces.push((m.start(), m.end())),
+        }
+    }
+    pieces
+}
+
+/// Possible tags of a token, most likely first
+fn candidate_tags(word: &str, lower: &str, at_start: bool) -> Vec<Tag> {
+    if !word.chars().any(char::is_alphanumeric) {
+        return vec![Tag::Punctuation];
+    }
+    if word.starts_with(|c: char| c.is_numeric()) {
+        return vec![Tag::Number];
+    }
+    match lower {
+        "n't" => return vec![Tag::Adverb],
+        "'s" => return vec![Tag::Possessive, Tag::VerbPresent3rd],
+        "'re" | "'m" => return vec![Tag::VerbPresent],
+        "'ve" => return vec![Tag::VerbPresent, Tag::VerbBase],
+        "'ll" | "'d" => return vec![Tag::Modal],
+        _ => {}
+    }
+
+    let acronym = word.chars().count() > 1 && word.chars().all(|c| c.is_uppercase() || !c.is_alphabetic());
+    let capitalized = !at_start && word.starts_with(char::is_uppercase);
+
+    let mut tags = if let Some(codes) = lexicon_tags(lower) {
+        codes.iter().filter_map(|code| Tag::from_code(code)).collect()
+    } else if acronym || capitalized {
+        vec![Tag::ProperNoun]
+    } else {
+        inflection_tags(lower).unwrap_or_else(|| suffix_tags(lower))
+    };
+
+    if lower.ends_with("ed") && is_adjective_exception(lower) && !tags.contains(&Tag::Adjective) {
+        tags.push(Tag::Adjective);
+    }
+    tags
+}
+
+/// Tags of an inflected form of a known verb
+fn inflection_tags(lower: &str) -> Option<Vec<Tag>> {
+    let (base, inflection) = regular_inflection(lower)?;
+    Some(match inflection {
+        Inflection::PresentParticiple => vec![Tag::Gerund, Tag::Noun],
+        Inflection::Past => vec![Tag::VerbPast, Tag::PastParticiple],
+        Inflection::ThirdPerson if lexicon_tags(&base).is_some_and(|tags| tags.contains(&"NN")) => {
+            vec![Tag::PluralNoun, Tag::VerbPresent3rd]
+        }
+        Inflection::ThirdPerson => vec![Tag::VerbPresent3rd],
+    })
+}
+
+/// Guess the tags of an unknown word from its ending
+fn suffix_tags(lower: &str) -> Vec<Tag> {
+    let len = lower.chars().count();
+    let ends = |suffixes: &[&str]| suffixes.iter().any(|s| lower.ends_with(s));
+
+    if len > 4 && lower.ends_with("ly") {
+        vec![Tag::Adverb]
+    } else if len > 5 && lower.ends_with("ing") {
+        vec![Tag::Gerund, Tag::Noun]
+    } else if len > 4 && lower.ends_with("ed") && !lower.ends_with("eed") {
+        vec![Tag::VerbPast, Tag::PastParticiple]
+    } else if ends(&["tion", "sion", "ment", "ness", "ity", "ance", "ence", "ism", "ship", "hood", "dom"]) {
+        vec![Tag::Noun]
+    } else if ends(&["ous", "ful", "ive", "able", "ible", "ic", "less", "ish"]) {
+        vec![Tag::Adjective]
+    } else if ends(&["al", "ant", "ent", "ary"]) {
+        vec![Tag::Adjective, Tag::Noun]
+    } else if len > 3 && lower.ends_with('s') && !ends(&["ss", "us", "is"]) {
+        vec![Tag::PluralNoun, Tag::VerbPresent3rd]
+    } else {
+        // Could be a noun or a verb; `choose` decides from the context
+        vec![Tag::Noun, Tag::VerbBase, Tag::VerbPresent]
+    }
+}
+
+/// Pick one of `candidates` from the surrounding tokens
+fn choose(candidates: &[Tag], context: &Context) -> Tag {
+    if candidates.len() == 1 {
+        return candidates[0];
+    }
+    let has = |tag: Tag| candidates.contains(&tag);
+    let first_of = |tags: &[Tag]| tags.iter().copied().find(|t| has(*t));
+    let prev_tag = context.prev.map(|t| t.tag);
+    let prev_is = |words: &[&str]| context.prev.is_some_and(|t| t.is_any_of(words));
+    let (next_word, next_tag) = context.next.unwrap_or(("", Tag::Punctuation));
+    let next_is_nominal = next_tag.is_noun() || matches!(next_tag, Tag::Adjective | Tag::Number);
+
+    // "pretty good": an adverb modifies what follows
+    if has(Tag::Adverb) && has(Tag::Adjective) && matches!(next_tag, Tag::Adjective | Tag::Adverb) {
+        return Tag::Adverb;
+    }
+    // Passive and progressive verb groups
+    if prev_is(BE_FORMS) || prev_is(GET_FORMS) {
+        if let Some(tag) = first_of(&[Tag::PastParticiple, Tag::Gerund, Tag::Adjective]) {
+            return tag;
+        }
+    }
+    // Perfect tenses
+    if prev_is(HAVE_FORMS) && has(Tag::PastParticiple) {
+        return Tag::PastParticiple;
+    }
+    // Infinitives and do-support
+    if (matches!(prev_tag, Some(Tag::Modal | Tag::To)) || prev_is(DO_FORMS)) && has(Tag::VerbBase) {
+        return Tag::VerbBase;
+    }
+    // Existential "there is"
+    if has(Tag::Pronoun) && has(Tag::Adverb) && BE_FORMS.contains(&next_word) {
+        return Tag::Pronoun;
+    }
+    // "it's" against "John's"
+    if has(Tag::Possessive) {
+        return if prev_tag == Some(Tag::Pronoun) { Tag::VerbPresent3rd } else { Tag::Possessive };
+    }
+    // "that book" against "that is" and "said that"
+    if has(Tag::Determiner) {
+        if next_is_nominal {
+            return Tag::Determiner;
+        }
+        if has(Tag::Subordinator) && prev_tag.is_some_and(|t| t.is_verb() || t.is_noun()) {
+            return Tag::Subordinator;
+        }
+        if let Some(tag) = first_of(&[Tag::Pronoun, Tag::Adjective]) {
+            return tag;
+        }
+    }
+    // "after the game" against "after we left"
+    if has(Tag::Subordinator) && has(Tag::Preposition) {
+        return if SUBJECT_PRONOUNS.contains(&next_word) { Tag::Subordinator } else { Tag::Preposition };
+    }
+    // Noun phrases
+    if matches!(
+        prev_tag,
+        Some(Tag::Determiner | Tag::Adjective | Tag::Possessive | Tag::Number | Tag::Preposition)
+    ) {
+        if has(Tag::Adjective) && next_is_nominal {
+            return Tag::Adjective;
+        }
+        if let Some(tag) = first_of(&[Tag::Noun, Tag::PluralNoun, Tag::Adjective, Tag::Gerund, Tag::PastParticiple]) {
+            return tag;
+        }
+    }
+    // An unknown word is a verb only after a pronoun or plural subject, not in "the data center"
+    if has(Tag::Noun) && has(Tag::VerbBase) && has(Tag::VerbPresent) && prev_tag.is_some_and(Tag::is_nominal) {
+        return if matches!(prev_tag, Some(Tag::Pronoun | Tag::PluralNoun)) { Tag::VerbPresent } else { Tag::Noun };
+    }
+    // A verb after its subject carries the tense
+    if prev_tag.is_some_and(Tag::is_nominal) {
+        if let Some(tag) = first_of(&[Tag::VerbPresent3rd, Tag::VerbPast, Tag::VerbPresent]) {
+            return tag;
+        }
+        if has(Tag::VerbBase) {
+            return Tag::VerbPresent;
+        }
+    }
+    // Imperatives
+    if context.at_start
+        && has(Tag::VerbBase)
+        && matches!(
+            next_tag,
+            Tag::Determiner | Tag::Pronoun | Tag::Preposition | Tag::To | Tag::Number | Tag::ProperNoun | Tag::Adverb
+        )
+    {
+        return Tag::VerbBase;
+    }
+    // An -ed form is the main verb until the clause has one
+    if has(Tag::VerbPast) && has(Tag::PastParticiple) {
+        return if context.finite_seen { Tag::PastParticiple } else { Tag::VerbPast };
+    }
+    candidates[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(text: &str) -> Vec<(String, Tag)> {
+        PosTagger::new().tag(text).into_iter().map(|t| (t.text, t.tag)).collect()
+    }
+
+    fn tag_of(text: &str, word: &str) -> Tag {
+        tags(text).into_iter().find(|(w, _)| w == word).unwrap().1
+    }
+
+    #[test]
+    fn test_tags_simple_sentence() {
+        let tagged = tags("The dog chased the ball.");
+        let expected = [
+            Tag::Determiner,
+            Tag::Noun,
+            Tag::VerbPast,
+            Tag::Determiner,
+            Tag::Noun,
+            Tag::Punctuation,
+        ];
+        assert_eq!(tagged.iter().map(|(_, t)| *t).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_disambiguates_by_context() {
+        assert_eq!(tag_of("They work hard.", "work"), Tag::VerbPresent);
+        assert_eq!(tag_of("The work is hard.", "work"), Tag::Noun);
+        assert_eq!(tag_of("She plans a trip.", "plans"), Tag::VerbPresent3rd);
+        assert_eq!(tag_of("The plans changed.", "plans"), Tag::PluralNoun);
+        assert_eq!(tag_of("The house was built by workers.", "built"), Tag::PastParticiple);
+        assert_eq!(tag_of("The workers built the house.", "built"), Tag::VerbPast);
+        assert_eq!(tag_of("Our family walked home.", "family"), Tag::Noun);
+        assert_eq!(tag_of("She walked slowly.", "slowly"), Tag::Adverb);
+        assert_eq!(tag_of("The day was pretty good.", "pretty"), Tag::Adverb);
+    }
+
+    #[test]
+    fn test_splits_contractions() {
+        let tagged = PosTagger::new().tag("It’s fine, don't worry.");
+        let words: Vec<&str> = tagged.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(words, ["It", "’s", "fine", ",", "do", "n't", "worry", "."]);
+        assert_eq!(tagged[1].tag, Tag::VerbPresent3rd);
+        assert_eq!(tagged[6].tag, Tag::VerbBase);
+        assert_eq!(&"It’s fine"[tagged[1].start..tagged[1].end], "’s");
+    }
+}
@@ -0,0 +1,898 @@
+// Grammar rules
+// The Rule trait, the built-in rules and the registry GrammarChecker draws from
+
+use crate::config::RuleSettings;
+use crate::dictionaries::morphology::{past_tense, present_tense, verb_forms, Agreement};
+use crate::fix::{match_case, Applicability, Fix};
+use crate::grammar::articles::IndefiniteArticle;
+use crate::grammar::confusables::ConfusedWords;
+use crate::grammar::{GrammarIssue, GrammarIssueType, Severity, Tag, Token};
+use crate::span::{Lowercased, OffsetIndex};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::Arc;
+
+const PLURAL_VERBS: &[&str] = &["are", "were", "have"];
+const SINGULAR_VERBS: &[&str] = &["is", "was", "has"];
+/// Object pronouns and their subject forms
+const PRONOUN_CASES: &[(&str, &str)] = &[("me", "I"), ("him", "he"), ("her", "she"), ("us", "we"), ("them", "they")];
+const QUOTES: &[&str] = &["\"", "“", "”", "'", "‘", "’"];
+/// Words that are correctly doubled: "had had", "that that", "what it is is"
+const LEGIT_DOUBLES: &[&str] = &["had", "that", "is", "do", "does", "bye", "ha", "no", "yes", "very", "so"];
+/// Words that put the verb before its subject in questions
+const INVERTING_VERBS: &[&str] = &["do", "does", "did", "have", "has", "had", "is", "are", "was", "were"];
+
+lazy_static! {
+    static ref DOUBLE_NEGATIVE: Regex = Regex::new(
+        r"\b(don't|doesn't|didn't|won't|can't|couldn't|shouldn't|wouldn't)\s+\w+\s+(no|nothing|nobody|never|nowhere|neither)\b"
+    ).unwrap();
+
+    static ref RUN_ON_INDICATORS: Regex = Regex::new(
+        r",\s+(and|but|or|so)\s+\w+\s+\w+\s+,\s+(and|but|or|so)"
+    ).unwrap();
+
+    static ref DOUBLE_SPACE: Regex = Regex::new(r"  +").unwrap();
+}
+
+/// Broad area a rule belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleCategory {
+    Grammar,
+    Punctuation,
+    Typography,
+    Structure,
+    Usage,
+    Spelling,
+}
+
+/// One sentence as seen by a rule
+pub struct SentenceContext<'a> {
+    /// Trimmed sentence text
+    pub sentence: &'a str,
+    /// Lowercased sentence; `lower_range` maps its offsets back to `sentence`
+    pub lower: &'a Lowercased,
+    /// 1-based position of the sentence in the document
+    pub sentence_num: usize,
+    /// Index of the paragraph (or block) containing the sentence
+    pub paragraph: usize,
+    /// Byte offset of the sentence in the checked text
+    pub offset: usize,
+    /// Tagged tokens, with offsets into `sentence`
+    pub tokens: &'a [Token],
+    index: &'a OffsetIndex<'a>,
+}
+
+impl<'a> SentenceContext<'a> {
+    pub(crate) fn new(
+        sentence: &'a str,
+        lower: &'a Lowercased,
+        sentence_num: usize,
+        paragraph: usize,
+        offset: usize,
+        tokens: &'a [Token],
+        index: &'a OffsetIndex<'a>,
+    ) -> Self {
+        Self {
+            sentence,
+            lower,
+            sentence_num,
+            paragraph,
+            offset,
+            tokens,
+            index,
+        }
+    }
+
+    /// Issue reported by `rule` for `range` of the sentence, at the rule's default severity
+    pub fn issue<R: Rule + ?Sized>(
+        &self,
+        rule: &R,
+        issue_type: GrammarIssueType,
+        message: impl Into<String>,
+        range: Range<usize>,
+    ) -> GrammarIssue {
+        let (start, end) = (self.offset + range.start, self.offset + range.end);
+        GrammarIssue {
+            issue_type,
+            rule_id: rule.id().to_string(),
+            message: message.into(),
+            sentence_num: self.sentence_num,
+            severity: rule.default_severity(),
+            start_index: start,
+            end_index: end,
+            length: end - start,
+            span: self.index.span(start, end),
+            fixes: Vec::new(),
+        }
+    }
+
+    /// Fix replacing `range` of the sentence with `replacement`
+    pub fn fix(
+        &self,
+        message: impl Into<String>,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Fix {
+        let span = self.index.span(self.offset + range.start, self.offset + range.end);
+        Fix::replace(message, span, replacement, applicability)
+    }
+
+    /// Range in the sentence of a match found in `lower.text`
+    pub fn lower_range(&self, m: regex::Match) -> Range<usize> {
+        self.lower.original(m.start())..self.lower.original(m.end())
+    }
+}
+
+/// A grammar check run on every sentence
+///
+/// Rules must be cheap to share: the checker runs them from several threads.
+pub trait Rule: Send + Sync {
+    /// Stable identifier used in configuration and reports, e.g. `double-negative`
+    fn id(&self) -> &str;
+
+    fn category(&self) -> RuleCategory;
+
+    fn default_severity(&self) -> Severity;
+
+    /// Whether the rule runs unless disabled in `rules.disabled`
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue>;
+
+    /// Check a whole paragraph, for issues that span sentences
+    fn check_paragraph(&self, _sentences: &[SentenceContext]) -> Vec<GrammarIssue> {
+        Vec::new()
+    }
+}
+
+/// The set of known rules, in the order they run
+#[derive(Clone)]
+pub struct RuleRegistry {
+    rules: Vec<Arc<dyn Rule>>,
+}
+
+impl Default for RuleRegistry {
+    /// Registry with the built-in rules
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(DoubleSpace);
+        registry.register(MissingPunctuation);
+        registry.register(SubjectVerbAgreement);
+        registry.register(DoubleNegative);
+        registry.register(RunOnSentence);
+        registry.register(CommaSplice);
+        registry.register(SentenceFragment);
+        registry.register(PronounCase);
+        registry.register(VerbTense);
+        registry.register(ConfusedWords);
+        registry.register(IndefiniteArticle);
+        registry.register(RepeatedWord);
+        registry
+    }
+}
+
+impl RuleRegistry {
+    /// Registry without any rules
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule; a rule with the ID of a registered rule replaces it in place
+    pub fn register(&mut self, rule: impl Rule + 'static) {
+        let rule: Arc<dyn Rule> = Arc::new(rule);
+        match self.rules.iter_mut().find(|r| r.id() == rule.id()) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules.iter().find(|r| r.id() == id).map(|r| r.as_ref())
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+
+    /// Rules that run under `settings`; `disabled` wins over `enabled`
+    pub fn active(&self, settings: &RuleSettings) -> Vec<Arc<dyn Rule>> {
+        self.rules
+            .iter()
+            .filter(|r| {
+                let id = r.id();
+                let listed = |ids: &[String]| ids.iter().any(|i| i == id);
+                !listed(&settings.disabled) && (r.enabled_by_default() || listed(&settings.enabled))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+// ========== BUILT-IN RULES ==========
+
+pub struct DoubleSpace;
+
+impl Rule for DoubleSpace {
+    fn id(&self) -> &str {
+        "double-space"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Typography
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        DOUBLE_SPACE
+            .find(ctx.sentence)
+            .map(|m| {
+                let mut issue = ctx.issue(self, GrammarIssueType::DoubleSpace, "Double space detected", m.range());
+                issue.fixes.push(ctx.fix("Use a single space", m.range(), " ", Applicability::MachineApplicable));
+                issue
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+pub struct MissingPunctuation;
+
+impl Rule for MissingPunctuation {
+    fn id(&self) -> &str {
+        "missing-punctuation"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Punctuation
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        match ctx.sentence.trim().chars().last() {
+            Some(last_char) if !matches!(last_char, '.' | '!' | '?') => {
+                let trimmed_len = ctx.sentence.trim().len();
+                let mut issue = ctx.issue(
+                    self,
+                    GrammarIssueType::MissingPunctuation,
+                    "Missing end punctuation",
+                    trimmed_len..ctx.sentence.len(),
+                );
+                // The sentence may be a question or an exclamation
+                let end = trimmed_len..trimmed_len;
+                issue.fixes.push(ctx.fix("Add a period", end, ".", Applicability::MaybeIncorrect));
+                vec![issue]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub struct SubjectVerbAgreement;
+
+impl Rule for SubjectVerbAgreement {
+    fn id(&self) -> &str {
+        "subject-verb-agreement"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Grammar
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let words: Vec<&Token> = ctx.tokens.iter().filter(|t| t.tag != Tag::Adverb).collect();
+        let mut issues = Vec::new();
+
+        for (i, pair) in words.windows(2).enumerate() {
+            let (subject, verb) = (pair[0], pair[1]);
+            let determined = i > 0 && words[i - 1].tag == Tag::Determiner;
+            // In "Does he have" the verb after the subject is not the one it agrees with
+            let before = if determined { i.checked_sub(2) } else { i.checked_sub(1) };
+            if before.is_some_and(|b| words[b].tag == Tag::Modal || words[b].is_any_of(INVERTING_VERBS)) {
+                continue;
+            }
+            let message = match subject.tag {
+                Tag::Pronoun if subject.is_any_of(&["he", "she", "it"]) && verb.is_any_of(PLURAL_VERBS) => {
+                    "Singular subject with plural verb"
+                }
+                Tag::Pronoun if subject.is_any_of(&["they", "we", "you"]) && verb.is_any_of(SINGULAR_VERBS) => {
+                    "Plural subject with singular verb"
+                }
+                Tag::Noun if determined && verb.is_any_of(PLURAL_VERBS) => "Possible singular subject with plural verb",
+                Tag::PluralNoun if determined && verb.is_any_of(SINGULAR_VERBS) => {
+                    "Possible plural subject with singular verb"
+                }
+                _ => continue,
+            };
+            let start = if determined { words[i - 1].start } else { subject.start };
+            issues.push(ctx.issue(self, GrammarIssueType::SubjectVerbAgreement, message, start..verb.end));
+        }
+        issues
+    }
+}
+
+pub struct DoubleNegative;
+
+impl Rule for DoubleNegative {
+    fn id(&self) -> &str {
+        "double-negative"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Grammar
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        DOUBLE_NEGATIVE
+            .find(&ctx.lower.text)
+            .map(|m| ctx.issue(self, GrammarIssueType::DoubleNegative, "Double negative detected", ctx.lower_range(m)))
+            .into_iter()
+            .collect()
+    }
+}
+
+pub struct RunOnSentence;
+
+impl Rule for RunOnSentence {
+    fn id(&self) -> &str {
+        "run-on-sentence"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Structure
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        RUN_ON_INDICATORS
+            .find(&ctx.lower.text)
+            .map(|m| ctx.issue(self, GrammarIssueType::RunOnSentence, "Possible run-on sentence", ctx.lower_range(m)))
+            .into_iter()
+            .collect()
+    }
+}
+
+pub struct CommaSplice;
+
+impl CommaSplice {
+    /// Index of the first comma joining two independent clauses
+    fn find_splice(tokens: &[Token]) -> Option<usize> {
+        let commas: Vec<usize> = (0..tokens.len()).filter(|&i| tokens[i].text == ",").collect();
+        let mut clause_start = 0;
+        for (n, &comma) in commas.iter().enumerate() {
+            let next_comma = commas.get(n + 1).copied().unwrap_or(tokens.len());
+            let (left, right) = (&tokens[clause_start..comma], &tokens[comma + 1..next_comma]);
+            clause_start = comma + 1;
+
+            // Dialogue such as "I'm tired," she said
+            let quoted = |t: Option<&Token>| t.is_some_and(|t| matches!(t.text.as_str(), "\"" | "'" | "”" | "’"));
+            if quoted(right.first()) {
+                continue;
+            }
+            if Self::is_independent_clause(left) && Self::is_independent_clause(right) {
+                return Some(comma);
+            }
+        }
+        None
+    }
+
+    /// A subject followed by a finite verb, not introduced by a conjunction
+    fn is_independent_clause(tokens: &[Token]) -> bool {
+        let mut words = tokens.iter().filter(|t| t.is_word()).peekable();
+        match words.peek() {
+            Some(first) if matches!(first.tag, Tag::Conjunction | Tag::Subordinator) => return false,
+            None => return false,
+            _ => {}
+        }
+        words
+            .skip_while(|t| !t.tag.is_nominal())
+            .any(|t| t.tag.is_finite_verb())
+    }
+}
+
+impl Rule for CommaSplice {
+    fn id(&self) -> &str {
+        "comma-splice"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Punctuation
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        Self::find_splice(ctx.tokens)
+            .map(|i| {
+                let comma = &ctx.tokens[i];
+                ctx.issue(self, GrammarIssueType::CommaSplice, "Possible comma splice", comma.start..comma.end)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+pub struct SentenceFragment;
+
+impl SentenceFragment {
+    /// Whether `words` hold a finite verb, or start with an imperative
+    fn has_predicate(words: &[&Token]) -> bool {
+        words.iter().any(|t| t.tag.is_finite_verb())
+            || words
+                .iter()
+                .find(|t| !matches!(t.tag, Tag::Interjection | Tag::Adverb))
+                .is_some_and(|t| t.tag == Tag::VerbBase)
+    }
+
+    /// Number of finite verbs that start a verb group
+    fn verb_groups(words: &[&Token]) -> usize {
+        (0..words.len())
+            .filter(|&i| words[i].tag.is_finite_verb())
+            .filter(|&i| i == 0 || !(words[i - 1].tag.is_verb() || words[i - 1].tag == Tag::Modal))
+            .count()
+    }
+
+    /// "Because it rained." with no main clause after the subordinate one
+    fn is_dangling(tokens: &[Token], words: &[&Token]) -> bool {
+        if words[0].tag != Tag::Subordinator || words[0].is_any_of(&["that", "than", "who", "whom"]) {
+            return false;
+        }
+        match tokens.iter().position(|t| t.text == ",") {
+            Some(comma) => {
+                let rest: Vec<&Token> = tokens[comma + 1..].iter().filter(|t| t.is_word()).collect();
+                !Self::has_predicate(&rest)
+            }
+            None => Self::verb_groups(words) <= 1,
+        }
+    }
+}
+
+impl Rule for SentenceFragment {
+    fn id(&self) -> &str {
+        "sentence-fragment"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Structure
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let words: Vec<&Token> = ctx.tokens.iter().filter(|t| t.is_word()).collect();
+        // Short exclamations and questions ("What a day!", "Why not?") are fine
+        if words.len() < 3 || ctx.sentence.trim_end().ends_with(['?', '!']) {
+            return Vec::new();
+        }
+
+        let message = if !Self::has_predicate(&words) {
+            "Sentence fragment: no main verb"
+        } else if Self::is_dangling(ctx.tokens, &words) {
+            "Sentence fragment: subordinate clause without a main clause"
+        } else {
+            return Vec::new();
+        };
+        vec![ctx.issue(self, GrammarIssueType::SentenceFragment, message, 0..ctx.sentence.trim_end().len())]
+    }
+}
+
+pub struct PronounCase;
+
+impl PronounCase {
+    /// Indexes of the nouns and pronouns of a phrase like "you and I" starting at `start`
+    fn coordinated(tokens: &[Token], start: usize) -> (Vec<usize>, usize) {
+        let mut members = Vec::new();
+        let mut i = start;
+        loop {
+            if tokens.get(i).is_some_and(|t| t.tag == Tag::Determiner && !t.is_any_of(&["her"])) {
+                i += 1;
+            }
+            match tokens.get(i) {
+                Some(t) if t.tag.is_nominal() => members.push(i),
+                _ => break,
+            }
+            i += 1;
+            match tokens.get(i) {
+                Some(t) if t.is_any_of(&["and", "or"]) => i += 1,
+                _ => break,
+            }
+        }
+        (members, i)
+    }
+
+    fn next_is_finite_verb(tokens: &[Token], from: usize) -> bool {
+        tokens[from.min(tokens.len())..]
+            .iter()
+            .find(|t| t.tag != Tag::Adverb)
+            .is_some_and(|t| t.tag.is_finite_verb())
+    }
+
+    fn issue(ctx: &SentenceContext, rule: &Self, token: &Token, replacement: &str, role: &str) -> GrammarIssue {
+        // "I" is capitalized anywhere, so it says nothing about the sentence start
+        let replacement = if replacement == "I" || token.lower == "i" {
+            replacement.to_string()
+        } else {
+            match_case(replacement, &token.text)
+        };
+        let mut issue = ctx.issue(
+            rule,
+            GrammarIssueType::PronoCase,
+            format!("Use the {} pronoun \"{}\"", role, replacement),
+            token.start..token.end,
+        );
+        let fix = ctx.fix(format!("Replace with \"{}\"", replacement), token.start..token.end, replacement, Applicability::MaybeIncorrect);
+        issue.fixes.push(fix);
+        issue
+    }
+}
+
+impl Rule for PronounCase {
+    fn id(&self) -> &str {
+        "pronoun-case"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Grammar
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        let tokens = ctx.tokens;
+        let mut issues = Vec::new();
+
+        for i in 0..tokens.len() {
+            let prev = i.checked_sub(1).map(|p| &tokens[p]);
+            let clause_start = prev.map_or(true, |p| matches!(p.text.as_str(), "," | ";" | ":") || p.tag == Tag::Subordinator);
+
+            // Object pronouns in a compound subject: "Me and him went"
+            if clause_start {
+                let (members, end) = Self::coordinated(tokens, i);
+                if members.len() > 1 && Self::next_is_finite_verb(tokens, end) {
+                    for &m in &members {
+                        if let Some((_, subject)) = PRONOUN_CASES.iter().find(|(object, _)| tokens[m].lower == *object) {
+                            issues.push(Self::issue(ctx, self, &tokens[m], subject, "subject"));
+                        }
+                    }
+                }
+            }
+
+            // Subject pronouns after a preposition: "between you and I"
+            let governs = matches!(tokens[i].tag, Tag::Preposition | Tag::To) && !tokens[i].is_any_of(&["like", "as", "than"]);
+            if governs {
+                let (members, end) = Self::coordinated(tokens, i + 1);
+                // "for they were tired" is a clause, not an object
+                if Self::next_is_finite_verb(tokens, end) {
+                    continue;
+                }
+                for &m in &members {
+                    if let Some((object, _)) = PRONOUN_CASES.iter().find(|(_, subject)| tokens[m].lower == subject.to_lowercase()) {
+                        issues.push(Self::issue(ctx, self, &tokens[m], object, "object"));
+                    }
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tense {
+    Past,
+    Present,
+}
+
+impl Tense {
+    fn name(self) -> &'static str {
+        match self {
+            Tense::Past => "past",
+            Tense::Present => "present",
+        }
+    }
+}
+
+pub struct VerbTense;
+
+impl VerbTense {
+    /// Token index and tense of the first verb of each verb group, leaving out modals
+    fn main_verbs(tokens: &[Token]) -> Vec<(usize, Tense)> {
+        let mut verbs = Vec::new();
+        let mut in_group = false;
+        for (i, token) in tokens.iter().enumerate() {
+            if token.tag == Tag::Adverb {
+                continue;
+            }
+            let tense = match token.tag {
+                Tag::VerbPast => Some(Tense::Past),
+                Tag::VerbPresent | Tag::VerbPresent3rd => Some(Tense::Present),
+                _ => None,
+            };
+            if let Some(tense) = tense {
+                if !in_group {
+                    verbs.push((i, tense));
+                }
+            }
+            in_group = token.tag.is_verb() || token.tag == Tag::Modal;
+        }
+        verbs
+    }
+
+    /// Dialogue and quotations keep their own tense
+    fn is_quoted(ctx: &SentenceContext) -> bool {
+        ctx.tokens.iter().any(|t| t.is_any_of(QUOTES))
+    }
+
+    /// The verb at `verb` put in `tense`, agreeing with the closest noun or pronoun before it
+    fn in_tense(tokens: &[Token], verb: usize, tense: Tense) -> Option<String> {
+        let agreement = tokens[..verb]
+            .iter()
+            .rev()
+            .find(|t| t.tag.is_nominal())
+            .map_or(Agreement::ThirdSingular, |t| Agreement::of_subject(&t.lower, t.tag == Tag::PluralNoun));
+        let word = &tokens[verb].lower;
+        match tense {
+            // Verbs missing from the lexicon are still inflected from their base form
+            Tense::Past => past_tense(word, agreement)
+                .or_else(|| (tokens[verb].tag == Tag::VerbPresent).then(|| verb_forms(word).past)),
+            Tense::Present => present_tense(word, agreement),
+        }
+    }
+
+    /// Issue for the verb at `verb`, with a fix putting it back in the `from` tense
+    fn issue(&self, ctx: &SentenceContext, verb: usize, from: Tense, to: Tense, scope: &str) -> GrammarIssue {
+        let token = &ctx.tokens[verb];
+        let mut issue = ctx.issue(
+            self,
+            GrammarIssueType::VerbTense,
+            format!("Tense shifts from {} to {} within the {}", from.name(), to.name(), scope),
+            token.start..token.end,
+        );
+        if let Some(replacement) = Self::in_tense(ctx.tokens, verb, from) {
+            let replacement = match_case(&replacement, &token.text);
+            issue.fixes.push(ctx.fix(
+                format!("Use the {} tense \"{}\"", from.name(), replacement),
+                token.start..token.end,
+                replacement,
+                Applicability::MaybeIncorrect,
+            ));
+        }
+        issue
+    }
+}
+
+impl Rule for VerbTense {
+    fn id(&self) -> &str {
+        "verb-tense"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Grammar
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    /// Coordinated verbs in different tenses: "He walked in and sits down"
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        // Subordinate and relative clauses may legitimately shift tense
+        let subordinate = ctx.tokens.iter().any(|t| t.tag == Tag::Subordinator || t.is_any_of(&["who", "which"]));
+        if subordinate || Self::is_quoted(ctx) {
+            return Vec::new();
+        }
+
+        let verbs = Self::main_verbs(ctx.tokens);
+        verbs
+            .windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .filter(|pair| ctx.tokens[pair[0].0..pair[1].0].iter().any(|t| t.tag == Tag::Conjunction))
+            .map(|pair| self.issue(ctx, pair[1].0, pair[0].1, pair[1].1, "sentence"))
+            .collect()
+    }
+
+    /// Sentences against the tense most of their paragraph is written in
+    fn check_paragraph(&self, sentences: &[SentenceContext]) -> Vec<GrammarIssue> {
+        let tensed: Vec<(&SentenceContext, usize, Tense)> = sentences
+            .iter()
+            .filter(|ctx| !Self::is_quoted(ctx))
+            .filter_map(|ctx| Self::main_verbs(ctx.tokens).first().map(|&(verb, tense)| (ctx, verb, tense)))
+            .collect();
+        let past = tensed.iter().filter(|(_, _, tense)| *tense == Tense::Past).count();
+        let present = tensed.len() - past;
+
+        // Only a clear majority sets the paragraph's tense
+        let dominant = match (past, present) {
+            _ if tensed.len() < 3 => return Vec::new(),
+            (past, present) if past >= 2 * present => Tense::Past,
+            (past, present) if present >= 2 * past => Tense::Present,
+            _ => return Vec::new(),
+        };
+        tensed
+            .iter()
+            .filter(|(_, _, tense)| *tense != dominant)
+            .map(|&(ctx, verb, tense)| self.issue(ctx, verb, dominant, tense, "paragraph"))
+            .collect()
+    }
+}
+
+pub struct RepeatedWord;
+
+impl Rule for RepeatedWord {
+    fn id(&self) -> &str {
+        "repeated-word"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Typography
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// Tokens skip whitespace, so "the\nthe" across a line break is caught too
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        ctx.tokens
+            .windows(2)
+            .filter(|pair| pair[0].lower == pair[1].lower && pair[0].is_word() && pair[0].tag != Tag::Number)
+            .filter(|pair| !pair[0].is_any_of(LEGIT_DOUBLES))
+            .map(|pair| {
+                let (first, second) = (&pair[0], &pair[1]);
+                let mut issue = ctx.issue(
+                    self,
+                    GrammarIssueType::RepeatedWord,
+                    format!("Repeated word \"{}\"", second.text),
+                    first.start..second.end,
+                );
+                let fix = ctx.fix("Remove the repeated word", first.end..second.end, "", Applicability::MachineApplicable);
+                issue.fixes.push(fix);
+                issue
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::grammar::GrammarChecker;
+
+    /// Flags every sentence mentioning "synergy"
+    struct NoSynergy;
+
+    impl Rule for NoSynergy {
+        fn id(&self) -> &str {
+            "no-synergy"
+        }
+
+        fn category(&self) -> RuleCategory {
+            RuleCategory::Usage
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Low
+        }
+
+        fn enabled_by_default(&self) -> bool {
+            false
+        }
+
+        fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+            ctx.lower
+                .text
+                .find("synergy")
+                .map(|pos| ctx.issue(self, GrammarIssueType::Other, "Avoid \"synergy\"", pos..pos + 7))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    fn ids(registry: &RuleRegistry, settings: &RuleSettings) -> Vec<String> {
+        registry.active(settings).iter().map(|r| r.id().to_string()).collect()
+    }
+
+    #[test]
+    fn test_rules_are_toggled_by_id() {
+        let registry = RuleRegistry::default();
+        assert_eq!(ids(&registry, &RuleSettings::default()).len(), 12);
+
+        let settings = RuleSettings {
+            disabled: vec!["comma-splice".to_string()],
+            enabled: vec!["comma-splice".to_string()],
+        };
+        assert!(!ids(&registry, &settings).contains(&"comma-splice".to_string()));
+    }
+
+    #[test]
+    fn test_custom_rule_runs_when_enabled() {
+        let mut registry = RuleRegistry::default();
+        registry.register(NoSynergy);
+        let sentences = vec!["We value Synergy above all.".to_string()];
+
+        let checker = GrammarChecker::with_rules(&registry, &RuleSettings::default());
+        assert!(checker.check(&sentences).unwrap().is_empty());
+
+        let settings = RuleSettings {
+            enabled: vec!["no-synergy".to_string()],
+            ..RuleSettings::default()
+        };
+        let issues = GrammarChecker::with_rules(&registry, &settings).check(&sentences).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule_id, "no-synergy");
+        assert_eq!(&sentences[0][issues[0].span.byte_range()], "Synergy");
+    }
+
+    #[test]
+    fn test_added_rules_keep_config_rules() {
+        let mut config = Config::default();
+        config.glossary.banned.insert("e-mail".to_string(), "email".to_string());
+        config.rules.enabled.push("no-synergy".to_string());
+        let mut registry = RuleRegistry::empty();
+        registry.register(NoSynergy);
+
+        let checker = GrammarChecker::from_config(&config).unwrap().with_added_rules(&registry, &config.rules);
+        let ids: Vec<&str> = checker.rule_ids().collect();
+        assert_eq!(ids.len(), 14);
+        assert!(ids.contains(&"glossary") && ids.contains(&"no-synergy"));
+    }
+
+    #[test]
+    fn test_register_replaces_rule_with_same_id() {
+        struct QuietDoubleSpace;
+
+        impl Rule for QuietDoubleSpace {
+            fn id(&self) -> &str {
+                "double-space"
+            }
+
+            fn category(&self) -> RuleCategory {
+                RuleCategory::Typography
+            }
+
+            fn default_severity(&self) -> Severity {
+                Severity::Low
+            }
+
+            fn check(&self, _ctx: &SentenceContext) -> Vec<GrammarIssue> {
+                Vec::new()
+            }
+        }
+
+        let mut registry = RuleRegistry::default();
+        registry.register(QuietDoubleSpace);
+        assert_eq!(registry.rules().count(), 12);
+
+        let checker = GrammarChecker::with_rules(&registry, &RuleSettings::default());
+        let issues = checker.check(&["We left two  spaces here.".to_string()]).unwrap();
+        assert!(issues.is_empty());
+    }
+}
@@ -1,21 +1,26 @@
 use crate::dictionaries::abbreviations::is_abbreviation;
-use crate::error::Result;
+use crate::error::{Deadline, Result};
+use crate::span::{OffsetIndex, Span};
 use regex::Regex;
 use lazy_static::lazy_static;
+use std::ops::Range;
 
 lazy_static! {
     /// Regex for decimal numbers (3.14, 2.5, etc.)
     static ref DECIMAL_PATTERN: Regex = Regex::new(r"\d+\.\d+").unwrap();
     
     /// Regex for URLs
-    static ref URL_PATTERN: Regex = Regex::new(
+    pub(crate) static ref URL_PATTERN: Regex = Regex::new(
         r"(?:https?://|www\.)[^\s]+"
     ).unwrap();
     
     /// Regex for email addresses
-    static ref EMAIL_PATTERN: Regex = Regex::new(
+    pub(crate) static ref EMAIL_PATTERN: Regex = Regex::new(
         r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b"
     ).unwrap();
+
+    /// Regex for inline code in backticks
+    static ref CODE_SPAN: Regex = Regex::new(r"`[^`\n]*`").unwrap();
     
     /// Regex for initials (J.K., U.S.A., etc.)
     static ref INITIALS_PATTERN: Regex = Regex::new(
@@ -48,45 +53,96 @@ impl SentenceSplitter {
 
     /// Split text into sentences with comprehensive boundary detection
     pub fn split(&self, text: &str) -> Result<Vec<String>> {
-        if text.trim().is_empty() {
-            return Ok(Vec::new());
+        self.split_with_deadline(text, &Deadline::none())
+    }
+
+    /// Split text into sentences, stopping with `TimeoutError` once `deadline` passes
+    pub fn split_with_deadline(&self, text: &str, deadline: &Deadline) -> Result<Vec<String>> {
+        Ok(self
+            .split_spans_with_deadline(text, deadline)?
+            .into_iter()
+            .map(|s| s.text)
+            .collect())
+    }
+
+    /// Split text into sentences with their exact location and paragraph
+    ///
+    /// Paragraphs are separated by blank lines and sentences never cross them.
+    pub fn split_spans(&self, text: &str) -> Result<Vec<SentenceSpan>> {
+        self.split_spans_with_deadline(text, &Deadline::none())
+    }
+
+    /// `split_spans`, stopping with `TimeoutError` once `deadline` passes
+    pub fn split_spans_with_deadline(&self, text: &str, deadline: &Deadline) -> Result<Vec<SentenceSpan>> {
+        self.split_blocks(text, &paragraph_ranges(text), deadline)
+    }
+
+    /// Split each block of `text` into sentences; `paragraph` is the block's index
+    ///
+    /// Blocks are byte ranges, e.g. the units of an extracted document.
+    pub fn split_blocks(
+        &self,
+        text: &str,
+        blocks: &[Range<usize>],
+        deadline: &Deadline,
+    ) -> Result<Vec<SentenceSpan>> {
+        let index = OffsetIndex::new(text);
+        let mut sentences = Vec::new();
+        for (paragraph, block) in blocks.iter().enumerate() {
+            deadline.check()?;
+            for range in self.split_block(&text[block.clone()], deadline)? {
+                let (start, end) = (block.start + range.start, block.start + range.end);
+                sentences.push(SentenceSpan {
+                    text: text[start..end].to_string(),
+                    span: index.span(start, end),
+                    paragraph,
+                });
+            }
         }
+        Ok(sentences)
+    }
 
+    /// Byte ranges of the trimmed sentences of one block
+    fn split_block(&self, text: &str, deadline: &Deadline) -> Result<Vec<Range<usize>>> {
         let mut sentences = Vec::new();
-        let mut current_sentence = String::new();
         let chars: Vec<char> = text.chars().collect();
-        let mut i = 0;
+        let mut sentence_start = 0;
+        let mut byte = 0;
 
-        while i < chars.len() {
-            let ch = chars[i];
-            current_sentence.push(ch);
+        for (i, &ch) in chars.iter().enumerate() {
+            if i % 4096 == 0 {
+                deadline.check()?;
+            }
+            byte += ch.len_utf8();
 
             // Check if this might be a sentence boundary
             if self.is_sentence_terminator(ch) {
                 let context = self.extract_context(&chars, i);
-                
-                if self.is_sentence_boundary(&context, &current_sentence) {
+
+                if self.is_sentence_boundary(&context, &text[sentence_start..byte]) {
                     // This is a real sentence boundary
-                    let sentence = current_sentence.trim().to_string();
-                    if sentence.len() >= self.min_sentence_length {
-                        sentences.push(sentence);
-                    }
-                    current_sentence.clear();
+                    self.push_trimmed(text, sentence_start..byte, &mut sentences);
+                    sentence_start = byte;
                 }
             }
-
-            i += 1;
         }
 
         // Add any remaining text as a sentence
-        let sentence = current_sentence.trim().to_string();
-        if sentence.len() >= self.min_sentence_length {
-            sentences.push(sentence);
-        }
+        self.push_trimmed(text, sentence_start..text.len(), &mut sentences);
 
         Ok(sentences)
     }
 
+    /// Record the trimmed part of `range` if it is long enough to be a sentence
+    fn push_trimmed(&self, text: &str, range: Range<usize>, sentences: &mut Vec<Range<usize>>) {
+        let raw = &text[range.clone()];
+        let start = range.start + (raw.len() - raw.trim_start().len());
+        let len = raw.trim().len();
+        if len >= self.min_sentence_length {
+            sentences.push(start..start + len);
+        }
+    }
+
     /// Check if character is a potential sentence terminator
     fn is_sentence_terminator(&self, ch: char) -> bool {
         matches!(ch, '.' | '!' | '?')
@@ -291,6 +347,41 @@ impl SentenceSplitter {
     }
 }
 
+/// Byte ranges of URLs, email addresses and `code spans` in `text`, which hold no prose to check
+pub(crate) fn verbatim_ranges(text: &str) -> Vec<Range<usize>> {
+    URL_PATTERN
+        .find_iter(text)
+        .chain(EMAIL_PATTERN.find_iter(text))
+        .chain(CODE_SPAN.find_iter(text))
+        .map(|m| m.range())
+        .collect()
+}
+
+/// A sentence and where it sits in the split text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceSpan {
+    /// Trimmed sentence text
+    pub text: String,
+    pub span: Span,
+    /// Index of the paragraph (or block) containing the sentence
+    pub paragraph: usize,
+}
+
+/// Byte ranges of the trimmed, non-empty paragraphs (blank-line separated)
+pub fn paragraph_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for piece in text.split("\n\n") {
+        let start = offset + (piece.len() - piece.trim_start().len());
+        let trimmed = piece.trim();
+        if !trimmed.is_empty() {
+            ranges.push(start..start + trimmed.len());
+        }
+        offset += piece.len() + 2;
+    }
+    ranges
+}
+
 #[derive(Debug)]
 struct SentenceContext {
     punctuation: char,
@@ -372,4 +463,18 @@ mod tests {
         let sentences = splitter.split("").unwrap();
         assert_eq!(sentences.len(), 0);
     }
+
+    #[test]
+    fn test_spans_locate_sentences() {
+        let splitter = SentenceSplitter::default();
+        let text = "  Café time.   It was late!\n\nNew paragraph\n\n\nlast one.";
+        let sentences = splitter.split_spans(text).unwrap();
+
+        let found: Vec<(&str, usize)> = sentences.iter().map(|s| (s.text.as_str(), s.paragraph)).collect();
+        assert_eq!(found, vec![("Café time.", 0), ("It was late!", 0), ("New paragraph", 1), ("last one.", 2)]);
+        for sentence in &sentences {
+            assert_eq!(&text[sentence.span.byte_range()], sentence.text);
+        }
+        assert_eq!(sentences[1].span.char_start, 15);
+    }
 }
@@ -0,0 +1,559 @@
+// Incremental analysis
+// Keeps per-paragraph results so an edit only re-analyzes the paragraphs it touches
+
+use crate::analysis_reports::*;
+use crate::comprehensive_analysis::{counted_lemma, ComprehensiveAnalyzer};
+use crate::config::Config;
+use crate::dictionaries::count_syllables;
+use crate::error::{AnalysisError, Deadline, Result, ValidationConfig};
+use crate::grammar::{GrammarChecker, GrammarIssue, PassiveVoiceDetector, PassiveVoiceMatch, SentenceSplitter};
+pub use crate::grammar::paragraph_ranges;
+use crate::span::{Lowercased, OffsetIndex, Span};
+use crate::word_lists::GLUE_WORDS;
+use crate::TextStatistics;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+lazy_static! {
+    static ref WORD_PATTERN: Regex = Regex::new(r"\b[\p{L}\p{N}]+(?:[-'][\p{L}\p{N}]+)*\b").unwrap();
+}
+
+/// Summary numbers for one paragraph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParagraphStats {
+    pub word_count: usize,
+    pub sentence_count: usize,
+    pub syllable_count: usize,
+    pub glue_count: usize,
+    pub glue_index: f64,
+    pub avg_sentence_length: f64,
+    pub grammar_issue_count: usize,
+    pub passive_voice_count: usize,
+}
+
+/// Analysis of a single paragraph, with findings relative to the paragraph start
+#[derive(Debug, Clone)]
+pub struct ParagraphAnalysis {
+    /// Byte range of the paragraph in the document
+    pub range: Range<usize>,
+    pub sentences: Vec<String>,
+    pub stats: ParagraphStats,
+    grammar: Vec<GrammarIssue>,
+    passive_voice: Vec<PassiveVoiceMatch>,
+    sticky: StickySentencesReport,
+    echoes: Option<EchoesReport>,
+    /// Lowercased word -> byte ranges, the paragraph's contribution to overused words
+    word_positions: HashMap<String, Vec<(usize, usize)>>,
+    /// Whitespace-separated tokens, the unit of `PassiveVoiceMatch::position`
+    token_count: usize,
+}
+
+/// A document that is analyzed once and then kept up to date edit by edit
+///
+/// Paragraphs are split the same way as `TextAnalyzer` (on blank lines).
+/// Document-wide reports are assembled from the cached paragraph results, so
+/// an edit costs a re-split of the paragraph boundaries plus a re-analysis of
+/// the paragraphs whose text changed.
+pub struct IncrementalDocument {
+    text: String,
+    config: Config,
+    /// Built once from `config`, as loading dictionaries and glossaries is not cheap
+    grammar_checker: GrammarChecker,
+    paragraphs: Vec<ParagraphAnalysis>,
+}
+
+impl IncrementalDocument {
+    /// Analyze `text` paragraph by paragraph
+    pub fn new(text: String, config: Config) -> Result<Self> {
+        let validator = Self::validator(&config);
+        validator.validate_text(&text)?;
+
+        let grammar_checker = GrammarChecker::from_config(&config)?;
+        let ranges = paragraph_ranges(&text);
+        let paragraphs = analyze_paragraphs(&text, &ranges, &config, &grammar_checker, &validator.deadline())?;
+        Ok(Self {
+            text,
+            config,
+            grammar_checker,
+            paragraphs,
+        })
+    }
+
+    /// Replace `range` (byte offsets) with `replacement` and re-analyze the affected paragraphs
+    ///
+    /// Returns the indices of the paragraphs that were re-analyzed. On error
+    /// the document is left as it was.
+    pub fn apply_edit(&mut self, range: Range<usize>, replacement: &str) -> Result<Range<usize>> {
+        if range.start > range.end
+            || range.end > self.text.len()
+            || !self.text.is_char_boundary(range.start)
+            || !self.text.is_char_boundary(range.end)
+        {
+            return Err(AnalysisError::ValidationError(format!(
+                "Edit range {}..{} is not a valid range of the {}-byte text",
+                range.start,
+                range.end,
+                self.text.len()
+            )));
+        }
+
+        let mut text = self.text.clone();
+        text.replace_range(range.clone(), replacement);
+        let validator = Self::validator(&self.config);
+        validator.validate_text(&text)?;
+
+        let new_ranges = paragraph_ranges(&text);
+        let delta = replacement.len() as isize - range.len() as isize;
+        let old = &self.paragraphs;
+
+        // Paragraphs wholly before or after the edit keep their analysis when
+        // their boundaries are unchanged
+        let mut prefix = 0;
+        while prefix < old.len().min(new_ranges.len())
+            && old[prefix].range.end < range.start
+            && old[prefix].range == new_ranges[prefix]
+        {
+            prefix += 1;
+        }
+        let mut suffix = 0;
+        while suffix < (old.len() - prefix).min(new_ranges.len() - prefix) {
+            let old_para = &old[old.len() - 1 - suffix];
+            if old_para.range.start > range.end
+                && shift(&old_para.range, delta) == new_ranges[new_ranges.len() - 1 - suffix]
+            {
+                suffix += 1;
+            } else {
+                break;
+            }
+        }
+
+        let changed = prefix..new_ranges.len() - suffix;
+        let analyzed = analyze_paragraphs(
+            &text,
+            &new_ranges[changed.clone()],
+            &self.config,
+            &self.grammar_checker,
+            &validator.deadline(),
+        )?;
+
+        let old_len = self.paragraphs.len();
+        self.paragraphs.splice(prefix..old_len - suffix, analyzed);
+        for para in &mut self.paragraphs[changed.end..] {
+            para.range = shift(&para.range, delta);
+        }
+        self.text = text;
+        Ok(changed)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Per-paragraph results in document order
+    pub fn paragraphs(&self) -> &[ParagraphAnalysis] {
+        &self.paragraphs
+    }
+
+    /// Document statistics summed from the paragraphs
+    pub fn statistics(&self) -> TextStatistics {
+        TextStatistics {
+            word_count: self.paragraphs.iter().map(|p| p.stats.word_count).sum(),
+            sentence_count: self.paragraphs.iter().map(|p| p.stats.sentence_count).sum(),
+            paragraph_count: self.paragraphs.len(),
+            character_count: self.text.chars().count(),
+            character_count_no_spaces: self.text.chars().filter(|c| !c.is_whitespace()).count(),
+        }
+    }
+
+    /// Grammar issues with document offsets and sentence numbers
+    pub fn grammar_issues(&self) -> Vec<GrammarIssue> {
+        let index = OffsetIndex::new(&self.text);
+        self.with_sentence_offsets()
+            .flat_map(|(para, first_sentence)| {
+                let index = &index;
+                para.grammar.iter().map(move |issue| {
+                    let shift = |span: &Span| index.span(span.byte_start + para.range.start, span.byte_end + para.range.start);
+                    let mut fixes = issue.fixes.clone();
+                    for edit in fixes.iter_mut().flat_map(|f| &mut f.edits) {
+                        edit.span = shift(&edit.span);
+                    }
+                    GrammarIssue {
+                        sentence_num: issue.sentence_num + first_sentence,
+                        start_index: issue.start_index + para.range.start,
+                        end_index: issue.end_index + para.range.start,
+                        span: shift(&issue.span),
+                        fixes,
+                        ..issue.clone()
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Passive voice matches with document offsets
+    pub fn passive_voice(&self) -> Vec<PassiveVoiceMatch> {
+        let index = OffsetIndex::new(&self.text);
+        let mut first_token = 0;
+        let mut matches = Vec::new();
+        for para in &self.paragraphs {
+            matches.extend(para.passive_voice.iter().map(|m| {
+                let (start, end) = (m.start_index + para.range.start, m.end_index + para.range.start);
+                let mut fix = m.fix.clone();
+                for edit in fix.iter_mut().flat_map(|f| &mut f.edits) {
+                    edit.span = index.span(edit.span.byte_start + para.range.start, edit.span.byte_end + para.range.start);
+                }
+                PassiveVoiceMatch {
+                    position: m.position + first_token,
+                    start_index: start,
+                    end_index: end,
+                    span: index.span(start, end),
+                    fix,
+                    ..m.clone()
+                }
+            }));
+            first_token += para.token_count;
+        }
+        matches
+    }
+
+    /// Sticky sentences, with the glue index recomputed from paragraph counts
+    pub fn sticky_sentences(&self) -> StickySentencesReport {
+        let total_words: usize = self.paragraphs.iter().map(|p| p.stats.word_count).sum();
+        let total_glue: usize = self.paragraphs.iter().map(|p| p.stats.glue_count).sum();
+        let glue_index = round1(percentage(total_glue, total_words));
+
+        let index = OffsetIndex::new(&self.text);
+        let shifted = |list: fn(&StickySentencesReport) -> &Vec<StickySentence>| -> Vec<StickySentence> {
+            self.with_sentence_offsets()
+                .flat_map(|(para, first_sentence)| {
+                    let index = &index;
+                    list(&para.sticky).iter().map(move |s| {
+                        let (start, end) = (s.start_index + para.range.start, s.end_index + para.range.start);
+                        StickySentence {
+                            sentence_num: s.sentence_num + first_sentence,
+                            start_index: start,
+                            end_index: end,
+                            span: index.span(start, end),
+                            ..s.clone()
+                        }
+                    })
+                })
+                .collect()
+        };
+        let sticky_sentences = shifted(|r| &r.sticky_sentences);
+
+        StickySentencesReport {
+            overall_glue_index: glue_index,
+            glue_index,
+            sticky_sentence_count: sticky_sentences.len(),
+            sticky_sentences,
+            semi_sticky_sentences: shifted(|r| &r.semi_sticky_sentences),
+        }
+    }
+
+    /// Overused words, merged from the paragraphs' word counts
+    pub fn overused_words(&self) -> OverusedWordsReport {
+        let index = OffsetIndex::new(&self.text);
+        let mut positions: HashMap<&str, Vec<(usize, usize)>> = HashMap::new();
+        for para in &self.paragraphs {
+            for (word, ranges) in &para.word_positions {
+                let start = para.range.start;
+                positions
+                    .entry(word.as_str())
+                    .or_default()
+                    .extend(ranges.iter().map(|&(s, e)| (s + start, e + start)));
+            }
+        }
+
+        let total_words: usize = self.paragraphs.iter().map(|p| p.stats.word_count).sum();
+        let total_unique_words = positions.len();
+        let mut by_lemma: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (word, ranges) in positions {
+            if let Some(lemma) = counted_lemma(word) {
+                by_lemma.entry(lemma).or_default().extend(ranges);
+            }
+        }
+        let mut overused_words: Vec<OverusedWord> = by_lemma
+            .into_iter()
+            .filter_map(|(word, mut starts)| {
+                starts.sort_unstable();
+                let frequency = percentage(starts.len(), total_words);
+                (frequency > self.config.thresholds.overused_word_threshold).then(|| OverusedWord {
+                    word,
+                    count: starts.len(),
+                    frequency: (frequency * 100.0).round() / 100.0,
+                    occurrences: starts
+                        .iter()
+                        .map(|&(start, end)| WordOccurrence {
+                            start_index: start,
+                            end_index: end,
+                            length: end - start,
+                            span: index.span(start, end),
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+        overused_words.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+
+        OverusedWordsReport {
+            overused_words,
+            total_unique_words,
+        }
+    }
+
+    /// Echoes from every paragraph (None when echo_detection is disabled)
+    pub fn echoes(&self) -> Option<EchoesReport> {
+        if !self.config.features.echo_detection {
+            return None;
+        }
+        let index = OffsetIndex::new(&self.text);
+        let mut total_echoes = 0;
+        let mut echoes: Vec<Echo> = Vec::new();
+        for (idx, para) in self.paragraphs.iter().enumerate() {
+            let Some(report) = &para.echoes else { continue };
+            total_echoes += report.total_echoes;
+            echoes.extend(report.echoes.iter().map(|echo| Echo {
+                paragraph: idx + 1,
+                positions: echo
+                    .positions
+                    .iter()
+                    .map(|p| {
+                        let (start, end) = (p.start_index + para.range.start, p.end_index + para.range.start);
+                        WordOccurrence {
+                            start_index: start,
+                            end_index: end,
+                            length: p.length,
+                            span: index.span(start, end),
+                        }
+                    })
+                    .collect(),
+                ..echo.clone()
+            }));
+        }
+        echoes.sort_by_key(|e| e.distance);
+        echoes.truncate(50);
+        Some(EchoesReport { total_echoes, echoes })
+    }
+
+    /// Paragraphs paired with the number of sentences before them
+    fn with_sentence_offsets(&self) -> impl Iterator<Item = (&ParagraphAnalysis, usize)> {
+        self.paragraphs.iter().scan(0, |seen, para| {
+            let first = *seen;
+            *seen += para.stats.sentence_count;
+            Some((para, first))
+        })
+    }
+
+    fn validator(config: &Config) -> ValidationConfig {
+        ValidationConfig {
+            max_file_size: config.validation.max_file_size_mb * 1024 * 1024,
+            min_words: config.validation.min_words,
+            max_words: config.validation.max_words,
+            timeout_seconds: config.validation.timeout_seconds,
+        }
+    }
+}
+
+fn analyze_paragraphs(
+    text: &str,
+    ranges: &[Range<usize>],
+    config: &Config,
+    grammar_checker: &GrammarChecker,
+    deadline: &Deadline,
+) -> Result<Vec<ParagraphAnalysis>> {
+    #[cfg(feature = "parallel")]
+    if config.analysis.parallel_processing {
+        use rayon::prelude::*;
+        return ranges
+            .par_iter()
+            .map(|range| analyze_paragraph(text, range.clone(), config, grammar_checker, deadline))
+            .collect();
+    }
+    ranges
+        .iter()
+        .map(|range| analyze_paragraph(text, range.clone(), config, grammar_checker, deadline))
+        .collect()
+}
+
+fn analyze_paragraph(
+    text: &str,
+    range: Range<usize>,
+    config: &Config,
+    grammar_checker: &GrammarChecker,
+    deadline: &Deadline,
+) -> Result<ParagraphAnalysis> {
+    deadline.check()?;
+    let paragraph = &text[range.clone()];
+    let spans = SentenceSplitter::default().split_spans_with_deadline(paragraph, deadline)?;
+    let sentences: Vec<String> = spans.iter().map(|s| s.text.clone()).collect();
+
+    let lower = Lowercased::new(paragraph);
+    let mut words = Vec::new();
+    let mut word_positions: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    for m in WORD_PATTERN.find_iter(&lower.text) {
+        words.push(m.as_str().to_string());
+        word_positions
+            .entry(m.as_str().to_string())
+            .or_default()
+            .push((lower.original(m.start()), lower.original(m.end())));
+    }
+
+    let grammar = if config.features.grammar_check {
+        grammar_checker.check_spans(paragraph, &spans, deadline)?
+    } else {
+        Vec::new()
+    };
+    let passive_voice = if config.features.style_check {
+        PassiveVoiceDetector::default().detect_with_deadline(paragraph, deadline)?
+    } else {
+        Vec::new()
+    };
+
+    let paragraphs = [paragraph.to_string()];
+    let analyzer = ComprehensiveAnalyzer::new(paragraph, &spans, &paragraphs, &words, config)
+        .with_parallel(false)
+        .with_deadline(*deadline);
+    let sticky = analyzer.analyze_sticky_sentences()?;
+    let echoes = if config.features.echo_detection {
+        Some(analyzer.analyze_echoes()?)
+    } else {
+        None
+    };
+
+    let glue_count = words.iter().filter(|w| GLUE_WORDS.contains(w.as_str())).count();
+    let stats = ParagraphStats {
+        word_count: words.len(),
+        sentence_count: sentences.len(),
+        syllable_count: words.iter().map(|w| count_syllables(w)).sum(),
+        glue_count,
+        glue_index: round1(percentage(glue_count, words.len())),
+        avg_sentence_length: if sentences.is_empty() {
+            0.0
+        } else {
+            round1(words.len() as f64 / sentences.len() as f64)
+        },
+        grammar_issue_count: grammar.len(),
+        passive_voice_count: passive_voice.len(),
+    };
+
+    Ok(ParagraphAnalysis {
+        range,
+        sentences,
+        stats,
+        grammar,
+        passive_voice,
+        sticky,
+        echoes,
+        word_positions,
+        token_count: paragraph.split_whitespace().count(),
+    })
+}
+
+fn shift(range: &Range<usize>, delta: isize) -> Range<usize> {
+    (range.start as isize + delta) as usize..(range.end as isize + delta) as usize
+}
+
+fn percentage(part: usize, total: usize) -> f64 {
+    if total > 0 {
+        part as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "The river was crossed by the scouts at dawn. They moved quickly and quietly.\n\n\
+        The camp was a quiet place. Smoke rose from the fires and the river ran past the tents.\n\n\
+        By noon the scouts had returned with news of the valley beyond the hills.";
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.validation.min_words = 1;
+        config
+    }
+
+    fn issue_spans(doc: &IncrementalDocument) -> Vec<(usize, usize, usize)> {
+        doc.grammar_issues()
+            .iter()
+            .map(|i| (i.sentence_num, i.start_index, i.end_index))
+            .collect()
+    }
+
+    #[test]
+    fn test_paragraph_ranges() {
+        let text = "  One.\n\n\n\nTwo  \n\nThree";
+        let ranges = paragraph_ranges(text);
+        let texts: Vec<&str> = ranges.iter().map(|r| &text[r.clone()]).collect();
+        assert_eq!(texts, vec!["One.", "Two", "Three"]);
+    }
+
+    #[test]
+    fn test_edit_reanalyzes_only_touched_paragraph() {
+        let mut doc = IncrementalDocument::new(TEXT.to_string(), config()).unwrap();
+        let pos = TEXT.find("quiet place").unwrap();
+
+        let changed = doc.apply_edit(pos..pos + 5, "noisy").unwrap();
+        assert_eq!(changed, 1..2);
+
+        let fresh = IncrementalDocument::new(doc.text().to_string(), config()).unwrap();
+        assert_eq!(issue_spans(&doc), issue_spans(&fresh));
+        assert_eq!(doc.sticky_sentences().glue_index, fresh.sticky_sentences().glue_index);
+        assert_eq!(doc.passive_voice(), fresh.passive_voice());
+        assert_eq!(doc.statistics().word_count, fresh.statistics().word_count);
+    }
+
+    #[test]
+    fn test_edit_shifts_later_paragraphs() {
+        let mut doc = IncrementalDocument::new(TEXT.to_string(), config()).unwrap();
+        let before = doc.paragraphs()[2].range.clone();
+
+        let changed = doc.apply_edit(0..0, "At first light, ").unwrap();
+        assert_eq!(changed, 0..1);
+        assert_eq!(doc.paragraphs()[2].range, before.start + 16..before.end + 16);
+
+        let scouts: Vec<&str> = doc
+            .overused_words()
+            .overused_words
+            .iter()
+            .find(|w| w.word == "scout")
+            .unwrap()
+            .occurrences
+            .iter()
+            .map(|o| &doc.text()[o.start_index..o.end_index])
+            .collect();
+        assert_eq!(scouts, vec!["scouts", "scouts"]);
+    }
+
+    #[test]
+    fn test_paragraph_break_splits_paragraph() {
+        let mut doc = IncrementalDocument::new(TEXT.to_string(), config()).unwrap();
+        let pos = TEXT.find(" They moved").unwrap();
+
+        let changed = doc.apply_edit(pos..pos + 1, "\n\n").unwrap();
+        assert_eq!(changed, 0..2);
+        assert_eq!(doc.paragraphs().len(), 4);
+        assert_eq!(doc.statistics().sentence_count, 5);
+    }
+
+    #[test]
+    fn test_invalid_edit_leaves_document_unchanged() {
+        let mut doc = IncrementalDocument::new(TEXT.to_string(), config()).unwrap();
+        assert!(doc.apply_edit(10..TEXT.len() + 1, "x").is_err());
+        assert_eq!(doc.text(), TEXT);
+    }
+}
@@ -0,0 +1,368 @@
+// HTML input
+// Extracts visible prose from block elements and locates it in the raw source
+
+use super::{paragraph_kind, BlockKind, DocumentBuilder, ExtractedDocument};
+use scraper::{ElementRef, Html, Node};
+
+/// Elements whose content is never analyzed
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "nav", "pre", "code", "svg", "iframe",
+];
+
+/// Containers that separate prose units without forming one themselves
+const BREAKING_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "body", "details", "div", "dl", "fieldset", "figure",
+    "footer", "form", "header", "hr", "main", "ol", "section", "summary", "table", "tbody",
+    "thead", "tfoot", "tr", "ul",
+];
+
+/// Elements whose raw content is not text (their bodies are not searched)
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Extract the prose units of an HTML document
+pub fn extract(source: &str) -> ExtractedDocument {
+    let html = Html::parse_document(source);
+    let mut extractor = Extractor {
+        builder: DocumentBuilder::new(),
+        locator: SourceLocator::new(source),
+        item_depth: 0,
+        quote_depth: 0,
+    };
+    extractor.visit(html.root_element());
+    ExtractedDocument {
+        source: source.to_string(),
+        ..extractor.builder.finish()
+    }
+}
+
+/// Walks the DOM in document order, feeding visible text to the builder
+struct Extractor<'a> {
+    builder: DocumentBuilder,
+    locator: SourceLocator<'a>,
+    item_depth: usize,
+    quote_depth: usize,
+}
+
+impl Extractor<'_> {
+    fn visit(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.visit_element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            // Consume the hidden text so later lookups start after it
+            for text in element.text() {
+                self.locator.locate(text);
+            }
+            return;
+        }
+
+        match name {
+            "p" => {
+                // Paragraphs inside list items belong to the item's unit
+                let continues_item = self.builder.open_kind() == Some(BlockKind::ListItem)
+                    && self.builder.open_is_empty();
+                if !continues_item {
+                    self.builder.start_block(paragraph_kind(self.item_depth, self.quote_depth));
+                }
+                self.visit(element);
+                self.builder.end_block();
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.builder.start_block(BlockKind::Heading);
+                self.visit(element);
+                self.builder.end_block();
+            }
+            "li" => {
+                self.item_depth += 1;
+                self.builder.start_block(BlockKind::ListItem);
+                self.visit(element);
+                self.item_depth -= 1;
+                self.builder.end_block();
+            }
+            "blockquote" => {
+                self.builder.end_block();
+                self.quote_depth += 1;
+                self.visit(element);
+                self.quote_depth -= 1;
+                self.builder.end_block();
+            }
+            "td" | "th" => {
+                self.builder.start_block(BlockKind::TableCell);
+                self.visit(element);
+                self.builder.end_block();
+            }
+            "br" => self.builder.push_space(self.locator.cursor),
+            _ if BREAKING_ELEMENTS.contains(&name) => {
+                self.builder.end_block();
+                self.visit(element);
+                self.builder.end_block();
+            }
+            // Inline markup (a, em, strong, span, ...) is transparent
+            _ => self.visit(element),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.trim().is_empty() {
+            self.builder.push_space(self.locator.cursor);
+            return;
+        }
+        if self.builder.open_kind().is_none() {
+            // Loose text directly inside a container
+            self.builder.start_block(paragraph_kind(self.item_depth, self.quote_depth));
+        }
+
+        let spans = self.locator.locate(text);
+        let span_of = |idx: usize, fallback: usize| {
+            spans.as_ref().map_or((fallback, fallback), |s| s[idx])
+        };
+        let fallback = self.locator.cursor;
+
+        // Collapse whitespace: each word is copied with its own source range
+        let mut word: Option<(usize, usize)> = None; // byte offset, char index
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        for (idx, &(offset, c)) in chars.iter().enumerate() {
+            if c.is_whitespace() {
+                if let Some((start, first)) = word.take() {
+                    let (src_start, _) = span_of(first, fallback);
+                    let (_, src_end) = span_of(idx - 1, fallback);
+                    self.builder.push_text(&text[start..offset], src_start, src_end);
+                }
+                self.builder.push_space(span_of(idx, fallback).0);
+            } else if word.is_none() {
+                word = Some((offset, idx));
+            }
+        }
+        if let Some((start, first)) = word {
+            let (src_start, _) = span_of(first, fallback);
+            let (_, src_end) = span_of(chars.len() - 1, fallback);
+            self.builder.push_text(&text[start..], src_start, src_end);
+        }
+    }
+}
+
+/// Finds decoded DOM text in the raw HTML, moving forward through the source
+struct SourceLocator<'a> {
+    source: &'a str,
+    /// Byte ranges of character data (outside tags, comments and raw-text elements)
+    regions: Vec<(usize, usize)>,
+    cursor: usize,
+}
+
+impl<'a> SourceLocator<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            regions: text_regions(source),
+            cursor: 0,
+        }
+    }
+
+    /// Source byte range of every char of `text`, searching from the cursor
+    fn locate(&mut self, text: &str) -> Option<Vec<(usize, usize)>> {
+        if text.is_empty() {
+            return None;
+        }
+        let first = self.regions.partition_point(|r| r.1 <= self.cursor);
+        for &(region_start, region_end) in &self.regions[first..] {
+            let start = region_start.max(self.cursor);
+            for (offset, _) in self.source[start..region_end].char_indices() {
+                if let Some(spans) = self.match_at(start + offset, region_end, text) {
+                    self.cursor = spans.last().map_or(self.cursor, |s| s.1);
+                    return Some(spans);
+                }
+            }
+        }
+        None
+    }
+
+    /// Match `text` at `pos`, decoding character references in the source
+    fn match_at(&self, pos: usize, limit: usize, text: &str) -> Option<Vec<(usize, usize)>> {
+        let mut spans = Vec::with_capacity(text.len());
+        let mut p = pos;
+        for c in text.chars() {
+            let rest = &self.source[p..limit];
+            let len = if let Some(len) = decode_entity(rest).filter(|(d, _)| *d == c).map(|(_, l)| l) {
+                len
+            } else if c == '\n' && rest.starts_with("\r\n") {
+                2
+            } else if rest.starts_with(c) {
+                c.len_utf8()
+            } else {
+                return None;
+            };
+            spans.push((p, p + len));
+            p += len;
+        }
+        Some(spans)
+    }
+}
+
+/// Byte ranges of the source that hold character data
+fn text_regions(source: &str) -> Vec<(usize, usize)> {
+    let bytes = source.as_bytes();
+    let mut regions = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+        let markup_end = if source[i..].starts_with("<!--") {
+            source[i + 4..].find("-->").map_or(bytes.len(), |e| i + 4 + e + 3)
+        } else if bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic() || b"/!?".contains(b)) {
+            let open_end = tag_end(bytes, i);
+            let name: String = source[i + 1..open_end]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                let close = format!("</{}", name);
+                source[open_end..]
+                    .to_ascii_lowercase()
+                    .find(&close)
+                    .map_or(bytes.len(), |e| tag_end(bytes, open_end + e))
+            } else {
+                open_end
+            }
+        } else {
+            // A literal '<' in text
+            i += 1;
+            continue;
+        };
+
+        if i > start {
+            regions.push((start, i));
+        }
+        start = markup_end;
+        i = markup_end;
+    }
+    if start < bytes.len() {
+        regions.push((start, bytes.len()));
+    }
+    regions
+}
+
+/// Offset just past the '>' closing the tag that opens at `start`
+fn tag_end(bytes: &[u8], start: usize) -> usize {
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate().skip(start + 1) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return i + 1,
+            None => {}
+        }
+    }
+    bytes.len()
+}
+
+/// Decode a character reference at the start of `s`, returning the char and its length
+fn decode_entity(s: &str) -> Option<(char, usize)> {
+    if !s.starts_with('&') {
+        return None;
+    }
+    let end = s[..s.len().min(12)].find(';')?;
+    let name = &s[1..end];
+    let c = if let Some(num) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        char::from_u32(u32::from_str_radix(num, 16).ok()?)?
+    } else if let Some(num) = name.strip_prefix('#') {
+        char::from_u32(num.parse().ok()?)?
+    } else {
+        match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{a0}',
+            "ndash" => '\u{2013}',
+            "mdash" => '\u{2014}',
+            "lsquo" => '\u{2018}',
+            "rsquo" => '\u{2019}',
+            "ldquo" => '\u{201c}',
+            "rdquo" => '\u{201d}',
+            "hellip" => '\u{2026}',
+            "copy" => '\u{a9}',
+            _ => return None,
+        }
+    };
+    Some((c, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_block_elements() {
+        let source = "<html><head><title>Help</title><style>p { color: red; }</style></head>\
+            <body><nav><a href=\"/\">Home</a></nav><h1>Getting Started</h1>\
+            <p>First paragraph.</p><ul><li>one item</li><li>another item</li></ul>\
+            <script>var x = 1;</script></body></html>";
+        let doc = extract(source);
+
+        assert_eq!(
+            doc.block_texts(),
+            vec!["Getting Started", "First paragraph.", "one item", "another item"]
+        );
+        let kinds: Vec<BlockKind> = doc.blocks.iter().map(|b| b.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![BlockKind::Heading, BlockKind::Paragraph, BlockKind::ListItem, BlockKind::ListItem]
+        );
+    }
+
+    #[test]
+    fn test_paragraphs_follow_the_dom() {
+        // No blank lines in the source, and a blank line inside one paragraph
+        let source = "<div><p>One.</p><p>Two\n\n   spans lines.</p></div>";
+        let doc = extract(source);
+
+        assert_eq!(doc.block_texts(), vec!["One.", "Two spans lines."]);
+    }
+
+    #[test]
+    fn test_offsets_map_to_source() {
+        let source = "<p>The <em>quick</em> fox &amp; the hound.</p>";
+        let doc = extract(source);
+
+        assert_eq!(doc.text, "The quick fox & the hound.");
+        let pos = doc.text.find("quick").unwrap();
+        let (start, end) = doc.source_map.range_to_source(pos, pos + "quick".len());
+        assert_eq!(&source[start..end], "quick");
+
+        let pos = doc.text.find("hound").unwrap();
+        let (start, end) = doc.source_map.range_to_source(pos, pos + "hound".len());
+        assert_eq!(&source[start..end], "hound");
+
+        let pos = doc.text.find('&').unwrap();
+        let (start, end) = doc.source_map.range_to_source(pos, pos + 1);
+        assert_eq!(&source[start..end], "&amp;");
+    }
+
+    #[test]
+    fn test_hidden_text_does_not_shift_offsets() {
+        let source = "<nav>Home</nav><p>Home is where the heart is.</p>";
+        let doc = extract(source);
+
+        let (start, _) = doc.source_map.range_to_source(0, 4);
+        assert_eq!(start, source.rfind("Home").unwrap());
+    }
+}
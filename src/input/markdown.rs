@@ -0,0 +1,133 @@
+// Markdown input
+// Extracts prose from CommonMark, skipping code, HTML, images and link targets
+
+use super::{paragraph_kind, BlockKind, DocumentBuilder, ExtractedDocument};
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+
+/// Extract the prose units of a Markdown document
+pub fn extract(source: &str) -> ExtractedDocument {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+
+    let mut builder = DocumentBuilder::new();
+    let mut item_depth = 0usize;
+    let mut quote_depth = 0usize;
+    // Depth of containers whose text is not prose (code, images, autolinks, metadata)
+    let mut skip_depth = 0usize;
+
+    for (event, range) in Parser::new_ext(source, options).into_offset_iter() {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Heading { .. } => builder.start_block(BlockKind::Heading),
+                Tag::Paragraph => {
+                    // Paragraphs of loose list items belong to the item's unit
+                    let continues_item = builder.open_kind() == Some(BlockKind::ListItem)
+                        && builder.open_is_empty();
+                    if !continues_item {
+                        builder.start_block(paragraph_kind(item_depth, quote_depth));
+                    }
+                }
+                Tag::Item => {
+                    item_depth += 1;
+                    builder.start_block(BlockKind::ListItem);
+                }
+                Tag::BlockQuote => quote_depth += 1,
+                Tag::TableCell => builder.start_block(BlockKind::TableCell),
+                Tag::CodeBlock(_) | Tag::HtmlBlock | Tag::MetadataBlock(_) | Tag::Image { .. } => {
+                    skip_depth += 1;
+                }
+                Tag::Link { link_type: LinkType::Autolink | LinkType::Email, .. } => skip_depth += 1,
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Heading(_) | TagEnd::Paragraph | TagEnd::TableCell => builder.end_block(),
+                TagEnd::Item => {
+                    item_depth = item_depth.saturating_sub(1);
+                    builder.end_block();
+                }
+                TagEnd::BlockQuote => quote_depth = quote_depth.saturating_sub(1),
+                TagEnd::CodeBlock | TagEnd::HtmlBlock | TagEnd::MetadataBlock(_) | TagEnd::Image => {
+                    skip_depth = skip_depth.saturating_sub(1);
+                }
+                TagEnd::Link if skip_depth > 0 => skip_depth -= 1,
+                _ => {}
+            },
+            Event::Text(text) if skip_depth == 0 => {
+                if builder.open_kind().is_none() {
+                    // Text of a tight list item after a nested list closed
+                    builder.start_block(paragraph_kind(item_depth, quote_depth));
+                }
+                builder.push_text(&text, range.start, range.end);
+            }
+            Event::SoftBreak | Event::HardBreak if skip_depth == 0 => builder.push_space(range.start),
+            // Inline code, raw HTML, footnote markers and rules are not prose
+            _ => {}
+        }
+    }
+
+    ExtractedDocument {
+        source: source.to_string(),
+        ..builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_code_blocks_and_inline_code() {
+        let source = "Intro text here.\n\n```rust\nfn main() {}\n```\n\nRun `cargo build` now.\n";
+        let doc = extract(source);
+
+        assert_eq!(doc.block_texts(), vec!["Intro text here.", "Run now."]);
+        assert!(!doc.text.contains("fn main"));
+    }
+
+    #[test]
+    fn test_headings_and_list_items_are_units() {
+        let source = "# Getting Started\n\nFirst paragraph.\n\n- one item\n- another item\n";
+        let doc = extract(source);
+
+        let kinds: Vec<BlockKind> = doc.blocks.iter().map(|b| b.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![BlockKind::Heading, BlockKind::Paragraph, BlockKind::ListItem, BlockKind::ListItem]
+        );
+        assert_eq!(doc.block_texts()[0], "Getting Started");
+    }
+
+    #[test]
+    fn test_links_keep_text_and_drop_urls() {
+        let source = "Read [the guide](https://example.com/guide) or <https://example.com>.";
+        let doc = extract(source);
+
+        assert_eq!(doc.text, "Read the guide or .");
+    }
+
+    #[test]
+    fn test_offsets_map_to_source() {
+        let source = "# Title\n\nThe *quick* fox jumps.";
+        let doc = extract(source);
+
+        let pos = doc.text.find("quick").unwrap();
+        let (start, end) = doc.source_map.range_to_source(pos, pos + "quick".len());
+        assert_eq!(&source[start..end], "quick");
+
+        let pos = doc.text.find("fox").unwrap();
+        let (start, end) = doc.source_map.range_to_source(pos, pos + 3);
+        assert_eq!(&source[start..end], "fox");
+    }
+
+    #[test]
+    fn test_loose_list_item_is_single_unit() {
+        let source = "- First item text.\n\n- Second item text.\n";
+        let doc = extract(source);
+
+        assert_eq!(doc.blocks.len(), 2);
+        assert!(doc.blocks.iter().all(|b| b.kind == BlockKind::ListItem));
+    }
+}
@@ -0,0 +1,468 @@
+// Structured input formats
+// Extracts analyzable prose from marked-up documents and maps offsets back to the source
+
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod plain;
+
+use crate::analysis_reports::*;
+use crate::fix::Fix;
+use crate::grammar::{GrammarIssue, HiddenVerb, PassiveVoiceMatch};
+use crate::readability::{PassageReadability, ReadabilityBreakdown, ReadabilityMetrics};
+use crate::span::{OffsetIndex, Span};
+#[cfg(any(feature = "markdown", feature = "html"))]
+use lazy_static::lazy_static;
+#[cfg(any(feature = "markdown", feature = "html"))]
+use regex::Regex;
+
+#[cfg(any(feature = "markdown", feature = "html"))]
+lazy_static! {
+    /// Bare URLs that should not be analyzed as prose
+    static ref URL_PATTERN: Regex = Regex::new(r"(?:https?://|www\.)[^\s<>)\]]+").unwrap();
+}
+
+/// Kind of prose unit extracted from a structured document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Paragraph,
+    Heading,
+    ListItem,
+    BlockQuote,
+    TableCell,
+}
+
+impl BlockKind {
+    /// Whether the unit is expected to contain punctuated sentences
+    pub fn is_prose(&self) -> bool {
+        matches!(self, BlockKind::Paragraph | BlockKind::BlockQuote)
+    }
+}
+
+/// Kind of a paragraph given its enclosing containers
+#[cfg(any(feature = "markdown", feature = "html"))]
+pub(crate) fn paragraph_kind(item_depth: usize, quote_depth: usize) -> BlockKind {
+    if item_depth > 0 {
+        BlockKind::ListItem
+    } else if quote_depth > 0 {
+        BlockKind::BlockQuote
+    } else {
+        BlockKind::Paragraph
+    }
+}
+
+/// A single prose unit, as a byte range into the extracted text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextBlock {
+    pub kind: BlockKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Contiguous run of extracted text copied from the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment {
+    text_start: usize,
+    text_end: usize,
+    source_start: usize,
+    source_end: usize,
+}
+
+impl Segment {
+    /// Map an offset inside this segment to the source
+    fn map(&self, offset: usize) -> usize {
+        let delta = offset - self.text_start;
+        if self.text_end - self.text_start == self.source_end - self.source_start {
+            self.source_start + delta
+        } else {
+            // Escapes or entities changed the length: stay within the source run
+            (self.source_start + delta).min(self.source_end)
+        }
+    }
+}
+
+/// Maps byte offsets in extracted text back to byte offsets in the original source
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    segments: Vec<Segment>,
+}
+
+impl SourceMap {
+    /// Map a single extracted-text offset to a source offset
+    pub fn to_source(&self, offset: usize) -> usize {
+        let idx = self.segments.partition_point(|s| s.text_start <= offset);
+        if idx == 0 {
+            return self.segments.first().map_or(0, |s| s.source_start);
+        }
+        let segment = &self.segments[idx - 1];
+        if offset < segment.text_end {
+            segment.map(offset)
+        } else {
+            // Offset falls on inserted whitespace between runs
+            segment.source_end
+        }
+    }
+
+    /// Map a half-open extracted-text range to a half-open source range
+    pub fn range_to_source(&self, start: usize, end: usize) -> (usize, usize) {
+        let source_start = self.to_source(start);
+        if end <= start {
+            return (source_start, source_start);
+        }
+        let source_end = self.to_source_end(end).max(source_start);
+        (source_start, source_end)
+    }
+
+    /// Map an exclusive range end, covering the whole source run when it ends a segment
+    fn to_source_end(&self, end: usize) -> usize {
+        let idx = self.segments.partition_point(|s| s.text_start < end);
+        match idx.checked_sub(1).map(|i| &self.segments[i]) {
+            Some(segment) if end == segment.text_end => segment.source_end,
+            _ => self.to_source(end - 1) + 1,
+        }
+    }
+
+    /// The map from source offsets back to extracted-text offsets
+    ///
+    /// Exact only for maps that keep the source order, like those of plain text.
+    pub fn inverse(&self) -> SourceMap {
+        let segments = self
+            .segments
+            .iter()
+            .map(|s| Segment {
+                text_start: s.source_start,
+                text_end: s.source_end,
+                source_start: s.text_start,
+                source_end: s.text_end,
+            })
+            .collect();
+        SourceMap { segments }
+    }
+
+    fn push(&mut self, text_start: usize, text_end: usize, source_start: usize, source_end: usize) {
+        if text_end > text_start {
+            self.segments.push(Segment { text_start, text_end, source_start, source_end });
+        }
+    }
+
+    #[cfg(any(feature = "markdown", feature = "html"))]
+    fn truncate(&mut self, text_len: usize) {
+        self.segments.retain(|s| s.text_start < text_len);
+        if let Some(last) = self.segments.last_mut() {
+            if last.text_end > text_len {
+                let removed = last.text_end - text_len;
+                last.text_end = text_len;
+                last.source_end = last.source_end.saturating_sub(removed).max(last.source_start);
+            }
+        }
+    }
+}
+
+/// Prose extracted from a structured document
+#[derive(Debug, Clone, Default)]
+pub struct ExtractedDocument {
+    /// The original document
+    pub source: String,
+    /// Extracted prose, one block per unit separated by blank lines
+    pub text: String,
+    pub blocks: Vec<TextBlock>,
+    pub source_map: SourceMap,
+}
+
+impl ExtractedDocument {
+    /// Text of each block, in document order
+    pub fn block_texts(&self) -> Vec<String> {
+        self.blocks
+            .iter()
+            .map(|b| self.text[b.start..b.end].to_string())
+            .collect()
+    }
+}
+
+/// Incrementally assembles an ExtractedDocument from source runs
+#[cfg(any(feature = "markdown", feature = "html"))]
+#[derive(Default)]
+pub(crate) struct DocumentBuilder {
+    doc: ExtractedDocument,
+    open: Option<(BlockKind, usize, usize)>, // kind, length before separator, block start
+}
+
+#[cfg(any(feature = "markdown", feature = "html"))]
+impl DocumentBuilder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Kind of the currently open block, if any
+    pub(crate) fn open_kind(&self) -> Option<BlockKind> {
+        self.open.map(|(kind, _, _)| kind)
+    }
+
+    /// Whether the currently open block has any text yet
+    pub(crate) fn open_is_empty(&self) -> bool {
+        self.open.map_or(true, |(_, _, start)| start == self.doc.text.len())
+    }
+
+    /// Start a new prose unit, closing any open one
+    pub(crate) fn start_block(&mut self, kind: BlockKind) {
+        self.end_block();
+        let before = self.doc.text.len();
+        if !self.doc.text.is_empty() {
+            self.doc.text.push_str("\n\n");
+        }
+        self.open = Some((kind, before, self.doc.text.len()));
+    }
+
+    /// Close the open unit, dropping it if it collected no text
+    pub(crate) fn end_block(&mut self) {
+        let Some((kind, before, start)) = self.open.take() else {
+            return;
+        };
+        let end = self.doc.text.trim_end().len().max(start);
+        if end == start {
+            self.doc.text.truncate(before);
+            self.doc.source_map.truncate(before);
+            return;
+        }
+        self.doc.text.truncate(end);
+        self.doc.source_map.truncate(end);
+        self.doc.blocks.push(TextBlock { kind, start, end });
+    }
+
+    /// Append text copied from `source_start..source_end`, skipping bare URLs
+    pub(crate) fn push_text(&mut self, text: &str, source_start: usize, source_end: usize) {
+        let linear = text.len() == source_end - source_start;
+        let mut last = 0;
+        for url in URL_PATTERN.find_iter(text) {
+            self.push_run(&text[last..url.start()], source_start, source_end, last, linear);
+            self.push_space(if linear { source_start + url.end() } else { source_end });
+            last = url.end();
+        }
+        self.push_run(&text[last..], source_start, source_end, last, linear);
+    }
+
+    fn push_run(&mut self, run: &str, source_start: usize, source_end: usize, offset: usize, linear: bool) {
+        if self.open.is_none() {
+            return;
+        }
+        let mut run = run;
+        let mut offset = offset;
+        if self.open_is_empty() || self.doc.text.ends_with(char::is_whitespace) {
+            let trimmed = run.trim_start();
+            offset += run.len() - trimmed.len();
+            run = trimmed;
+        }
+        if run.is_empty() {
+            return;
+        }
+        let text_start = self.doc.text.len();
+        self.doc.text.push_str(run);
+        let (src_start, src_end) = if linear {
+            (source_start + offset, source_start + offset + run.len())
+        } else {
+            (source_start, source_end)
+        };
+        self.doc.source_map.push(text_start, self.doc.text.len(), src_start, src_end);
+    }
+
+    /// Append a single separating space (collapsed with neighbouring whitespace)
+    pub(crate) fn push_space(&mut self, source_pos: usize) {
+        if self.open.is_none() || self.open_is_empty() || self.doc.text.ends_with(char::is_whitespace) {
+            return;
+        }
+        let text_start = self.doc.text.len();
+        self.doc.text.push(' ');
+        self.doc.source_map.push(text_start, text_start + 1, source_pos, source_pos + 1);
+    }
+
+    pub(crate) fn finish(mut self) -> ExtractedDocument {
+        self.end_block();
+        self.doc
+    }
+}
+
+/// Rewrite extracted-text offsets in a report to source offsets
+///
+/// Spans are recomputed against `source`, the index of the original document.
+pub trait MapToSource {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex);
+}
+
+/// Remap a (start_index, end_index, length) triple in place
+fn remap(map: &SourceMap, start: &mut usize, end: &mut usize, length: &mut usize) {
+    let (s, e) = map.range_to_source(*start, *end);
+    *start = s;
+    *end = e;
+    *length = e - s;
+}
+
+/// Remap an offset triple and recompute its span in the source
+fn remap_span(
+    map: &SourceMap,
+    source: &OffsetIndex,
+    start: &mut usize,
+    end: &mut usize,
+    length: &mut usize,
+    span: &mut Span,
+) {
+    remap(map, start, end, length);
+    *span = source.span(*start, *end);
+}
+
+impl MapToSource for GrammarIssue {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+        self.fixes.map_to_source(map, source);
+    }
+}
+
+impl MapToSource for Fix {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        for edit in &mut self.edits {
+            let (start, end) = map.range_to_source(edit.span.byte_start, edit.span.byte_end);
+            edit.span = source.span(start, end);
+        }
+    }
+}
+
+impl MapToSource for PassiveVoiceMatch {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+        if let Some(fix) = &mut self.fix {
+            fix.map_to_source(map, source);
+        }
+    }
+}
+
+impl MapToSource for HiddenVerb {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+        self.fix.map_to_source(map, source);
+    }
+}
+
+impl MapToSource for PassageReadability {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+    }
+}
+
+impl MapToSource for ReadabilityBreakdown {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        self.sentences.map_to_source(map, source);
+        self.paragraphs.map_to_source(map, source);
+    }
+}
+
+impl MapToSource for ReadabilityMetrics {
+    // Scores of the whole text hold no offsets
+    fn map_to_source(&mut self, _map: &SourceMap, _source: &OffsetIndex) {}
+}
+
+impl MapToSource for WordOccurrence {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+    }
+}
+
+impl MapToSource for StickySentence {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+    }
+}
+
+impl MapToSource for TransitionFound {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+    }
+}
+
+impl MapToSource for ComplexParagraph {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        remap_span(map, source, &mut self.start_index, &mut self.end_index, &mut self.length, &mut self.span);
+    }
+}
+
+impl<T: MapToSource> MapToSource for Vec<T> {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        for item in self.iter_mut() {
+            item.map_to_source(map, source);
+        }
+    }
+}
+
+impl MapToSource for FullAnalysisReport {
+    fn map_to_source(&mut self, map: &SourceMap, source: &OffsetIndex) {
+        self.sticky_sentences.sticky_sentences.map_to_source(map, source);
+        self.sticky_sentences.semi_sticky_sentences.map_to_source(map, source);
+        self.transitions.all_transitions.map_to_source(map, source);
+        for word in &mut self.overused_words.overused_words {
+            word.occurrences.map_to_source(map, source);
+        }
+        for phrase in &mut self.repeated_phrases.most_repeated {
+            phrase.occurrences.map_to_source(map, source);
+        }
+        for echo in self.echoes.iter_mut().flat_map(|r| &mut r.echoes) {
+            echo.positions.map_to_source(map, source);
+        }
+        for vague in &mut self.diction.most_common_vague {
+            vague.occurrences.map_to_source(map, source);
+        }
+        for cliche in self.cliches.iter_mut().flat_map(|r| &mut r.cliches) {
+            cliche.occurrences.map_to_source(map, source);
+        }
+        for jargon in self.business_jargon.iter_mut().flat_map(|r| &mut r.jargon_list) {
+            jargon.occurrences.map_to_source(map, source);
+        }
+        self.complex_paragraphs.complex_paragraphs.map_to_source(map, source);
+        self.style.hidden_verbs.map_to_source(map, source);
+    }
+}
+
+#[cfg(all(test, any(feature = "markdown", feature = "html")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_collapses_whitespace_and_drops_empty_blocks() {
+        let mut builder = DocumentBuilder::new();
+        builder.start_block(BlockKind::Heading);
+        builder.push_text("Title", 2, 7);
+        builder.start_block(BlockKind::Paragraph);
+        builder.end_block();
+        builder.start_block(BlockKind::Paragraph);
+        builder.push_text("Run ", 9, 13);
+        builder.push_text(" now.", 20, 25);
+        let doc = builder.finish();
+
+        assert_eq!(doc.text, "Title\n\nRun now.");
+        assert_eq!(doc.blocks.len(), 2);
+        assert_eq!(doc.block_texts(), vec!["Title", "Run now."]);
+    }
+
+    #[test]
+    fn test_source_map_ranges() {
+        let mut builder = DocumentBuilder::new();
+        builder.start_block(BlockKind::Paragraph);
+        builder.push_text("Hello", 10, 15);
+        builder.push_space(15);
+        builder.push_text("world.", 30, 36);
+        let doc = builder.finish();
+
+        assert_eq!(doc.text, "Hello world.");
+        assert_eq!(doc.source_map.range_to_source(0, 5), (10, 15));
+        assert_eq!(doc.source_map.range_to_source(6, 12), (30, 36));
+    }
+
+    #[test]
+    fn test_bare_urls_are_skipped() {
+        let mut builder = DocumentBuilder::new();
+        builder.start_block(BlockKind::Paragraph);
+        builder.push_text("See https://example.com for details.", 0, 36);
+        let doc = builder.finish();
+
+        assert_eq!(doc.text, "See for details.");
+        assert_eq!(doc.source_map.range_to_source(4, 7), (24, 27));
+    }
+}
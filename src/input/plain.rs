@@ -0,0 +1,116 @@
+// Plain text input
+// Takes suppression directives out of plain text and normalizes its line endings,
+// mapping offsets back to the text as written
+
+use super::{BlockKind, ExtractedDocument, SourceMap, TextBlock};
+use crate::grammar::paragraph_ranges;
+use crate::suppression::directive_ranges;
+use std::ops::Range;
+
+/// Horizontal whitespace, which a removed directive takes with it
+const BLANKS: [char; 2] = [' ', '\t'];
+
+/// The prose of a plain text document, without its suppression directives
+///
+/// A directive and the blanks around it become a single space between words
+/// and nothing at the start or end of a line, so rules never see the comment
+/// and a line holding only directives separates paragraphs like a blank line.
+/// Line endings become `\n` and blanks ending a line or whitespace ending the
+/// text are dropped, so texts differing only in those analyze, and cache, the same.
+pub fn extract(source: &str) -> ExtractedDocument {
+    let mut text = String::with_capacity(source.len());
+    let mut source_map = SourceMap::default();
+
+    let mut last = 0;
+    for comment in directive_ranges(source) {
+        let start = source[..comment.start].trim_end_matches(BLANKS).len().max(last);
+        let end = source.len() - source[comment.end..].trim_start_matches(BLANKS).len();
+        copy(source, last..start, &mut text, &mut source_map);
+        let between_words = !text.is_empty()
+            && !text.ends_with(char::is_whitespace)
+            && source[end..].starts_with(|c: char| !c.is_whitespace());
+        if between_words {
+            // The space stands for the whole directive it replaces
+            source_map.push(text.len(), text.len() + 1, start, end);
+            text.push(' ');
+        }
+        last = end;
+    }
+    let end = source.trim_end().len().max(last);
+    copy(source, last..end, &mut text, &mut source_map);
+
+    let blocks = paragraph_ranges(&text)
+        .into_iter()
+        .map(|range| TextBlock {
+            kind: BlockKind::Paragraph,
+            start: range.start,
+            end: range.end,
+        })
+        .collect();
+    ExtractedDocument {
+        source: source.to_string(),
+        text,
+        blocks,
+        source_map,
+    }
+}
+
+/// Append `source[range]` to `text`, normalizing line endings and dropping blanks before them
+fn copy(source: &str, range: Range<usize>, text: &mut String, source_map: &mut SourceMap) {
+    let mut start = range.start;
+    while start < range.end {
+        let rest = &source[start..range.end];
+        let line_len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+        let line = &rest[..line_len];
+        // A range stopping mid-line stops at a directive, which takes its own blanks
+        let kept = if line_len < rest.len() { line.trim_end_matches(BLANKS) } else { line };
+        source_map.push(text.len(), text.len() + kept.len(), start, start + kept.len());
+        text.push_str(kept);
+
+        start += line_len;
+        if start < range.end {
+            // CRLF and a lone CR both stand for the \n
+            let break_len = if rest[line_len..].starts_with("\r\n") { 2 } else { 1 };
+            source_map.push(text.len(), text.len() + 1, start, start + break_len);
+            text.push('\n');
+            start += break_len;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directives_are_removed_with_their_blanks() {
+        let source = "We  left <!-- grammar-disable-line cliche --> early.\n<!-- grammar-disable -->\nNext part.";
+        let doc = extract(source);
+
+        assert_eq!(doc.text, "We  left early.\n\nNext part.");
+        assert_eq!(doc.block_texts(), vec!["We  left early.", "Next part."]);
+
+        let early = doc.text.find("early").unwrap();
+        let (start, end) = doc.source_map.range_to_source(early, early + 5);
+        assert_eq!((start, &source[start..end]), (source.find("early").unwrap(), "early"));
+        // The space standing in for the directive covers it in the source
+        let (start, end) = doc.source_map.range_to_source(8, 9);
+        assert_eq!(&source[start..end], " <!-- grammar-disable-line cliche --> ");
+    }
+
+    #[test]
+    fn test_line_endings_and_trailing_blanks_are_normalized() {
+        let source = "First line. \t\r\nSecond line.\rThird line.  \r\n";
+        let doc = extract(source);
+
+        assert_eq!(doc.text, "First line.\nSecond line.\nThird line.");
+        assert_eq!(doc.text, extract("First line.\nSecond line.\nThird line.").text);
+
+        let second = doc.text.find("Second").unwrap();
+        let (start, end) = doc.source_map.range_to_source(second, doc.text.len());
+        assert_eq!(&source[start..end], "Second line.\rThird line.");
+        // Offsets map back exactly through the inverse map
+        let inverse = doc.source_map.inverse();
+        assert_eq!(inverse.range_to_source(start, end), (second, doc.text.len()));
+    }
+}
@@ -9,21 +9,35 @@ pub mod word_lists;
 pub mod analysis_reports;
 pub mod comprehensive_analysis;
 pub mod visualizer;
+pub mod input;
+pub mod cache;
+pub mod incremental;
+pub mod span;
+pub mod fix;
+pub mod spelling;
+pub mod suppression;
+pub mod readability;
 
 // Re-export commonly used types
 pub use config::Config;
 pub use error::{Result, AnalysisError};
 pub use analysis_reports::*;
 pub use visualizer::HtmlVisualizer;
+pub use incremental::IncrementalDocument;
+pub use readability::{ReadabilityBreakdown, ReadabilityMetrics, TextCounts};
 
-use error::ValidationConfig;
-use dictionaries::count_syllables;
-use grammar::{SentenceSplitter, PassiveVoiceDetector, GrammarChecker};
+use error::{Deadline, ValidationConfig};
+use grammar::{SentenceSpan, SentenceSplitter, PassiveVoiceDetector, GrammarChecker, RuleRegistry};
 use comprehensive_analysis::ComprehensiveAnalyzer;
+use input::{ExtractedDocument, MapToSource, SourceMap};
+use span::OffsetIndex;
+use cache::{AnalysisCache, CacheKey, Cacheable};
+use suppression::{Directive, Suppress, Suppressions};
 
 use regex::Regex;
 use lazy_static::lazy_static;
 use std::collections::HashSet;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 lazy_static! {
@@ -45,10 +59,46 @@ lazy_static! {
     };
 }
 
+/// Evaluate independent analysis passes, concurrently on the rayon pool when
+/// `$parallel` is true, binding each result to a local and propagating errors
+/// in declaration order. Pass expressions must only capture references.
+macro_rules! run_passes {
+    ($parallel:expr; $($name:ident = $pass:expr),+ $(,)?) => {
+        $(#[allow(unused_mut)] let mut $name: Option<Result<_>> = None;)+
+        #[cfg(feature = "parallel")]
+        if $parallel {
+            rayon::scope(|s| {
+                $(
+                    let slot = &mut $name;
+                    s.spawn(move |_| *slot = Some($pass));
+                )+
+            });
+        }
+        // Without rayon every pass runs in turn, whatever the setting
+        #[cfg(not(feature = "parallel"))]
+        let _ = $parallel;
+        $(
+            let $name = match $name {
+                Some(result) => result?,
+                None => $pass?,
+            };
+        )+
+    };
+}
+
+/// Run `pass` only when `enabled`, yielding `None` for a skipped pass
+fn optional_pass<T>(enabled: bool, pass: impl FnOnce() -> Result<T>) -> Result<Option<T>> {
+    if enabled {
+        pass().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 /// Main text analyzer struct
 pub struct TextAnalyzer {
     text: String,
-    sentences: Vec<String>,
+    sentences: Vec<SentenceSpan>,
     paragraphs: Vec<String>,
     words: Vec<String>,
     config: Config,
@@ -56,11 +106,38 @@ pub struct TextAnalyzer {
     sentence_splitter: SentenceSplitter,
     passive_detector: PassiveVoiceDetector,
     grammar_checker: GrammarChecker,
+    /// Source structure when the input was a marked-up document
+    document: Option<ExtractedDocument>,
+    /// Whether `document` only normalizes the line endings and trailing blanks of plain text
+    normalized: bool,
+    /// Suppression directives of the input, with offsets into the source
+    suppressions: Suppressions,
+    /// Result cache, set when `analysis.cache_results` is enabled
+    cache: Option<Arc<AnalysisCache>>,
+    /// Time limit shared by every analysis run through this analyzer
+    deadline: Deadline,
 }
 
 impl TextAnalyzer {
     /// Create a new text analyzer with validation
+    ///
+    /// Suppression directives are taken out of the text, and its line endings
+    /// and trailing blanks normalized, before it is analyzed; reported offsets
+    /// still refer to `text` as given. All analysis through the analyzer must
+    /// finish within `validation.timeout_seconds` of its creation.
     pub fn new(text: String, config: Config) -> Result<Self> {
+        let suppressions = Suppressions::parse(&text);
+        if !suppressions.is_empty() {
+            return Self::from_document(input::plain::extract(&text), config);
+        }
+        let document = input::plain::extract(&text);
+        if document.text != text {
+            let mut analyzer = Self::new(document.text.clone(), config)?;
+            analyzer.document = Some(document);
+            analyzer.normalized = true;
+            return Ok(analyzer);
+        }
+
         // Validate input
         let validator = ValidationConfig {
             max_file_size: config.validation.max_file_size_mb * 1024 * 1024,
@@ -70,13 +147,16 @@ impl TextAnalyzer {
         };
         
         validator.validate_text(&text)?;
+        let deadline = validator.deadline();
 
         // Initialize components
         let sentence_splitter = SentenceSplitter::default();
-        let sentences = sentence_splitter.split(&text)?;
+        let sentences = sentence_splitter.split_spans_with_deadline(&text, &deadline)?;
         let paragraphs = Self::split_into_paragraphs(&text);
         let words = Self::extract_words(&text)?;
 
+        let cache = config.analysis.cache_results.then(AnalysisCache::shared);
+        let grammar_checker = GrammarChecker::from_config(&config)?;
         Ok(Self {
             text,
             sentences,
@@ -85,7 +165,12 @@ impl TextAnalyzer {
             config,
             sentence_splitter,
             passive_detector: PassiveVoiceDetector::default(),
-            grammar_checker: GrammarChecker::default(),
+            grammar_checker,
+            cache,
+            document: None,
+            normalized: false,
+            suppressions,
+            deadline,
         })
     }
 
@@ -94,6 +179,136 @@ impl TextAnalyzer {
         Self::new(text, Config::default())
     }
 
+    /// Create an analyzer over the prose of a Markdown document
+    ///
+    /// Code blocks, inline code and URLs are skipped, headings and list items
+    /// are analyzed as their own units, and reported offsets refer to `source`.
+    #[cfg(feature = "markdown")]
+    pub fn from_markdown(source: &str, config: Config) -> Result<Self> {
+        Self::from_document(input::markdown::extract(source), config)
+    }
+
+    /// Create an analyzer over the visible text of an HTML document
+    ///
+    /// Paragraph boundaries come from block elements (p, li, h1-h6, blockquote),
+    /// script, style and nav content is dropped, and reported offsets refer to `source`.
+    #[cfg(feature = "html")]
+    pub fn from_html(source: &str, config: Config) -> Result<Self> {
+        Self::from_document(input::html::extract(source), config)
+    }
+
+    /// Create an analyzer over an already extracted document
+    pub fn from_document(document: ExtractedDocument, config: Config) -> Result<Self> {
+        let validator = ValidationConfig {
+            max_file_size: config.validation.max_file_size_mb * 1024 * 1024,
+            min_words: config.validation.min_words,
+            max_words: config.validation.max_words,
+            timeout_seconds: config.validation.timeout_seconds,
+        };
+
+        validator.validate_text(&document.text)?;
+        let deadline = validator.deadline();
+
+        // Each block is its own unit: sentences never cross block boundaries
+        let sentence_splitter = SentenceSplitter::default();
+        let paragraphs = document.block_texts();
+        let blocks: Vec<_> = document.blocks.iter().map(|b| b.start..b.end).collect();
+        let sentences = sentence_splitter.split_blocks(&document.text, &blocks, &deadline)?;
+        let words = Self::extract_words(&document.text)?;
+
+        let cache = config.analysis.cache_results.then(AnalysisCache::shared);
+        let grammar_checker = GrammarChecker::from_config(&config)?;
+        let suppressions = Suppressions::parse(&document.source);
+        Ok(Self {
+            text: document.text.clone(),
+            sentences,
+            paragraphs,
+            words,
+            config,
+            sentence_splitter,
+            passive_detector: PassiveVoiceDetector::default(),
+            grammar_checker,
+            cache,
+            document: Some(document),
+            normalized: false,
+            suppressions,
+            deadline,
+        })
+    }
+
+    /// Also check grammar with the rules of `registry`, filtered by `config.rules`
+    ///
+    /// Use this to run rules defined outside this crate alongside the ones the
+    /// configuration sets up, including spelling and glossary rules. A rule
+    /// with the ID of one of those replaces it; to run custom rules instead of
+    /// the built-in ones, list the built-in IDs under `config.rules.disabled`.
+    pub fn with_rules(mut self, registry: &RuleRegistry) -> Self {
+        self.grammar_checker = self.grammar_checker.with_added_rules(registry, &self.config.rules);
+        self
+    }
+
+    /// Stop analysis at `deadline` instead of `validation.timeout_seconds` after creation
+    ///
+    /// Lets several analyzers serving one request share a single time budget.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Cache results in `cache` instead of the process-wide shared cache
+    ///
+    /// Readability, grammar and full-report results are then looked up by a
+    /// hash of the text and the effective configuration before being computed.
+    pub fn with_cache(mut self, cache: Arc<AnalysisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Return the cached result for this text and configuration, or compute it
+    ///
+    /// Normalized plain text is cached in the offsets of the analyzed text, so
+    /// every input normalizing to it gets results in its own offsets.
+    fn cached<T: Cacheable + MapToSource>(&self, compute: impl FnOnce() -> Result<T>) -> Result<T> {
+        let Some(cache) = &self.cache else {
+            return compute();
+        };
+        let source_map = self.source_map().filter(|_| !self.normalized);
+        let key = CacheKey::new(&self.text, source_map, &self.config)
+            .with_rules(self.grammar_checker.rule_ids())
+            .with_suppressions(&self.suppressions);
+        match self.document.as_ref().filter(|_| self.normalized) {
+            Some(document) => {
+                if let Some(mut value) = cache.get::<T>(key) {
+                    value.map_to_source(&document.source_map, &OffsetIndex::new(&document.source));
+                    return Ok(value);
+                }
+                let value = compute()?;
+                let mut stored = value.clone();
+                stored.map_to_source(&document.source_map.inverse(), &OffsetIndex::new(&self.text));
+                cache.insert(key, stored);
+                Ok(value)
+            }
+            None => cache.get_or_compute(key, compute),
+        }
+    }
+
+    /// Source offset map, when the analyzed text differs from the input (None otherwise)
+    pub fn source_map(&self) -> Option<&SourceMap> {
+        self.document.as_ref().map(|d| &d.source_map)
+    }
+
+    /// Rewrite offsets and spans of findings to the original document (structured input only)
+    fn map_to_source<T: MapToSource>(&self, findings: &mut T) {
+        if let Some(document) = &self.document {
+            findings.map_to_source(&document.source_map, &OffsetIndex::new(&document.source));
+        }
+    }
+
+    /// The analysis deadline, started with the analyzer
+    fn deadline(&self) -> Deadline {
+        self.deadline
+    }
+
     /// Split text into paragraphs
     fn split_into_paragraphs(text: &str) -> Vec<String> {
         text.split("\n\n")
@@ -112,66 +327,27 @@ impl TextAnalyzer {
 
     /// Calculate readability metrics
     pub fn readability_metrics(&self) -> Result<ReadabilityMetrics> {
-        let total_sentences = self.sentences.len();
-        let total_words = self.words.len();
-
-        if total_sentences == 0 || total_words == 0 {
-            return Ok(ReadabilityMetrics::default());
-        }
-
-        // Count syllables using dictionary and improved estimation
-        let total_syllables: usize = self.words
-            .iter()
-            .map(|w| count_syllables(w))
-            .sum();
-
-        let words_per_sentence = total_words as f64 / total_sentences as f64;
-        let syllables_per_word = total_syllables as f64 / total_words as f64;
-
-        // Flesch Reading Ease
-        let mut reading_ease = 206.835 - (1.015 * words_per_sentence) - (84.6 * syllables_per_word);
-        reading_ease = reading_ease.max(0.0).min(100.0);
-
-        // Flesch-Kincaid Grade Level
-        let grade_level = ((0.39 * words_per_sentence) + (11.8 * syllables_per_word) - 15.59).max(0.0);
+        self.cached(|| self.compute_readability_metrics())
+    }
 
-        // SMOG Index (for texts with 30+ sentences)
-        let smog_index = if total_sentences >= 30 {
-            Some(Self::calculate_smog(&self.sentences))
-        } else {
-            None
-        };
+    fn compute_readability_metrics(&self) -> Result<ReadabilityMetrics> {
+        Ok(ReadabilityMetrics::from_sentences(&self.sentence_counts()))
+    }
 
-        Ok(ReadabilityMetrics {
-            flesch_reading_ease: Self::round(reading_ease, 1),
-            flesch_kincaid_grade: Self::round(grade_level, 1),
-            smog_index,
-            avg_words_per_sentence: Self::round(words_per_sentence, 1),
-            avg_syllables_per_word: Self::round(syllables_per_word, 2),
+    /// Readability of each sentence and paragraph, with the hardest sentences ranked
+    ///
+    /// Passages are graded hard or very hard from the `readability` settings.
+    pub fn readability_breakdown(&self) -> Result<ReadabilityBreakdown> {
+        self.cached(|| {
+            let mut breakdown = ReadabilityBreakdown::new(&self.text, &self.sentences, &self.config.readability);
+            self.map_to_source(&mut breakdown);
+            Ok(breakdown)
         })
     }
 
-    /// Calculate SMOG Index
-    fn calculate_smog(sentences: &[String]) -> f64 {
-        if sentences.is_empty() {
-            return 0.0;
-        }
-
-        let mut polysyllable_count = 0;
-        for sentence in sentences.iter().take(30) {
-            let words: Vec<String> = WORD_EXTRACT
-                .find_iter(&sentence.to_lowercase())
-                .map(|m| m.as_str().to_string())
-                .collect();
-            
-            for word in words {
-                if count_syllables(&word) >= 3 {
-                    polysyllable_count += 1;
-                }
-            }
-        }
-
-        1.0430 * ((polysyllable_count as f64 * 30.0 / sentences.len().min(30) as f64).sqrt()) + 3.1291
+    /// What the readability formulas count in each sentence, in order
+    pub fn sentence_counts(&self) -> Vec<TextCounts> {
+        self.sentences.iter().map(|s| TextCounts::of_sentence(&s.text)).collect()
     }
 
     /// Check grammar
@@ -179,15 +355,81 @@ impl TextAnalyzer {
         if !self.config.features.grammar_check {
             return Ok(Vec::new());
         }
-        self.grammar_checker.check(&self.sentences)
+        self.cached(|| self.compute_grammar_issues())
+    }
+
+    fn compute_grammar_issues(&self) -> Result<Vec<grammar::GrammarIssue>> {
+        self.grammar_issues_with(&self.suppressions)
+    }
+
+    /// Grammar issues, without the ones `suppressions` silence
+    fn grammar_issues_with(&self, suppressions: &Suppressions) -> Result<Vec<grammar::GrammarIssue>> {
+        let mut issues = self.grammar_checker.check_spans(&self.text, &self.sentences, &self.deadline())?;
+
+        if let Some(document) = &self.document {
+            // Headings, list items and table cells are not expected to be punctuated sentences
+            issues.retain(|issue| {
+                !matches!(
+                    issue.issue_type,
+                    grammar::GrammarIssueType::MissingPunctuation | grammar::GrammarIssueType::SentenceFragment
+                ) || self.sentences
+                        .get(issue.sentence_num - 1)
+                        .map_or(true, |s| document.blocks[s.paragraph].kind.is_prose())
+            });
+        }
+        self.map_to_source(&mut issues);
+        issues.suppress(suppressions);
+        for issue in &mut issues {
+            issue.fixes.retain(|fix| self.suppressions.keeps_directives(fix));
+        }
+
+        Ok(issues)
     }
 
     /// Detect passive voice
     pub fn detect_passive_voice(&self) -> Result<Vec<grammar::PassiveVoiceMatch>> {
+        self.detect_passive_voice_within(&self.deadline(), &self.suppressions)
+    }
+
+    fn detect_passive_voice_within(
+        &self,
+        deadline: &Deadline,
+        suppressions: &Suppressions,
+    ) -> Result<Vec<grammar::PassiveVoiceMatch>> {
         if !self.config.features.style_check {
             return Ok(Vec::new());
         }
-        self.passive_detector.detect(&self.text)
+        let mut matches = self.passive_detector.detect_with_deadline(&self.text, deadline)?;
+        self.map_to_source(&mut matches);
+        matches.suppress(suppressions);
+        for pv in &mut matches {
+            pv.fix = pv.fix.take().filter(|fix| self.suppressions.keeps_directives(fix));
+        }
+        Ok(matches)
+    }
+
+    /// Suppression directives found in the input
+    pub fn suppressions(&self) -> &Suppressions {
+        &self.suppressions
+    }
+
+    /// Suppression directives that silence no finding
+    ///
+    /// Runs the grammar check and the full report again without suppressions,
+    /// bypassing the cache.
+    pub fn unused_suppressions(&self) -> Result<Vec<Directive>> {
+        if self.suppressions.is_empty() {
+            return Ok(Vec::new());
+        }
+        let none = Suppressions::default();
+        let issues = if self.config.features.grammar_check { self.grammar_issues_with(&none)? } else { Vec::new() };
+        let passive_voice = self.detect_passive_voice_within(&self.deadline(), &none)?;
+        let report = self.full_report_with(&none)?;
+
+        let mut findings = issues.findings();
+        findings.extend(passive_voice.findings());
+        findings.extend(report.findings());
+        Ok(self.suppressions.unused(&findings).into_iter().cloned().collect())
     }
 
     /// Get basic statistics
@@ -201,57 +443,96 @@ impl TextAnalyzer {
         }
     }
 
+    /// Get the configuration this analyzer was created with
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     /// Get text reference (for visualizer)
     pub fn text(&self) -> &str {
         &self.text
     }
 
-    /// Get sentences reference (for visualizer)
-    pub fn sentences(&self) -> &[String] {
+    /// Get sentences with their location and paragraph (for visualizer)
+    pub fn sentences(&self) -> &[SentenceSpan] {
         &self.sentences
     }
 
     /// Generate FULL comprehensive analysis report with ALL features
+    ///
+    /// Fails with `AnalysisError::TimeoutError` if the passes do not finish
+    /// before the analyzer's deadline, which every call made through it shares.
     pub fn generate_full_report(&self) -> Result<FullAnalysisReport> {
-        let analyzer = ComprehensiveAnalyzer::new(
+        self.cached(|| self.compute_full_report())
+    }
+
+    fn compute_full_report(&self) -> Result<FullAnalysisReport> {
+        self.full_report_with(&self.suppressions)
+    }
+
+    /// Full report, with the findings `suppressions` silence left out of lists, counts and scores
+    fn full_report_with(&self, suppressions: &Suppressions) -> Result<FullAnalysisReport> {
+        let parallel = self.config.analysis.parallel_processing;
+        let deadline = self.deadline();
+        let analyzer = &ComprehensiveAnalyzer::new(
             &self.text,
             &self.sentences,
             &self.paragraphs,
             &self.words,
-        );
+            &self.config,
+        )
+        .with_deadline(deadline);
+        let deadline = &deadline;
+
+        let features = &self.config.features;
+
+        // Generate all analysis reports (the passes are independent)
+        run_passes! {
+            parallel;
+            sticky_sentences = analyzer.analyze_sticky_sentences(),
+            pacing = analyzer.analyze_pacing(),
+            sentence_length = analyzer.analyze_sentence_length(),
+            transitions = analyzer.analyze_transitions(),
+            overused_words = analyzer.analyze_overused_words(),
+            repeated_phrases = analyzer.analyze_repeated_phrases(),
+            echoes = optional_pass(features.echo_detection, || analyzer.analyze_echoes()),
+            sensory = optional_pass(features.sensory_analysis, || analyzer.analyze_sensory_words()),
+            diction = analyzer.analyze_diction(),
+            cliches = optional_pass(features.cliche_detection, || analyzer.analyze_cliches()),
+            consistency = optional_pass(features.consistency_check, || analyzer.analyze_consistency()),
+            acronyms = analyzer.analyze_acronyms(),
+            conjunction_starts = analyzer.analyze_conjunction_starts(),
+            business_jargon = optional_pass(features.jargon_detection, || analyzer.analyze_business_jargon()),
+            complex_paragraphs = analyzer.analyze_complex_paragraphs(),
+            // Get style report with adverbs and hidden verbs
+            style = analyzer.analyze_style(),
+            passive_voice = self.detect_passive_voice_within(deadline, suppressions),
+        }
 
-        // Generate all analysis reports
-        let sticky_sentences = analyzer.analyze_sticky_sentences()?;
-        let pacing = analyzer.analyze_pacing()?;
-        let sentence_length = analyzer.analyze_sentence_length()?;
-        let transitions = analyzer.analyze_transitions()?;
-        let overused_words = analyzer.analyze_overused_words()?;
-        let repeated_phrases = analyzer.analyze_repeated_phrases()?;
-        let echoes = analyzer.analyze_echoes()?;
-        let sensory = analyzer.analyze_sensory_words()?;
-        let diction = analyzer.analyze_diction()?;
-        let cliches = analyzer.analyze_cliches()?;
-        let consistency = analyzer.analyze_consistency()?;
-        let acronyms = analyzer.analyze_acronyms()?;
-        let conjunction_starts = analyzer.analyze_conjunction_starts()?;
-        let business_jargon = analyzer.analyze_business_jargon()?;
-        let complex_paragraphs = analyzer.analyze_complex_paragraphs()?;
-        
-        // Get style report with adverbs and hidden verbs
-        let mut style = analyzer.analyze_style()?;
-        
         // Add passive voice count to style report
-        let passive_voice = self.detect_passive_voice()?;
-        style.passive_voice_count = passive_voice.len();
-
-        // Calculate overall style score
-        let style_score = analyzer.calculate_style_score(&style, &sticky_sentences, &diction);
+        let style = StyleReport {
+            passive_voice_count: passive_voice.len(),
+            ..style
+        };
 
-        Ok(FullAnalysisReport {
+        // Report which passes were disabled, using their report field names
+        let skipped_passes: Vec<String> = [
+            ("echoes", features.echo_detection),
+            ("sensory", features.sensory_analysis),
+            ("cliches", features.cliche_detection),
+            ("consistency", features.consistency_check),
+            ("business_jargon", features.jargon_detection),
+        ]
+        .iter()
+        .filter(|(_, enabled)| !enabled)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+        let mut report = FullAnalysisReport {
             word_count: self.words.len(),
             sentence_count: self.sentences.len(),
             paragraph_count: self.paragraphs.len(),
-            style_score,
+            style_score: 0,
             style,
             sticky_sentences,
             pacing,
@@ -268,34 +549,18 @@ impl TextAnalyzer {
             conjunction_starts,
             business_jargon,
             complex_paragraphs,
-        })
-    }
+            skipped_passes,
+        };
 
-    /// Utility: Round to specified decimal places
-    fn round(value: f64, decimals: u32) -> f64 {
-        let multiplier = 10_f64.powi(decimals as i32);
-        (value * multiplier).round() / multiplier
-    }
-}
+        self.map_to_source(&mut report);
+        report.suppress(suppressions);
+        // A rewrite spanning a directive comment would delete it
+        report.style.hidden_verbs.retain(|hv| self.suppressions.keeps_directives(&hv.fix));
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ReadabilityMetrics {
-    pub flesch_reading_ease: f64,
-    pub flesch_kincaid_grade: f64,
-    pub smog_index: Option<f64>,
-    pub avg_words_per_sentence: f64,
-    pub avg_syllables_per_word: f64,
-}
+        // Calculate overall style score from what is left
+        report.style_score = analyzer.calculate_style_score(&report.style, &report.sticky_sentences, &report.diction);
 
-impl Default for ReadabilityMetrics {
-    fn default() -> Self {
-        Self {
-            flesch_reading_ease: 0.0,
-            flesch_kincaid_grade: 0.0,
-            smog_index: None,
-            avg_words_per_sentence: 0.0,
-            avg_syllables_per_word: 0.0,
-        }
+        Ok(report)
     }
 }
 
@@ -360,4 +625,308 @@ mod tests {
         
         assert!(words.iter().any(|w| w.contains("well-known") || w == "well" || w == "known"));
     }
+
+    #[test]
+    fn test_thresholds_drive_full_report() {
+        // 6 of 13 words are glue words: ~46%
+        let text = "The cat sat on the mat and looked at the bird outside today.".to_string();
+
+        let fiction = TextAnalyzer::new(text.clone(), Config::preset(config::DocumentType::Fiction))
+            .unwrap()
+            .generate_full_report()
+            .unwrap();
+        let business = TextAnalyzer::new(text, Config::preset(config::DocumentType::Business))
+            .unwrap()
+            .generate_full_report()
+            .unwrap();
+
+        assert_eq!(fiction.sticky_sentences.sticky_sentence_count, 1);
+        assert_eq!(business.sticky_sentences.sticky_sentence_count, 0);
+        assert_eq!(business.sticky_sentences.semi_sticky_sentences.len(), 1);
+    }
+
+    #[test]
+    fn test_echo_distance_threshold() {
+        let text = "Rivers carve valleys slowly over many thousands of years, and rivers shape the land.".to_string();
+
+        let mut config = Config::default();
+        let report = TextAnalyzer::new(text.clone(), config.clone()).unwrap().generate_full_report().unwrap();
+        assert!(report.echoes.unwrap().echoes.iter().any(|e| e.word == "river"));
+
+        config.thresholds.echo_distance = 5;
+        let report = TextAnalyzer::new(text, config).unwrap().generate_full_report().unwrap();
+        assert!(report.echoes.unwrap().echoes.iter().all(|e| e.word != "river"));
+    }
+
+    #[test]
+    fn test_word_forms_count_together() {
+        let text = "She writes every morning. She wrote the summary after writing the notes. \
+                    The board made a decision. We make a decision every week."
+            .to_string();
+        let report = TextAnalyzer::with_default_config(text.clone()).unwrap().generate_full_report().unwrap();
+
+        let echoes = report.echoes.unwrap().echoes;
+        let write = echoes.iter().find(|e| e.word == "write").unwrap();
+        let forms: Vec<&str> = write.positions.iter().map(|p| &text[p.start_index..p.end_index]).collect();
+        assert_eq!(forms, vec!["writes", "wrote", "writing"]);
+
+        let phrases = &report.repeated_phrases.most_repeated;
+        let decision = phrases.iter().find(|p| p.phrase == "make a decision").unwrap();
+        let forms: Vec<&str> = decision.occurrences.iter().map(|o| &text[o.start_index..o.end_index]).collect();
+        assert_eq!(forms, vec!["made a decision", "make a decision"]);
+    }
+
+    #[test]
+    fn test_glossary_terms_are_authoritative() {
+        let text = "Acme Cloud syncs files. The acme cloud client ships weekly. Send e-mail to support.".to_string();
+        let mut config = Config::default();
+        let report = TextAnalyzer::new(text.clone(), config.clone()).unwrap().generate_full_report().unwrap();
+        assert!(report.consistency.unwrap().issues.iter().any(|i| i.contains("acme")));
+
+        config.glossary.capitalization.push("Acme Cloud".to_string());
+        config.glossary.banned.insert("e-mail".to_string(), "email".to_string());
+        let analyzer = TextAnalyzer::new(text, config).unwrap();
+        let report = analyzer.generate_full_report().unwrap();
+        assert!(report.consistency.unwrap().issues.iter().all(|i| !i.contains("acme")));
+
+        let issues = analyzer.check_grammar().unwrap();
+        let fixes: Vec<&str> = issues
+            .iter()
+            .filter(|i| i.rule_id == "glossary")
+            .map(|i| i.fixes[0].edits[0].replacement.as_str())
+            .collect();
+        assert_eq!(fixes, vec!["Acme Cloud", "email"]);
+    }
+
+    #[test]
+    fn test_results_are_cached_per_text_and_config() {
+        let text = "The report was written by the team. It covers the quarter in detail.".to_string();
+        let cache = Arc::new(AnalysisCache::new(8));
+
+        let analyzer = TextAnalyzer::new(text.clone(), Config::default())
+            .unwrap()
+            .with_cache(Arc::clone(&cache));
+        let report = analyzer.generate_full_report().unwrap();
+        analyzer.readability_metrics().unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // A fresh analyzer over the same text is served from the cache
+        let again = TextAnalyzer::new(text.clone(), Config::default())
+            .unwrap()
+            .with_cache(Arc::clone(&cache))
+            .generate_full_report()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            serde_json::to_string(&again).unwrap()
+        );
+        assert_eq!(cache.len(), 1);
+
+        // Changing a threshold changes the key
+        let mut config = Config::default();
+        config.thresholds.echo_distance = 5;
+        TextAnalyzer::new(text, config)
+            .unwrap()
+            .with_cache(Arc::clone(&cache))
+            .generate_full_report()
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_line_ending_variants_share_cached_results() {
+        let texts = [
+            "The cake was eaten by the dog.  \r\n\r\nI don't have nothing to add. \r\n",
+            "The cake was eaten by the dog.\n\nI don't have nothing to add.",
+            "The cake was eaten by the dog.\t\r\rI don't have nothing to add.",
+        ];
+        let cache = Arc::new(AnalysisCache::new(8));
+
+        let mut found = Vec::new();
+        for text in texts {
+            let analyzer = TextAnalyzer::new(text.to_string(), Config::default())
+                .unwrap()
+                .with_cache(Arc::clone(&cache));
+            let issues = analyzer.check_grammar().unwrap();
+            let report = analyzer.generate_full_report().unwrap();
+            assert!(!issues.is_empty());
+            for issue in &issues {
+                assert_eq!(issue.span, OffsetIndex::new(text).span(issue.start_index, issue.end_index));
+            }
+            let sticky: Vec<&str> = report.sticky_sentences.sticky_sentences.iter().map(|s| &text[s.span.byte_range()]).collect();
+            found.push((issues.iter().map(|i| &text[i.span.byte_range()]).collect::<Vec<_>>(), sticky));
+        }
+        assert_eq!(cache.len(), 1);
+        assert!(found.iter().all(|f| *f == found[0]));
+    }
+
+    #[test]
+    fn test_spans_are_exact_for_non_ascii_text() {
+        let text = "Zoë ordered crème brûlée at the café.  I don't have nothing. The cake was eaten by Zoë.".to_string();
+        let analyzer = TextAnalyzer::new(text.clone(), Config::default()).unwrap();
+        let chars: Vec<char> = text.chars().collect();
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+
+        let issues = analyzer.check_grammar().unwrap();
+        let negative = issues
+            .iter()
+            .find(|i| i.issue_type == grammar::GrammarIssueType::DoubleNegative)
+            .unwrap();
+        assert_eq!(&text[negative.span.byte_range()], "don't have nothing");
+        let by_char: String = chars[negative.span.char_range()].iter().collect();
+        assert_eq!(by_char, "don't have nothing");
+        assert_eq!(
+            String::from_utf16(&utf16[negative.span.utf16_range()]).unwrap(),
+            "don't have nothing"
+        );
+
+        let passive = analyzer.detect_passive_voice().unwrap();
+        let by_char: String = chars[passive[0].span.char_range()].iter().collect();
+        assert_eq!(by_char, "was eaten");
+
+        let text = "Zoë's café was closed on Monday. However, the crème brûlée was still sold out.";
+        let report = TextAnalyzer::with_default_config(text.to_string()).unwrap().generate_full_report().unwrap();
+        let chars: Vec<char> = text.chars().collect();
+        let transition = &report.transitions.all_transitions[0];
+        let by_char: String = chars[transition.span.char_range()].iter().collect();
+        assert_eq!(by_char, "However");
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_markdown_input_skips_code() {
+        let source = "# Usage\n\nInstall the tool and run it on your files.\n\n```sh\ncargo install text-analyzer --locked\n```\n\nThe report is printed to standard output.\n";
+        let analyzer = TextAnalyzer::from_markdown(source, Config::default()).unwrap();
+
+        assert!(!analyzer.text().contains("cargo"));
+        assert_eq!(analyzer.statistics().paragraph_count, 3);
+        assert_eq!(analyzer.sentences()[0].text, "Usage");
+
+        // The heading is not flagged for missing punctuation
+        let issues = analyzer.check_grammar().unwrap();
+        assert!(issues
+            .iter()
+            .all(|i| i.issue_type != grammar::GrammarIssueType::MissingPunctuation));
+    }
+
+    #[test]
+    fn test_suppressed_findings_leave_counts_and_score() {
+        let passive = "The report was written by the team. The results were checked by an auditor.";
+        let text = format!("{}\n\n<!-- grammar-disable-next-line passive-voice -->\n{}", passive, passive);
+
+        let analyzer = TextAnalyzer::with_default_config(text.clone()).unwrap();
+        let report = analyzer.generate_full_report().unwrap();
+        let unsuppressed = TextAnalyzer::with_default_config(text.replace("passive-voice", "cliche"))
+            .unwrap()
+            .generate_full_report()
+            .unwrap();
+
+        assert!(!analyzer.text().contains("grammar-disable"));
+        assert_eq!(analyzer.detect_passive_voice().unwrap().len(), 2);
+        assert_eq!(report.style.passive_voice_count, 2);
+        assert_eq!(unsuppressed.style.passive_voice_count, 4);
+        assert!(report.style_score > unsuppressed.style_score);
+        assert!(analyzer.unused_suppressions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fixes_leave_plain_text_directives_alone() {
+        let text = "We  left early. <!-- grammar-disable-line cliche --> It was fine.\n<!-- grammar-disable-next-line passive-voice -->\nThe cake was eaten by the dog.";
+        let analyzer = TextAnalyzer::with_default_config(text.to_string()).unwrap();
+        assert_eq!(analyzer.statistics().paragraph_count, 2);
+
+        let issues = analyzer.check_grammar().unwrap();
+        let fixes = issues
+            .iter()
+            .flat_map(|i| &i.fixes)
+            .filter(|f| f.applicability == fix::Applicability::MachineApplicable);
+        let outcome = fix::apply_fixes(text, fixes);
+
+        assert_eq!(outcome.applied, 1);
+        assert_eq!(outcome.text, text.replacen("We  left", "We left", 1));
+    }
+
+    #[test]
+    fn test_hidden_verbs_in_style_report() {
+        let text = "The editors finished early. Then the board made a decision about the budget.\n\n<!-- grammar-disable-next-line hidden-verb -->\nWe conduct an investigation of every leak.";
+        let analyzer = TextAnalyzer::with_default_config(text.to_string()).unwrap();
+        let report = analyzer.generate_full_report().unwrap();
+
+        let hidden = &report.style.hidden_verbs;
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(&analyzer.text()[hidden[0].span.byte_range()], "made a decision");
+        assert_eq!(hidden[0].fix.edits[0].replacement, "decided");
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_markdown_suppressions_and_unused() {
+        let source = "<!-- grammar-disable-file double-negative -->\n\nThe cake was eaten by Zoë. I don't have nothing.\n\n<!-- grammar-disable cliche -->\nWe ship the new release on Monday.\n";
+        let analyzer = TextAnalyzer::from_markdown(source, Config::default()).unwrap();
+
+        let issues = analyzer.check_grammar().unwrap();
+        assert!(issues.iter().all(|i| i.rule_id != "double-negative"));
+        assert_eq!(analyzer.detect_passive_voice().unwrap().len(), 1);
+
+        let unused: Vec<String> = analyzer.unused_suppressions().unwrap().iter().map(|d| d.to_string()).collect();
+        assert_eq!(unused, vec!["grammar-disable cliche"]);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_markdown_offsets_refer_to_source() {
+        let source = "## Notes\n\nThe ball was thrown by **John** yesterday.\n";
+        let analyzer = TextAnalyzer::from_markdown(source, Config::default()).unwrap();
+
+        let passive = analyzer.detect_passive_voice().unwrap();
+        assert!(!passive.is_empty());
+        assert_eq!(&source[passive[0].start_index..passive[0].end_index], "was thrown");
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_readability_breakdown_refers_to_source() {
+        let source = "## Notes\n\nThe cat sat on the mat. The **institutional** ramifications of decentralization were systematically underestimated.\n";
+        let analyzer = TextAnalyzer::from_markdown(source, Config::default()).unwrap();
+
+        let breakdown = analyzer.readability_breakdown().unwrap();
+        assert_eq!(breakdown.paragraphs.len(), 2);
+        let hardest: Vec<_> = breakdown.hardest().collect();
+        assert_eq!(hardest.len(), 1);
+        assert!(source[hardest[0].span.byte_range()].starts_with("The **institutional**"));
+        assert_eq!(&source[breakdown.sentences[1].span.byte_range()], "The cat sat on the mat.");
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_fixes_apply_to_markdown_source() {
+        let source = "## Notes\n\nThe ball was thrown by **John**  yesterday.\n";
+        let analyzer = TextAnalyzer::from_markdown(source, Config::default()).unwrap();
+
+        let issues = analyzer.check_grammar().unwrap();
+        let fixes = issues
+            .iter()
+            .flat_map(|i| &i.fixes)
+            .filter(|f| f.applicability == fix::Applicability::MachineApplicable);
+        let outcome = fix::apply_fixes(source, fixes);
+
+        assert_eq!(outcome.applied, 1);
+        assert_eq!(outcome.text, "## Notes\n\nThe ball was thrown by **John** yesterday.\n");
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_html_input_uses_dom_paragraphs() {
+        let source = "<html><body><nav><a href=\"/\">Home</a> | <a href=\"/docs\">Docs</a></nav>\
+            <h2>Notes</h2><p>The ball was thrown by <b>John</b> yesterday.</p>\
+            <p>It landed far away &amp; nobody found it again.</p></body></html>";
+        let analyzer = TextAnalyzer::from_html(source, Config::default()).unwrap();
+
+        assert!(!analyzer.text().contains("Docs"));
+        assert_eq!(analyzer.statistics().paragraph_count, 3);
+
+        let passive = analyzer.detect_passive_voice().unwrap();
+        assert!(!passive.is_empty());
+        assert_eq!(&source[passive[0].start_index..passive[0].end_index], "was thrown");
+    }
 }
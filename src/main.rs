@@ -1,7 +1,10 @@
-use Rust_Grammar::{TextAnalyzer, Config, error::Result};
+use Rust_Grammar::{TextAnalyzer, Config, cache::AnalysisCache, error::Result};
+use Rust_Grammar::fix::{apply_fixes, Applicability};
+use Rust_Grammar::grammar::GrammarIssue;
 use clap::Parser;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, Level};
 use tracing_subscriber;
@@ -22,6 +25,15 @@ struct Cli {
     #[arg(short, long, default_value = "text")]
     format: String,
 
+    /// Input format: auto, text, markdown, html (auto detects from the file extension)
+    #[arg(short = 'i', long, default_value = "auto")]
+    input_format: String,
+
+    /// Directory for cached results (enables caching; defaults to the user
+    /// cache directory when `analysis.cache_results` is set in the config)
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
     /// Configuration file (YAML or TOML)
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
@@ -53,6 +65,22 @@ struct Cli {
     /// Generate visual HTML report with highlights
     #[arg(short = 'V', long)]
     visualize: bool,
+
+    /// Generate visual HTML report shading each sentence by reading difficulty
+    #[arg(long)]
+    heatmap: bool,
+
+    /// Apply machine-applicable fixes to the input file in place
+    #[arg(long, conflicts_with = "fix_dry_run")]
+    fix: bool,
+
+    /// Print the machine-applicable fixes as a unified diff without writing
+    #[arg(long)]
+    fix_dry_run: bool,
+
+    /// Warn about suppression comments that no longer silence any finding
+    #[arg(long)]
+    report_unused_suppressions: bool,
 }
 
 fn main() -> Result<()> {
@@ -73,9 +101,12 @@ fn main() -> Result<()> {
     let config = load_config(&cli)?;
     info!("Configuration loaded: {:?}", config.analysis.document_type);
 
+    // Fixes are applied to the file as read, not to the extracted text
+    let original = (cli.fix || cli.fix_dry_run).then(|| text.clone());
+
     // Create analyzer
     let analyzer_start = Instant::now();
-    let analyzer = TextAnalyzer::new(text, config)?;
+    let analyzer = attach_cache(&cli, create_analyzer(&cli, text, config)?);
     info!("Analyzer initialized in {:?}", analyzer_start.elapsed());
 
     // Run analysis
@@ -100,6 +131,14 @@ fn main() -> Result<()> {
     let grammar_issues = analyzer.check_grammar()?;
     info!("Found {} grammar issues", grammar_issues.len());
 
+    if cli.report_unused_suppressions {
+        report_unused_suppressions(&cli, &analyzer)?;
+    }
+
+    if let Some(original) = original {
+        return apply_grammar_fixes(&cli, &original, &grammar_issues);
+    }
+
     // Run passive voice detection
     let passive_voice = analyzer.detect_passive_voice()?;
     info!("Found {} passive voice instances", passive_voice.len());
@@ -110,26 +149,31 @@ fn main() -> Result<()> {
     // Generate and output report
     if cli.quiet {
         print_statistics(&stats);
-    } else if cli.visualize {
+    } else if cli.visualize || cli.heatmap {
         // VISUAL HTML REPORT
         println!("🎨 Generating visual HTML report...");
         
-        let full_report = if cli.all {
-            Some(analyzer.generate_full_report()?)
+        let html = if cli.heatmap {
+            let breakdown = analyzer.readability_breakdown()?;
+            Rust_Grammar::HtmlVisualizer::generate_heatmap(&stats, &readability, &breakdown)
         } else {
-            None
+            let full_report = if cli.all {
+                Some(analyzer.generate_full_report()?)
+            } else {
+                None
+            };
+
+            Rust_Grammar::HtmlVisualizer::generate(
+                analyzer.text(),
+                analyzer.sentences(),
+                &stats,
+                &readability,
+                &grammar_issues,
+                &passive_voice,
+                full_report.as_ref(),
+            )
         };
         
-        let html = Rust_Grammar::HtmlVisualizer::generate(
-            analyzer.text(),
-            analyzer.sentences(),
-            &stats,
-            &readability,
-            &grammar_issues,
-            &passive_voice,
-            full_report.as_ref(),
-        );
-        
         // Determine output path
         let html_path = if let Some(output) = &cli.output {
             output.with_extension("html")
@@ -147,7 +191,7 @@ fn main() -> Result<()> {
         // COMPREHENSIVE ANALYSIS - ALL 19 FEATURES
         println!("🔍 Running comprehensive analysis (all features)...");
         let full_report = analyzer.generate_full_report()?;
-        print_comprehensive_report(&full_report);
+        print_comprehensive_report(&full_report, &analyzer.config().thresholds);
     } else {
         match cli.format.as_str() {
             "json" => print_json_report(&stats, &readability, &grammar_issues, &passive_voice)?,
@@ -167,6 +211,46 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn apply_grammar_fixes(cli: &Cli, original: &str, issues: &[GrammarIssue]) -> Result<()> {
+    let fixes = issues
+        .iter()
+        .flat_map(|issue| &issue.fixes)
+        .filter(|fix| fix.applicability == Applicability::MachineApplicable);
+    let outcome = apply_fixes(original, fixes);
+
+    if cli.fix_dry_run {
+        print!("{}", outcome.unified_diff(original, &cli.input_file.display().to_string()));
+    } else if outcome.applied > 0 {
+        fs::write(&cli.input_file, &outcome.text)?;
+    }
+
+    if !cli.quiet {
+        let verb = if cli.fix_dry_run { "Would apply" } else { "Applied" };
+        println!("\n🔧 {} {} fix(es) to {}", verb, outcome.applied, cli.input_file.display());
+        if outcome.skipped > 0 {
+            println!("   Skipped {} overlapping fix(es); run again to apply them", outcome.skipped);
+        }
+    }
+    Ok(())
+}
+
+/// Print a warning on stderr for each suppression directive that silences nothing
+fn report_unused_suppressions(cli: &Cli, analyzer: &TextAnalyzer) -> Result<()> {
+    let unused = analyzer.unused_suppressions()?;
+    for directive in &unused {
+        eprintln!(
+            "⚠️  {}:{}: unused suppression `{}`",
+            cli.input_file.display(),
+            directive.line,
+            directive
+        );
+    }
+    if !unused.is_empty() {
+        info!("Found {} unused suppressions", unused.len());
+    }
+    Ok(())
+}
+
 fn init_logging(cli: &Cli) {
     let level = if cli.debug {
         Level::DEBUG
@@ -190,6 +274,61 @@ fn read_input_file(path: &PathBuf) -> Result<String> {
         .map_err(|e| Rust_Grammar::error::AnalysisError::IoError(e))?)
 }
 
+fn create_analyzer(cli: &Cli, text: String, config: Config) -> Result<TextAnalyzer> {
+    let format = match cli.input_format.to_lowercase().as_str() {
+        "auto" => match cli.input_file.extension().and_then(|s| s.to_str()) {
+            Some("md") | Some("markdown") => "markdown",
+            Some("html") | Some("htm") => "html",
+            _ => "text",
+        },
+        "md" | "markdown" => "markdown",
+        "html" | "htm" => "html",
+        "text" | "txt" | "plain" => "text",
+        other => {
+            return Err(Rust_Grammar::error::AnalysisError::ConfigError(format!(
+                "Unknown input format: {}",
+                other
+            )))
+        }
+    };
+    info!("Input format: {}", format);
+
+    match format {
+        #[cfg(feature = "markdown")]
+        "markdown" => TextAnalyzer::from_markdown(&text, config),
+        #[cfg(not(feature = "markdown"))]
+        "markdown" => Err(Rust_Grammar::error::AnalysisError::ConfigError(
+            "Markdown input requires the `markdown` feature".to_string(),
+        )),
+        #[cfg(feature = "html")]
+        "html" => TextAnalyzer::from_html(&text, config),
+        #[cfg(not(feature = "html"))]
+        "html" => Err(Rust_Grammar::error::AnalysisError::ConfigError(
+            "HTML input requires the `html` feature".to_string(),
+        )),
+        _ => TextAnalyzer::new(text, config),
+    }
+}
+
+fn attach_cache(cli: &Cli, analyzer: TextAnalyzer) -> TextAnalyzer {
+    let cache_dir = cli.cache_dir.clone().or_else(|| {
+        if analyzer.config().analysis.cache_results {
+            dirs::cache_dir().map(|dir| dir.join("text-analyzer"))
+        } else {
+            None
+        }
+    });
+
+    match cache_dir {
+        Some(dir) => {
+            info!("Caching results in: {}", dir.display());
+            let cache = AnalysisCache::new(AnalysisCache::DEFAULT_CAPACITY).with_disk_dir(dir);
+            analyzer.with_cache(Arc::new(cache))
+        }
+        None => analyzer,
+    }
+}
+
 fn load_config(cli: &Cli) -> Result<Config> {
     // Load from config file if provided
     if let Some(config_path) = &cli.config {
@@ -247,6 +386,14 @@ fn print_text_report(
     if let Some(smog) = readability.smog_index {
         println!("SMOG Index: {:.1}", smog);
     }
+    println!("Gunning Fog: {:.1}", readability.gunning_fog);
+    println!("Coleman-Liau: {:.1}", readability.coleman_liau);
+    println!("Automated Readability Index: {:.1}", readability.automated_readability_index);
+    println!("Dale-Chall Score: {:.1}", readability.dale_chall);
+    println!("Spache: {:.1}", readability.spache);
+    println!("Linsear Write: {:.1}", readability.linsear_write);
+    println!("FORCAST: {:.1}", readability.forcast);
+    println!("Consensus Grade Level: {:.1}", readability.consensus_grade);
     println!("Avg Words/Sentence: {:.1}", readability.avg_words_per_sentence);
     println!("Avg Syllables/Word: {:.2}", readability.avg_syllables_per_word);
 
@@ -332,7 +479,10 @@ fn print_yaml_report(
     Ok(())
 }
 
-fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
+fn print_comprehensive_report(
+    report: &Rust_Grammar::FullAnalysisReport,
+    thresholds: &Rust_Grammar::config::ThresholdSettings,
+) {
     println!("\n{}", "=".repeat(80));
     println!("COMPREHENSIVE TEXT ANALYSIS REPORT - ALL FEATURES");
     println!("{}", "=".repeat(80));
@@ -354,7 +504,7 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     if !report.style.hidden_verbs.is_empty() {
         println!("\nHidden Verbs:");
         for hv in report.style.hidden_verbs.iter().take(10) {
-            println!("  • {}", hv);
+            println!("  • \"{}\" → \"{}\"", hv.text, hv.fix.edits[0].replacement);
         }
         if report.style.hidden_verbs.len() > 10 {
             println!("  ... and {} more", report.style.hidden_verbs.len() - 10);
@@ -396,7 +546,8 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     println!("Variety Score: {}/10", report.sentence_length.variety_score);
     println!("Shortest: {} words | Longest: {} words",
         report.sentence_length.shortest, report.sentence_length.longest);
-    println!("Very Long Sentences (>30 words): {}", report.sentence_length.very_long_sentences);
+    println!("Very Long Sentences (>{} words): {}",
+        thresholds.very_long_sentence, report.sentence_length.very_long_sentences);
 
     // Transitions
     println!("\n🔄 TRANSITION REPORT");
@@ -416,7 +567,7 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     println!("{}", "-".repeat(80));
     println!("Total Unique Words: {}", report.overused_words.total_unique_words);
     if !report.overused_words.overused_words.is_empty() {
-        println!("\nOverused Words (>0.5% frequency):");
+        println!("\nOverused Words (>{}% frequency):", thresholds.overused_word_threshold);
         for ow in report.overused_words.overused_words.iter().take(10) {
             println!("  • '{}': {} times ({}%)", ow.word, ow.count, ow.frequency);
         }
@@ -442,29 +593,37 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     // Echoes
     println!("\n🔊 ECHOES REPORT");
     println!("{}", "-".repeat(80));
-    println!("Total Echoes Found: {}", report.echoes.total_echoes);
-    if !report.echoes.echoes.is_empty() {
-        println!("\nClosest Echoes:");
-        for echo in report.echoes.echoes.iter().take(10) {
-            println!("  • '{}' in paragraph {}: {} times, {} words apart",
-                echo.word, echo.paragraph, echo.occurrences, echo.distance);
-        }
-        if report.echoes.echoes.len() > 10 {
-            println!("  ... and {} more", report.echoes.echoes.len() - 10);
+    if let Some(echoes) = &report.echoes {
+        println!("Total Echoes Found: {}", echoes.total_echoes);
+        if !echoes.echoes.is_empty() {
+            println!("\nClosest Echoes:");
+            for echo in echoes.echoes.iter().take(10) {
+                println!("  • '{}' in paragraph {}: {} times, {} words apart",
+                    echo.word, echo.paragraph, echo.occurrences, echo.distance);
+            }
+            if echoes.echoes.len() > 10 {
+                println!("  ... and {} more", echoes.echoes.len() - 10);
+            }
         }
+    } else {
+        println!("⏭️  Skipped (echo_detection is disabled)");
     }
 
     // Sensory
     println!("\n👁️ 👂 ✋ 👃 👅 SENSORY REPORT");
     println!("{}", "-".repeat(80));
-    println!("Total Sensory Words: {} ({}%)",
-        report.sensory.sensory_word_count, report.sensory.sensory_percentage);
-    println!("\nBy Sense:");
-    let mut senses: Vec<_> = report.sensory.by_sense.iter().collect();
-    senses.sort_by(|a, b| b.1.count.cmp(&a.1.count));
-    for (sense, data) in senses {
-        println!("  • {}: {} words ({}% of sensory), {} unique",
-            sense, data.count, data.percentage, data.unique_words);
+    if let Some(sensory) = &report.sensory {
+        println!("Total Sensory Words: {} ({}%)",
+            sensory.sensory_word_count, sensory.sensory_percentage);
+        println!("\nBy Sense:");
+        let mut senses: Vec<_> = sensory.by_sense.iter().collect();
+        senses.sort_by(|a, b| b.1.count.cmp(&a.1.count));
+        for (sense, data) in senses {
+            println!("  • {}: {} words ({}% of sensory), {} unique",
+                sense, data.count, data.percentage, data.unique_words);
+        }
+    } else {
+        println!("⏭️  Skipped (sensory_analysis is disabled)");
     }
 
     // Diction
@@ -482,30 +641,38 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     // Clichés
     println!("\n🎭 CLICHÉS REPORT");
     println!("{}", "-".repeat(80));
-    println!("Total Clichés Found: {}", report.cliches.total_cliches);
-    if !report.cliches.cliches.is_empty() {
-        println!("\nClichés:");
-        for cliche in &report.cliches.cliches {
-            println!("  • \"{}\": {} time(s)", cliche.cliche, cliche.count);
+    if let Some(cliches) = &report.cliches {
+        println!("Total Clichés Found: {}", cliches.total_cliches);
+        if !cliches.cliches.is_empty() {
+            println!("\nClichés:");
+            for cliche in &cliches.cliches {
+                println!("  • \"{}\": {} time(s)", cliche.cliche, cliche.count);
+            }
+        } else {
+            println!("✅ No clichés detected!");
         }
     } else {
-        println!("✅ No clichés detected!");
+        println!("⏭️  Skipped (cliche_detection is disabled)");
     }
 
     // Consistency
     println!("\n✅ CONSISTENCY REPORT");
     println!("{}", "-".repeat(80));
-    println!("Total Issues: {}", report.consistency.total_issues);
-    if !report.consistency.issues.is_empty() {
-        println!("\nInconsistencies Found:");
-        for issue in report.consistency.issues.iter().take(10) {
-            println!("  • {}", issue);
-        }
-        if report.consistency.issues.len() > 10 {
-            println!("  ... and {} more", report.consistency.issues.len() - 10);
+    if let Some(consistency) = &report.consistency {
+        println!("Total Issues: {}", consistency.total_issues);
+        if !consistency.issues.is_empty() {
+            println!("\nInconsistencies Found:");
+            for issue in consistency.issues.iter().take(10) {
+                println!("  • {}", issue);
+            }
+            if consistency.issues.len() > 10 {
+                println!("  ... and {} more", consistency.issues.len() - 10);
+            }
+        } else {
+            println!("✅ No consistency issues detected!");
         }
     } else {
-        println!("✅ No consistency issues detected!");
+        println!("⏭️  Skipped (consistency_check is disabled)");
     }
 
     // Acronyms
@@ -529,15 +696,19 @@ fn print_comprehensive_report(report: &Rust_Grammar::FullAnalysisReport) {
     // Business Jargon
     println!("\n💼 BUSINESS JARGON REPORT");
     println!("{}", "-".repeat(80));
-    println!("Total Jargon Instances: {}", report.business_jargon.total_jargon);
-    println!("Unique Jargon Phrases: {}", report.business_jargon.unique_jargon_phrases);
-    if !report.business_jargon.jargon_list.is_empty() {
-        println!("\nJargon Found:");
-        for j in &report.business_jargon.jargon_list {
-            println!("  • \"{}\": {} time(s)", j.jargon, j.count);
+    if let Some(jargon) = &report.business_jargon {
+        println!("Total Jargon Instances: {}", jargon.total_jargon);
+        println!("Unique Jargon Phrases: {}", jargon.unique_jargon_phrases);
+        if !jargon.jargon_list.is_empty() {
+            println!("\nJargon Found:");
+            for j in &jargon.jargon_list {
+                println!("  • \"{}\": {} time(s)", j.jargon, j.count);
+            }
+        } else {
+            println!("✅ No business jargon detected!");
         }
     } else {
-        println!("✅ No business jargon detected!");
+        println!("⏭️  Skipped (jargon_detection is disabled)");
     }
 
     // Complex Paragraphs
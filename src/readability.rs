@@ -0,0 +1,514 @@
+// Readability
+// Grade-level formulas, all computed from the same counts of sentences,
+// words, letters and syllables
+
+use crate::config::ReadabilitySettings;
+use crate::dictionaries::{count_syllables, is_familiar};
+use crate::grammar::SentenceSpan;
+use crate::span::{OffsetIndex, Span};
+use crate::WORD_EXTRACT;
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// Sentences SMOG needs, since it was normed on samples of 30
+const SMOG_SENTENCES: usize = 30;
+/// Endings that can make a word three syllables without making it hard, for Gunning Fog
+const FOG_ENDINGS: &[&str] = &["es", "ed", "ing"];
+
+/// What the readability formulas count in a text
+///
+/// Counts add up, so the counts of a paragraph or document are the sum of
+/// the counts of its sentences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextCounts {
+    pub sentences: usize,
+    pub words: usize,
+    /// Letters and digits
+    pub characters: usize,
+    pub syllables: usize,
+    /// Words of one syllable
+    pub monosyllables: usize,
+    /// Words of three or more syllables
+    pub polysyllables: usize,
+    /// Polysyllables other than names, hyphenated compounds and words that
+    /// only reach three syllables with "-es", "-ed" or "-ing"
+    pub complex_words: usize,
+    /// Words that are not Dale-Chall familiar words, names or numbers
+    pub unfamiliar_words: usize,
+}
+
+impl TextCounts {
+    /// Counts of a single sentence
+    pub fn of_sentence(sentence: &str) -> Self {
+        let mut counts = Self {
+            sentences: 1,
+            ..Self::default()
+        };
+        for (i, m) in WORD_EXTRACT.find_iter(sentence).enumerate() {
+            let word = m.as_str();
+            let lower = word.to_lowercase();
+            let syllables = count_syllables(&lower);
+            // Capitalized words past the first are taken for names
+            let name = i > 0 && word.starts_with(char::is_uppercase);
+            let number = word.contains(|c: char| c.is_ascii_digit());
+
+            counts.words += 1;
+            counts.characters += word.chars().filter(|c| c.is_alphanumeric()).count();
+            counts.syllables += syllables;
+            if syllables == 1 {
+                counts.monosyllables += 1;
+            }
+            if syllables >= 3 {
+                counts.polysyllables += 1;
+                let inflected = FOG_ENDINGS
+                    .iter()
+                    .any(|ending| lower.strip_suffix(ending).is_some_and(|stem| count_syllables(stem) < 3));
+                if !name && !word.contains('-') && !inflected {
+                    counts.complex_words += 1;
+                }
+            }
+            if !name && !number && !is_familiar(&lower) {
+                counts.unfamiliar_words += 1;
+            }
+        }
+        counts
+    }
+
+    /// Whether there is anything to score
+    pub fn is_empty(&self) -> bool {
+        self.sentences == 0 || self.words == 0
+    }
+
+    pub fn words_per_sentence(&self) -> f64 {
+        self.words as f64 / self.sentences.max(1) as f64
+    }
+
+    pub fn syllables_per_word(&self) -> f64 {
+        self.syllables as f64 / self.words.max(1) as f64
+    }
+
+    /// Share of `count` among the words, in percent
+    fn percent_of_words(&self, count: usize) -> f64 {
+        count as f64 / self.words.max(1) as f64 * 100.0
+    }
+
+    /// Flesch Reading Ease, from 0 (hardest) to 100 (easiest)
+    pub fn flesch_reading_ease(&self) -> f64 {
+        (206.835 - 1.015 * self.words_per_sentence() - 84.6 * self.syllables_per_word()).clamp(0.0, 100.0)
+    }
+
+    pub fn flesch_kincaid_grade(&self) -> f64 {
+        (0.39 * self.words_per_sentence() + 11.8 * self.syllables_per_word() - 15.59).max(0.0)
+    }
+
+    /// SMOG grade, meaningful from 30 sentences on
+    pub fn smog_index(&self) -> f64 {
+        1.0430 * (self.polysyllables as f64 * SMOG_SENTENCES as f64 / self.sentences.max(1) as f64).sqrt() + 3.1291
+    }
+
+    pub fn gunning_fog(&self) -> f64 {
+        0.4 * (self.words_per_sentence() + self.percent_of_words(self.complex_words))
+    }
+
+    /// Coleman-Liau index, from letters rather than syllables
+    pub fn coleman_liau(&self) -> f64 {
+        let letters = self.percent_of_words(self.characters);
+        let sentences = self.percent_of_words(self.sentences);
+        (0.0588 * letters - 0.296 * sentences - 15.8).max(0.0)
+    }
+
+    pub fn automated_readability_index(&self) -> f64 {
+        let characters_per_word = self.characters as f64 / self.words.max(1) as f64;
+        (4.71 * characters_per_word + 0.5 * self.words_per_sentence() - 21.43).max(0.0)
+    }
+
+    /// New Dale-Chall score; `dale_chall_grade` turns it into a grade level
+    pub fn dale_chall(&self) -> f64 {
+        let unfamiliar = self.percent_of_words(self.unfamiliar_words);
+        let adjustment = if unfamiliar > 5.0 { 3.6365 } else { 0.0 };
+        0.1579 * unfamiliar + 0.0496 * self.words_per_sentence() + adjustment
+    }
+
+    /// Revised Spache grade, meant for texts up to the fourth grade
+    ///
+    /// Unfamiliar words are judged by the Dale-Chall list.
+    pub fn spache(&self) -> f64 {
+        0.121 * self.words_per_sentence() + 0.082 * self.percent_of_words(self.unfamiliar_words) + 0.659
+    }
+
+    /// Linsear Write grade: one point per word of up to two syllables, three per longer word
+    ///
+    /// Points are averaged per sentence over the whole text rather than a
+    /// 100-word sample.
+    pub fn linsear_write(&self) -> f64 {
+        let points = (self.words - self.polysyllables) + 3 * self.polysyllables;
+        let per_sentence = points as f64 / self.sentences.max(1) as f64;
+        let grade = if per_sentence > 20.0 { per_sentence / 2.0 } else { (per_sentence - 2.0) / 2.0 };
+        grade.max(0.0)
+    }
+
+    /// FORCAST grade, from the share of one-syllable words scaled to a 150-word sample
+    pub fn forcast(&self) -> f64 {
+        let monosyllables = self.monosyllables as f64 * 150.0 / self.words.max(1) as f64;
+        20.0 - monosyllables / 10.0
+    }
+}
+
+impl Add for TextCounts {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for TextCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.sentences += other.sentences;
+        self.words += other.words;
+        self.characters += other.characters;
+        self.syllables += other.syllables;
+        self.monosyllables += other.monosyllables;
+        self.polysyllables += other.polysyllables;
+        self.complex_words += other.complex_words;
+        self.unfamiliar_words += other.unfamiliar_words;
+    }
+}
+
+impl Sum for TextCounts {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a TextCounts> for TextCounts {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Grade level of a New Dale-Chall score, at the middle of its band
+pub fn dale_chall_grade(score: f64) -> f64 {
+    match score {
+        s if s < 5.0 => 4.0,
+        s if s < 6.0 => 5.5,
+        s if s < 7.0 => 7.5,
+        s if s < 8.0 => 9.5,
+        s if s < 9.0 => 11.5,
+        s if s < 10.0 => 14.0,
+        _ => 16.0,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadabilityMetrics {
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+    pub smog_index: Option<f64>,
+    pub gunning_fog: f64,
+    pub coleman_liau: f64,
+    pub automated_readability_index: f64,
+    /// New Dale-Chall score, not a grade level
+    pub dale_chall: f64,
+    pub spache: f64,
+    pub linsear_write: f64,
+    pub forcast: f64,
+    /// Median of the grade levels: Flesch-Kincaid, SMOG when there is one,
+    /// Gunning Fog, Coleman-Liau, ARI, Dale-Chall, Linsear Write and FORCAST
+    pub consensus_grade: f64,
+    pub avg_words_per_sentence: f64,
+    pub avg_syllables_per_word: f64,
+}
+
+impl Default for ReadabilityMetrics {
+    fn default() -> Self {
+        Self {
+            flesch_reading_ease: 0.0,
+            flesch_kincaid_grade: 0.0,
+            smog_index: None,
+            gunning_fog: 0.0,
+            coleman_liau: 0.0,
+            automated_readability_index: 0.0,
+            dale_chall: 0.0,
+            spache: 0.0,
+            linsear_write: 0.0,
+            forcast: 0.0,
+            consensus_grade: 0.0,
+            avg_words_per_sentence: 0.0,
+            avg_syllables_per_word: 0.0,
+        }
+    }
+}
+
+impl ReadabilityMetrics {
+    /// Metrics of a text from the counts of each of its sentences, in order
+    ///
+    /// SMOG is only reported from 30 sentences on, over the first 30.
+    pub fn from_sentences(sentences: &[TextCounts]) -> Self {
+        let smog = (sentences.len() >= SMOG_SENTENCES)
+            .then(|| sentences[..SMOG_SENTENCES].iter().sum::<TextCounts>().smog_index());
+        Self::from_counts(&sentences.iter().sum(), smog)
+    }
+
+    /// Metrics of `counts`, with a SMOG grade worked out separately
+    pub fn from_counts(counts: &TextCounts, smog_index: Option<f64>) -> Self {
+        if counts.is_empty() {
+            return Self::default();
+        }
+
+        let flesch_kincaid_grade = counts.flesch_kincaid_grade();
+        let gunning_fog = counts.gunning_fog();
+        let coleman_liau = counts.coleman_liau();
+        let automated_readability_index = counts.automated_readability_index();
+        let dale_chall = counts.dale_chall();
+        let linsear_write = counts.linsear_write();
+        let forcast = counts.forcast();
+
+        let mut grades = vec![
+            flesch_kincaid_grade,
+            gunning_fog,
+            coleman_liau,
+            automated_readability_index,
+            dale_chall_grade(dale_chall),
+            linsear_write,
+            forcast,
+        ];
+        grades.extend(smog_index);
+
+        Self {
+            flesch_reading_ease: round(counts.flesch_reading_ease(), 1),
+            flesch_kincaid_grade: round(flesch_kincaid_grade, 1),
+            smog_index: smog_index.map(|smog| round(smog, 1)),
+            gunning_fog: round(gunning_fog, 1),
+            coleman_liau: round(coleman_liau, 1),
+            automated_readability_index: round(automated_readability_index, 1),
+            dale_chall: round(dale_chall, 1),
+            spache: round(counts.spache(), 1),
+            linsear_write: round(linsear_write, 1),
+            forcast: round(forcast, 1),
+            consensus_grade: round(median(&mut grades), 1),
+            avg_words_per_sentence: round(counts.words_per_sentence(), 1),
+            avg_syllables_per_word: round(counts.syllables_per_word(), 2),
+        }
+    }
+}
+
+/// How hard a sentence or paragraph is to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty {
+    Readable,
+    Hard,
+    VeryHard,
+}
+
+impl Difficulty {
+    /// Difficulty of a passage at consensus grade `grade`
+    pub fn of_grade(grade: f64, settings: &ReadabilitySettings) -> Self {
+        if grade >= settings.very_hard_grade {
+            Difficulty::VeryHard
+        } else if grade >= settings.hard_grade {
+            Difficulty::Hard
+        } else {
+            Difficulty::Readable
+        }
+    }
+}
+
+/// Readability of one sentence or paragraph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassageReadability {
+    /// Position among the sentences or paragraphs, from 0
+    pub index: usize,
+    pub text: String,
+    pub word_count: usize,
+    pub syllable_count: usize,
+    pub flesch_reading_ease: f64,
+    /// Consensus grade level, as in `ReadabilityMetrics`
+    pub grade_level: f64,
+    pub difficulty: Difficulty,
+    pub start_index: usize,
+    pub end_index: usize,
+    pub length: usize,
+    pub span: Span,
+}
+
+impl PassageReadability {
+    fn new(index: usize, text: &str, counts: &TextCounts, span: Span, settings: &ReadabilitySettings) -> Self {
+        let metrics = ReadabilityMetrics::from_counts(counts, None);
+        Self {
+            index,
+            text: text.to_string(),
+            word_count: counts.words,
+            syllable_count: counts.syllables,
+            flesch_reading_ease: metrics.flesch_reading_ease,
+            grade_level: metrics.consensus_grade,
+            difficulty: Difficulty::of_grade(metrics.consensus_grade, settings),
+            start_index: span.byte_start,
+            end_index: span.byte_end,
+            length: span.byte_end - span.byte_start,
+            span,
+        }
+    }
+}
+
+/// Readability sentence by sentence and paragraph by paragraph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadabilityBreakdown {
+    pub sentences: Vec<PassageReadability>,
+    pub paragraphs: Vec<PassageReadability>,
+    /// Indexes into `sentences` of the hard and very hard sentences, hardest first
+    pub hardest_sentences: Vec<usize>,
+}
+
+impl ReadabilityBreakdown {
+    /// Breakdown of `text`, split into `sentences`
+    ///
+    /// A paragraph runs from its first sentence to its last, so its counts
+    /// are the sum of theirs.
+    pub fn new(text: &str, sentences: &[SentenceSpan], settings: &ReadabilitySettings) -> Self {
+        let index = OffsetIndex::new(text);
+        let counts: Vec<TextCounts> = sentences.iter().map(|s| TextCounts::of_sentence(&s.text)).collect();
+
+        let mut paragraphs = Vec::new();
+        let mut start = 0;
+        while start < sentences.len() {
+            let paragraph = sentences[start].paragraph;
+            let end = start + sentences[start..].iter().take_while(|s| s.paragraph == paragraph).count();
+            let span = index.span(sentences[start].span.byte_start, sentences[end - 1].span.byte_end);
+            let total: TextCounts = counts[start..end].iter().sum();
+            paragraphs.push(PassageReadability::new(
+                paragraphs.len(),
+                &text[span.byte_range()],
+                &total,
+                span,
+                settings,
+            ));
+            start = end;
+        }
+
+        let sentences: Vec<PassageReadability> = sentences
+            .iter()
+            .zip(&counts)
+            .enumerate()
+            .map(|(i, (sentence, counts))| PassageReadability::new(i, &sentence.text, counts, sentence.span, settings))
+            .collect();
+
+        // Longer sentences first among equals, as they weigh more on the document
+        let mut hardest_sentences: Vec<usize> = sentences
+            .iter()
+            .filter(|s| s.difficulty != Difficulty::Readable)
+            .map(|s| s.index)
+            .collect();
+        hardest_sentences.sort_by(|&a, &b| {
+            let (a, b) = (&sentences[a], &sentences[b]);
+            b.grade_level.total_cmp(&a.grade_level).then(b.word_count.cmp(&a.word_count))
+        });
+
+        Self {
+            sentences,
+            paragraphs,
+            hardest_sentences,
+        }
+    }
+
+    /// The hard and very hard sentences, hardest first
+    pub fn hardest(&self) -> impl Iterator<Item = &PassageReadability> {
+        self.hardest_sentences.iter().map(|&i| &self.sentences[i])
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+/// Round to `decimals` places
+fn round(value: f64, decimals: u32) -> f64 {
+    let multiplier = 10_f64.powi(decimals as i32);
+    (value * multiplier).round() / multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_counts() {
+        let counts = TextCounts::of_sentence("Yesterday Alexandria was relaxing with dangerous animals.");
+        assert_eq!((counts.words, counts.monosyllables, counts.polysyllables), (7, 2, 5));
+        // Not the name, nor "relaxing", which only "-ing" makes three syllables
+        assert_eq!(counts.complex_words, 3);
+        // "relaxing"; the name does not count and "animals" is a familiar plural
+        assert_eq!(counts.unfamiliar_words, 1);
+    }
+
+    #[test]
+    fn test_counts_add_up() {
+        let a = TextCounts::of_sentence("The cat sat on the mat.");
+        let b = TextCounts::of_sentence("Nobody expected an extraordinary interruption.");
+        let total: TextCounts = [a, b].iter().sum();
+        assert_eq!(total, a + b);
+        assert_eq!((total.sentences, total.words), (2, 11));
+    }
+
+    #[test]
+    fn test_harder_text_scores_higher_on_every_grade() {
+        let counts = |sentences: &[&str]| {
+            ReadabilityMetrics::from_sentences(&sentences.iter().map(|s| TextCounts::of_sentence(s)).collect::<Vec<_>>())
+        };
+        let easy = counts(&["The dog ran to the park.", "He saw a big red ball.", "It was fun to play."]);
+        let hard = counts(&[
+            "Institutional stakeholders systematically underestimated the organizational ramifications of decentralization.",
+            "Consequently, administrative inefficiencies proliferated throughout interdepartmental communications.",
+        ]);
+
+        assert!(easy.flesch_reading_ease > hard.flesch_reading_ease);
+        for (easy, hard) in [
+            (easy.flesch_kincaid_grade, hard.flesch_kincaid_grade),
+            (easy.gunning_fog, hard.gunning_fog),
+            (easy.coleman_liau, hard.coleman_liau),
+            (easy.automated_readability_index, hard.automated_readability_index),
+            (easy.dale_chall, hard.dale_chall),
+            (easy.spache, hard.spache),
+            (easy.linsear_write, hard.linsear_write),
+            (easy.forcast, hard.forcast),
+            (easy.consensus_grade, hard.consensus_grade),
+        ] {
+            assert!(easy < hard, "{} < {}", easy, hard);
+        }
+        assert!(easy.smog_index.is_none());
+    }
+
+    #[test]
+    fn test_breakdown_by_sentence_and_paragraph() {
+        let text = "The dog ran. It was fun.\n\nInstitutional stakeholders systematically underestimated the organizational ramifications of decentralization. We left.";
+        let sentences = crate::grammar::SentenceSplitter::default().split_spans(text).unwrap();
+        let breakdown = ReadabilityBreakdown::new(text, &sentences, &ReadabilitySettings::default());
+
+        assert_eq!(breakdown.sentences.len(), 4);
+        assert_eq!(breakdown.paragraphs.len(), 2);
+        assert_eq!(&text[breakdown.paragraphs[0].span.byte_range()], "The dog ran. It was fun.");
+        assert_eq!(breakdown.paragraphs[1].word_count, 11);
+
+        let hardest: Vec<&str> = breakdown.hardest().map(|s| s.text.as_str()).collect();
+        assert_eq!(hardest.len(), 1);
+        assert!(hardest[0].starts_with("Institutional"));
+        assert_eq!(breakdown.sentences[2].difficulty, Difficulty::VeryHard);
+        assert_eq!(breakdown.sentences[0].difficulty, Difficulty::Readable);
+        assert!(breakdown.paragraphs[1].grade_level > breakdown.paragraphs[0].grade_level);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+}
@@ -0,0 +1,212 @@
+// Spans
+// Byte, char and UTF-16 offsets of a finding in the original text
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Location of a finding in the original text
+///
+/// Byte offsets index Rust strings, char offsets count Unicode scalar values,
+/// and UTF-16 offsets match JavaScript string indices. All ranges are half-open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Span {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub utf16_start: usize,
+    pub utf16_end: usize,
+}
+
+impl Span {
+    /// Span of `byte_start..byte_end` in `text`
+    ///
+    /// Builds a throwaway index; use `OffsetIndex` when converting many ranges.
+    pub fn in_text(text: &str, byte_start: usize, byte_end: usize) -> Self {
+        OffsetIndex::new(text).span(byte_start, byte_end)
+    }
+
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_end
+    }
+
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_start..self.char_end
+    }
+
+    pub fn utf16_range(&self) -> Range<usize> {
+        self.utf16_start..self.utf16_end
+    }
+}
+
+/// Converts byte offsets of one text into char and UTF-16 offsets
+///
+/// Only non-ASCII characters are recorded, so an ASCII text costs nothing and
+/// each lookup is a binary search.
+pub struct OffsetIndex<'a> {
+    text: &'a str,
+    /// (byte offset, char offset, UTF-16 offset) just after each non-ASCII char
+    checkpoints: Vec<(usize, usize, usize)>,
+}
+
+impl<'a> OffsetIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let mut checkpoints = Vec::new();
+        let mut utf16 = 0;
+        for (chars, (byte, c)) in text.char_indices().enumerate() {
+            utf16 += c.len_utf16();
+            if !c.is_ascii() {
+                checkpoints.push((byte + c.len_utf8(), chars + 1, utf16));
+            }
+        }
+        Self { text, checkpoints }
+    }
+
+    /// Span of `byte_start..byte_end`, clamped to the text and to char boundaries
+    pub fn span(&self, byte_start: usize, byte_end: usize) -> Span {
+        let byte_start = self.floor_boundary(byte_start);
+        let byte_end = self.floor_boundary(byte_end).max(byte_start);
+        let (char_start, utf16_start) = self.offsets_at(byte_start);
+        let (char_end, utf16_end) = self.offsets_at(byte_end);
+        Span {
+            byte_start,
+            byte_end,
+            char_start,
+            char_end,
+            utf16_start,
+            utf16_end,
+        }
+    }
+
+    /// Char and UTF-16 offsets of a byte offset on a char boundary
+    fn offsets_at(&self, byte: usize) -> (usize, usize) {
+        let i = self.checkpoints.partition_point(|&(b, _, _)| b <= byte);
+        match i.checked_sub(1).map(|i| self.checkpoints[i]) {
+            // Everything between the checkpoint and `byte` is ASCII
+            Some((b, chars, utf16)) => (chars + byte - b, utf16 + byte - b),
+            None => (byte, byte),
+        }
+    }
+
+    fn floor_boundary(&self, byte: usize) -> usize {
+        let mut byte = byte.min(self.text.len());
+        while !self.text.is_char_boundary(byte) {
+            byte -= 1;
+        }
+        byte
+    }
+}
+
+/// Start offset of each piece, found in order in `text`
+///
+/// Pieces are expected to be verbatim substrings appearing in sequence, like
+/// trimmed sentences or paragraphs. A piece that cannot be found is placed at
+/// the current search position.
+pub fn locate_sequence(text: &str, pieces: &[String]) -> Vec<usize> {
+    let mut cursor = 0;
+    pieces
+        .iter()
+        .map(|piece| match text[cursor..].find(piece.as_str()) {
+            Some(pos) => {
+                let start = cursor + pos;
+                cursor = start + piece.len();
+                start
+            }
+            None => cursor,
+        })
+        .collect()
+}
+
+/// Lowercased copy of a text that can translate its offsets back to the original
+///
+/// Most text lowercases byte for byte, in which case no offset table is kept.
+pub struct Lowercased {
+    pub text: String,
+    /// Original byte offset of every byte of `text`, plus one for the end
+    offsets: Option<Vec<usize>>,
+}
+
+impl Lowercased {
+    pub fn new(original: &str) -> Self {
+        let mut text = String::with_capacity(original.len());
+        let mut offsets: Option<Vec<usize>> = None;
+        for (byte, c) in original.char_indices() {
+            let lower = c.to_lowercase();
+            let same_width = lower.len() == 1 && lower.clone().all(|l| l.len_utf8() == c.len_utf8());
+            if !same_width && offsets.is_none() {
+                offsets = Some((0..text.len()).collect());
+            }
+            for l in lower {
+                text.push(l);
+                if let Some(offsets) = &mut offsets {
+                    offsets.extend(std::iter::repeat(byte).take(l.len_utf8()));
+                }
+            }
+        }
+        if let Some(offsets) = &mut offsets {
+            offsets.push(original.len());
+        }
+        Self { text, offsets }
+    }
+
+    /// Offset in the original text of a byte offset in the lowercased text
+    pub fn original(&self, offset: usize) -> usize {
+        match &self.offsets {
+            Some(offsets) => offsets[offset.min(offsets.len() - 1)],
+            None => offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_offsets_are_identical() {
+        let span = Span::in_text("plain ascii text", 6, 11);
+        assert_eq!(span.char_range(), 6..11);
+        assert_eq!(span.utf16_range(), 6..11);
+    }
+
+    #[test]
+    fn test_non_ascii_offsets() {
+        // 'é' is 2 bytes / 1 UTF-16 unit, '😀' is 4 bytes / 2 UTF-16 units
+        let text = "café 😀 smile";
+        let start = text.find("smile").unwrap();
+        let span = Span::in_text(text, start, start + 5);
+
+        assert_eq!(span.byte_range(), 11..16);
+        assert_eq!(span.char_range(), 7..12);
+        assert_eq!(span.utf16_range(), 8..13);
+        let chars: String = text.chars().skip(span.char_start).take(5).collect();
+        assert_eq!(chars, "smile");
+    }
+
+    #[test]
+    fn test_offsets_inside_a_char_are_floored() {
+        let span = Span::in_text("é", 1, 9);
+        assert_eq!(span.byte_range(), 0..2);
+        assert_eq!(span.char_range(), 0..1);
+    }
+
+    #[test]
+    fn test_locate_sequence() {
+        let text = "One.  Two.\nOne.";
+        let pieces = vec!["One.".to_string(), "Two.".to_string(), "One.".to_string()];
+        assert_eq!(locate_sequence(text, &pieces), vec![0, 6, 11]);
+    }
+
+    #[test]
+    fn test_lowercase_offsets_follow_the_original() {
+        // 'İ' lowercases to two chars, shifting every later byte
+        let text = "İstanbul Is Big";
+        let lower = Lowercased::new(text);
+        let pos = lower.text.find("big").unwrap();
+        assert_eq!(&text[lower.original(pos)..lower.original(pos + 3)], "Big");
+
+        let ascii = Lowercased::new("Plain Text");
+        assert_eq!(ascii.text, "plain text");
+        assert_eq!(ascii.original(6), 6);
+    }
+}
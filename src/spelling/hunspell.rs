@@ -0,0 +1,485 @@
+// Hunspell dictionaries
+// Parses .aff/.dic pairs and expands each stem with its affix rules into the
+// set of accepted word forms
+
+use crate::error::{AnalysisError, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Affix flag, decoded according to the `FLAG` setting
+type Flag = u32;
+
+/// How flags are written after the slash in the .dic file (`FLAG`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagMode {
+    /// One character per flag, the default (also `FLAG UTF-8`)
+    Char,
+    /// Two characters per flag (`FLAG long`)
+    Long,
+    /// Comma-separated numbers (`FLAG num`)
+    Num,
+}
+
+impl FlagMode {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagMode::Char => flags.chars().map(|c| c as Flag).collect(),
+            FlagMode::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| pair.iter().fold(0, |flag, &c| (flag << 16) | c as Flag))
+                    .collect()
+            }
+            FlagMode::Num => flags.split(',').filter_map(|n| n.trim().parse().ok()).collect(),
+        }
+    }
+
+    fn parse_one(self, flag: &str) -> Option<Flag> {
+        self.parse(flag).first().copied()
+    }
+}
+
+/// One character position of an affix condition
+#[derive(Debug, Clone)]
+enum CharClass {
+    Any,
+    /// Characters in brackets; `true` when negated with `^`
+    Set(Vec<char>, bool),
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Set(chars, negated) => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// Condition a stem must meet for an affix to apply, like `[^aeiou]y`
+#[derive(Debug, Clone)]
+struct Condition(Vec<CharClass>);
+
+impl Condition {
+    fn parse(pattern: &str) -> Self {
+        let mut classes = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => classes.push(CharClass::Any),
+                '[' => {
+                    let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                    let negated = set.first() == Some(&'^');
+                    if negated {
+                        set.remove(0);
+                    }
+                    classes.push(CharClass::Set(set, negated));
+                }
+                c => classes.push(CharClass::Set(vec![c], false)),
+            }
+        }
+        Self(classes)
+    }
+
+    fn matches_start(&self, stem: &[char]) -> bool {
+        self.0.len() <= stem.len() && self.0.iter().zip(stem).all(|(class, &c)| class.matches(c))
+    }
+
+    fn matches_end(&self, stem: &[char]) -> bool {
+        self.0.len() <= stem.len() && self.0.iter().zip(&stem[stem.len() - self.0.len()..]).all(|(class, &c)| class.matches(c))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AffixEntry {
+    strip: String,
+    add: String,
+    /// Flags of further affixes that may attach to the affixed form
+    continuation: Vec<Flag>,
+    condition: Condition,
+}
+
+impl AffixEntry {
+    fn apply_prefix(&self, stem: &str) -> Option<String> {
+        let chars: Vec<char> = stem.chars().collect();
+        (stem.starts_with(&self.strip) && self.condition.matches_start(&chars))
+            .then(|| format!("{}{}", self.add, &stem[self.strip.len()..]))
+    }
+
+    fn apply_suffix(&self, stem: &str) -> Option<String> {
+        let chars: Vec<char> = stem.chars().collect();
+        (stem.ends_with(&self.strip) && self.condition.matches_end(&chars))
+            .then(|| format!("{}{}", &stem[..stem.len() - self.strip.len()], self.add))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Affix {
+    /// Whether the affix combines with affixes of the other kind
+    cross_product: bool,
+    entries: Vec<AffixEntry>,
+}
+
+/// Affix rules and special flags from an .aff file
+#[derive(Debug, Default)]
+struct AffixFile {
+    flag_mode: Option<FlagMode>,
+    prefixes: HashMap<Flag, Affix>,
+    suffixes: HashMap<Flag, Affix>,
+    need_affix: Option<Flag>,
+    forbidden: Option<Flag>,
+    no_suggest: Option<Flag>,
+}
+
+impl AffixFile {
+    fn parse(aff: &str) -> Result<Self> {
+        let mut file = AffixFile::default();
+        let mode = |file: &AffixFile| file.flag_mode.unwrap_or(FlagMode::Char);
+
+        for (line_num, line) in aff.lines().enumerate() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let invalid = || AnalysisError::DictionaryError(format!("invalid affix rule on line {}: {}", line_num + 1, line));
+            match parts.as_slice() {
+                ["FLAG", value, ..] => {
+                    file.flag_mode = Some(match *value {
+                        "long" => FlagMode::Long,
+                        "num" => FlagMode::Num,
+                        _ => FlagMode::Char,
+                    })
+                }
+                ["NEEDAFFIX" | "ONLYINCOMPOUND", flag, ..] => file.need_affix = mode(&file).parse_one(flag),
+                ["FORBIDDENWORD", flag, ..] => file.forbidden = mode(&file).parse_one(flag),
+                ["NOSUGGEST", flag, ..] => file.no_suggest = mode(&file).parse_one(flag),
+                [kind @ ("PFX" | "SFX"), flag, rest @ ..] => {
+                    let mode = mode(&file);
+                    let flag = mode.parse_one(flag).ok_or_else(invalid)?;
+                    let affixes = if *kind == "PFX" { &mut file.prefixes } else { &mut file.suffixes };
+                    match affixes.get_mut(&flag) {
+                        // The first line of a rule is its header: "SFX S Y 4"
+                        None => {
+                            let cross_product = rest.first().ok_or_else(invalid)? == &"Y";
+                            affixes.insert(flag, Affix { cross_product, entries: Vec::new() });
+                        }
+                        Some(affix) => {
+                            let [strip, add, condition @ ..] = rest else {
+                                return Err(invalid());
+                            };
+                            let (add, continuation) = add.split_once('/').unwrap_or((add, ""));
+                            let empty = |s: &str| if s == "0" { String::new() } else { s.to_string() };
+                            affix.entries.push(AffixEntry {
+                                strip: empty(strip),
+                                add: empty(add),
+                                continuation: mode.parse(continuation),
+                                condition: Condition::parse(condition.first().copied().unwrap_or(".")),
+                            });
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(file)
+    }
+}
+
+/// Word forms accepted by a Hunspell dictionary
+///
+/// Affixes are expanded once at load time, so lookups are a hash probe.
+/// Prefixes and suffixes combine when both allow cross products, and a
+/// suffix's continuation flags are followed one level deep.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+    /// Forms flagged `FORBIDDENWORD`, rejected even when an affix rule produces them
+    forbidden: HashSet<String>,
+    /// Forms to offer as suggestions, indexed by length in chars
+    suggestable: Vec<Vec<String>>,
+}
+
+impl Dictionary {
+    /// Load a dictionary from its .aff and .dic files
+    ///
+    /// Files are read as UTF-8, or as Latin-1 when the .aff declares an ISO-8859 `SET`.
+    pub fn load(aff_path: &Path, dic_path: &Path) -> Result<Self> {
+        let read = |path: &Path| {
+            fs::read(path).map_err(|e| AnalysisError::DictionaryError(format!("cannot read {}: {}", path.display(), e)))
+        };
+        let (aff, dic) = (read(aff_path)?, read(dic_path)?);
+        let latin1 = aff
+            .split(|&b| b == b'\n')
+            .any(|line| line.starts_with(b"SET ISO8859") || line.starts_with(b"SET ISO-8859"));
+        let decode = |bytes: &[u8]| {
+            if latin1 {
+                bytes.iter().map(|&b| b as char).collect()
+            } else {
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+        };
+        Self::parse(&decode(&aff), &decode(&dic))
+    }
+
+    /// Build a dictionary from the contents of an .aff and a .dic file
+    pub fn parse(aff: &str, dic: &str) -> Result<Self> {
+        let affixes = AffixFile::parse(aff)?;
+        let mode = affixes.flag_mode.unwrap_or(FlagMode::Char);
+        let mut dictionary = Dictionary::default();
+        let mut no_suggest = HashSet::new();
+
+        for (i, line) in dic.lines().enumerate() {
+            let line = line.trim();
+            // The first line holds the approximate word count
+            if line.is_empty() || (i == 0 && line.chars().all(|c| c.is_ascii_digit())) {
+                continue;
+            }
+            let (stem, flags) = split_entry(line);
+            let flags = mode.parse(&flags);
+            let has = |flag: Option<Flag>| flag.is_some_and(|f| flags.contains(&f));
+
+            let mut forms = Vec::new();
+            if !has(affixes.need_affix) {
+                forms.push(stem.clone());
+            }
+            expand(&affixes, &stem, &flags, &mut forms);
+
+            if has(affixes.forbidden) {
+                dictionary.forbidden.extend(forms);
+            } else {
+                if has(affixes.no_suggest) {
+                    no_suggest.extend(forms.iter().cloned());
+                }
+                dictionary.words.extend(forms);
+            }
+        }
+
+        for word in &dictionary.words {
+            if dictionary.forbidden.contains(word) || no_suggest.contains(word) {
+                continue;
+            }
+            let len = word.chars().count();
+            if dictionary.suggestable.len() <= len {
+                dictionary.suggestable.resize(len + 1, Vec::new());
+            }
+            dictionary.suggestable[len].push(word.clone());
+        }
+        for forms in &mut dictionary.suggestable {
+            forms.sort();
+        }
+        Ok(dictionary)
+    }
+
+    /// Number of accepted word forms
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether `word` is spelled correctly
+    ///
+    /// A capitalized or all-caps word is also accepted in lowercase, as at
+    /// the start of a sentence; a lowercase word must match exactly.
+    pub fn check(&self, word: &str) -> bool {
+        let word = word.replace('’', "'");
+        let accepted = |w: &str| self.words.contains(w) && !self.forbidden.contains(w);
+        if accepted(&word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        let capitalized = capitalize(&lower);
+        if word == capitalized {
+            return accepted(&lower);
+        }
+        // All caps: "BOXES", "PARIS"
+        word == word.to_uppercase() && (accepted(&lower) || accepted(&capitalized))
+    }
+
+    /// Accepted words within two edits of `word`, closest first
+    ///
+    /// Edits are insertions, deletions, substitutions and swaps of adjacent
+    /// characters; ties go to words of the same length, then alphabetical order.
+    pub fn suggest(&self, word: &str, max: usize) -> Vec<String> {
+        const MAX_DISTANCE: usize = 2;
+        let lower: Vec<char> = word.replace('’', "'").to_lowercase().chars().collect();
+        let len = lower.len();
+
+        let mut candidates: Vec<(usize, usize, &String)> = Vec::new();
+        for forms in self.suggestable.iter().take(len + MAX_DISTANCE + 1).skip(len.saturating_sub(MAX_DISTANCE)) {
+            for form in forms {
+                let form_lower: Vec<char> = form.to_lowercase().chars().collect();
+                if let Some(distance) = edit_distance(&lower, &form_lower, MAX_DISTANCE) {
+                    candidates.push((distance, form_lower.len().abs_diff(len), form));
+                }
+            }
+        }
+        candidates.sort();
+
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        let mut suggestions: Vec<String> = Vec::new();
+        for (_, _, form) in candidates {
+            let suggestion = if capitalized { capitalize(form) } else { form.clone() };
+            if !suggestions.contains(&suggestion) && suggestion != word {
+                suggestions.push(suggestion);
+            }
+            if suggestions.len() == max {
+                break;
+            }
+        }
+        suggestions
+    }
+}
+
+/// Stem and flags of a .dic line like `walk/DGS po:verb`; `\/` escapes a slash
+fn split_entry(line: &str) -> (String, String) {
+    let entry = line.split(['\t', ' ']).next().unwrap_or(line);
+    let mut stem = String::new();
+    let mut chars = entry.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => stem.extend(chars.next()),
+            '/' => return (stem, chars.collect()),
+            c => stem.push(c),
+        }
+    }
+    (stem, String::new())
+}
+
+/// Push every affixed form of `stem` onto `forms`
+fn expand(affixes: &AffixFile, stem: &str, flags: &[Flag], forms: &mut Vec<String>) {
+    let mut suffixed = Vec::new();
+    for suffix in flags.iter().filter_map(|f| affixes.suffixes.get(f)) {
+        for entry in &suffix.entries {
+            let Some(form) = entry.apply_suffix(stem) else {
+                continue;
+            };
+            for next in entry.continuation.iter().filter_map(|f| affixes.suffixes.get(f)) {
+                forms.extend(next.entries.iter().filter_map(|e| e.apply_suffix(&form)));
+            }
+            if suffix.cross_product {
+                suffixed.push(form.clone());
+            }
+            forms.push(form);
+        }
+    }
+
+    for prefix in flags.iter().filter_map(|f| affixes.prefixes.get(f)) {
+        for entry in &prefix.entries {
+            forms.extend(entry.apply_prefix(stem));
+            if prefix.cross_product {
+                forms.extend(suffixed.iter().filter_map(|s| entry.apply_prefix(s)));
+            }
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Optimal string alignment distance between `a` and `b`, or `None` if over `max`
+fn edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut before: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        if row.iter().all(|&d| d > max) {
+            return None;
+        }
+        before = std::mem::replace(&mut prev, row);
+    }
+    Some(prev[b.len()]).filter(|&d| d <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+NOSUGGEST !
+FORBIDDENWORD X
+NEEDAFFIX Z
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+
+SFX D Y 2
+SFX D   0     d          e
+SFX D   0     ed         [^e]
+
+SFX M Y 1
+SFX M   0     ment/S     .
+";
+
+    const DIC: &str = "8
+city/S
+happy/U
+lock/USD
+box/S
+damn/!
+walk/DZ
+irregardless/X
+govern/M
+";
+
+    #[test]
+    fn test_affixes_expand_stems() {
+        let dictionary = Dictionary::parse(AFF, DIC).unwrap();
+        for word in ["city", "cities", "boxes", "unhappy", "locks", "unlocks", "locked", "walked", "governments"] {
+            assert!(dictionary.check(word), "{}", word);
+        }
+        for word in ["citys", "unbox", "walk", "irregardless", "happies"] {
+            assert!(!dictionary.check(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_case_rules() {
+        let dictionary = Dictionary::parse(AFF, DIC).unwrap();
+        assert!(dictionary.check("Cities"));
+        assert!(dictionary.check("BOXES"));
+        assert!(!dictionary.check("hAPPY"));
+    }
+
+    #[test]
+    fn test_suggestions_ranked_by_edit_distance() {
+        let dictionary = Dictionary::parse(AFF, DIC).unwrap();
+        assert_eq!(dictionary.suggest("citys", 3), vec!["city", "cities"]);
+        assert_eq!(dictionary.suggest("Lcoked", 1), vec!["Locked"]);
+        // NOSUGGEST words are accepted but never offered
+        assert!(dictionary.check("damn"));
+        assert!(dictionary.suggest("damm", 5).is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("recieve"), &chars("receive"), 2), Some(1));
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting"), 2), None);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting"), 3), Some(3));
+    }
+}
@@ -0,0 +1,263 @@
+// Spelling
+// Checks words against Hunspell dictionaries loaded from local files
+
+pub mod hunspell;
+
+pub use hunspell::Dictionary;
+
+use crate::config::SpellingSettings;
+use crate::error::{AnalysisError, Result};
+use crate::fix::Applicability;
+use crate::grammar::rules::{Rule, RuleCategory, SentenceContext};
+use crate::grammar::sentence_splitter::verbatim_ranges;
+use crate::grammar::{GrammarIssue, GrammarIssueType, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+lazy_static! {
+    /// Words, with inner apostrophes: "don't", "O’Brien"
+    static ref WORD: Regex = Regex::new(r"\p{L}+(?:['’]\p{L}+)*").unwrap();
+
+    /// Dictionaries loaded so far, by .dic path, shared by every checker
+    static ref LOADED: Mutex<HashMap<PathBuf, Arc<Dictionary>>> = Mutex::new(HashMap::new());
+}
+
+/// System directories holding Hunspell dictionaries
+const SYSTEM_DIRS: &[&str] = &["/usr/share/hunspell", "/usr/share/myspell", "/usr/share/myspell/dicts", "/Library/Spelling"];
+
+/// A word the dictionary does not accept
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misspelling {
+    /// Byte range of the word in the checked text
+    pub range: Range<usize>,
+    pub word: String,
+    /// Replacements, closest first
+    pub suggestions: Vec<String>,
+}
+
+/// Finds misspelled words in text
+#[derive(Debug, Clone)]
+pub struct SpellChecker {
+    dictionary: Arc<Dictionary>,
+    /// Extra accepted words, lowercased
+    words: HashSet<String>,
+    max_suggestions: usize,
+}
+
+impl SpellChecker {
+    pub fn new(dictionary: Arc<Dictionary>) -> Self {
+        Self {
+            dictionary,
+            words: HashSet::new(),
+            max_suggestions: 5,
+        }
+    }
+
+    /// Spell checker for `settings`, loading each dictionary once per process
+    pub fn from_settings(settings: &SpellingSettings) -> Result<Self> {
+        let (aff, dic) = find_dictionary(settings)?;
+        let mut loaded = LOADED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let dictionary = match loaded.get(&dic) {
+            Some(dictionary) => dictionary.clone(),
+            None => {
+                let dictionary = Arc::new(Dictionary::load(&aff, &dic)?);
+                loaded.insert(dic, dictionary.clone());
+                dictionary
+            }
+        };
+        Ok(Self::new(dictionary)
+            .with_words(&settings.words)
+            .with_max_suggestions(settings.max_suggestions))
+    }
+
+    /// Also accept `words`, in any case
+    pub fn with_words<S: AsRef<str>>(mut self, words: impl IntoIterator<Item = S>) -> Self {
+        self.words.extend(words.into_iter().map(|w| w.as_ref().to_lowercase()));
+        self
+    }
+
+    pub fn with_max_suggestions(mut self, max: usize) -> Self {
+        self.max_suggestions = max;
+        self
+    }
+
+    pub fn is_correct(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase()) || self.dictionary.check(word)
+    }
+
+    /// Misspelled words in `text`
+    ///
+    /// URLs, email addresses, `code spans`, acronyms, words with inner capitals
+    /// ("iPhone") and words joined to digits or underscores are skipped.
+    pub fn check(&self, text: &str) -> Vec<Misspelling> {
+        let skipped = verbatim_ranges(text);
+
+        WORD.find_iter(text)
+            .filter(|m| !skipped.iter().any(|r| r.start < m.end() && m.start() < r.end))
+            .filter(|m| !is_exempt(text, m.range()) && !self.is_correct(m.as_str()))
+            .map(|m| Misspelling {
+                range: m.range(),
+                word: m.as_str().to_string(),
+                suggestions: self.dictionary.suggest(m.as_str(), self.max_suggestions),
+            })
+            .collect()
+    }
+}
+
+/// Single letters, acronyms, inner capitals, and parts of identifiers like `snake_case` or `utf8`
+fn is_exempt(text: &str, range: Range<usize>) -> bool {
+    let word = &text[range.clone()];
+    let identifier = |c: char| c.is_ascii_digit() || c == '_';
+    let joined = text[..range.start].chars().next_back().is_some_and(identifier)
+        || text[range.end..].chars().next().is_some_and(identifier);
+    word.chars().nth(1).is_none() || word.chars().skip(1).any(char::is_uppercase) || joined
+}
+
+/// Directories searched for a dictionary by language, in order
+///
+/// After the configured ones come `dictionaries/hunspell` in the working
+/// directory and in this crate, where bundled dictionaries live, then the
+/// usual system locations.
+pub fn dictionary_dirs(settings: &SpellingSettings) -> Vec<PathBuf> {
+    let mut searched = settings.dictionary_dirs.clone();
+    searched.push(PathBuf::from("dictionaries/hunspell"));
+    searched.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("dictionaries/hunspell"));
+    searched.extend(SYSTEM_DIRS.iter().map(PathBuf::from));
+    if let Some(home) = dirs::home_dir() {
+        searched.push(home.join("Library/Spelling"));
+    }
+    searched
+}
+
+/// Paths of the .aff and .dic files `settings` point to
+pub(crate) fn find_dictionary(settings: &SpellingSettings) -> Result<(PathBuf, PathBuf)> {
+    if let Some(dic) = &settings.dictionary {
+        let aff = dic.with_extension("aff");
+        if dic.is_file() && aff.is_file() {
+            return Ok((aff, dic.clone()));
+        }
+        return Err(AnalysisError::DictionaryError(format!(
+            "{} and {} must both exist",
+            dic.display(),
+            aff.display()
+        )));
+    }
+
+    let searched = dictionary_dirs(settings);
+    for dir in &searched {
+        let dic = dir.join(format!("{}.dic", settings.language));
+        let aff = dic.with_extension("aff");
+        if dic.is_file() && aff.is_file() {
+            return Ok((aff, dic));
+        }
+    }
+    let searched: Vec<String> = searched.iter().map(|d| d.display().to_string()).collect();
+    Err(AnalysisError::DictionaryError(format!(
+        "no Hunspell dictionary for {} in {}",
+        settings.language,
+        searched.join(", ")
+    )))
+}
+
+/// Reports words the spell checker does not accept, with its suggestions as fixes
+pub struct SpellingRule {
+    checker: SpellChecker,
+}
+
+impl SpellingRule {
+    pub fn new(checker: SpellChecker) -> Self {
+        Self { checker }
+    }
+}
+
+impl Rule for SpellingRule {
+    fn id(&self) -> &str {
+        "spelling"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Spelling
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, ctx: &SentenceContext) -> Vec<GrammarIssue> {
+        self.checker
+            .check(ctx.sentence)
+            .into_iter()
+            .map(|m| {
+                let message = format!("Possible misspelling \"{}\"", m.word);
+                let mut issue = ctx.issue(self, GrammarIssueType::Spelling, message, m.range.clone());
+                issue.fixes = m
+                    .suggestions
+                    .iter()
+                    .map(|s| ctx.fix(format!("Replace with \"{}\"", s), m.range.clone(), s, Applicability::MaybeIncorrect))
+                    .collect();
+                issue
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{GrammarChecker, RuleRegistry};
+    use crate::config::RuleSettings;
+
+    const AFF: &str = "SET UTF-8\n\nSFX S Y 2\nSFX S 0 s [^s]\nSFX S 0 es s\n";
+    const DIC: &str = "9\nthe\nteam\nreleased\nnew\nversion/S\nread\nat\nnotes\ncan't\n";
+
+    fn checker() -> SpellChecker {
+        SpellChecker::new(Arc::new(Dictionary::parse(AFF, DIC).unwrap()))
+    }
+
+    #[test]
+    fn test_skips_urls_emails_code_and_acronyms() {
+        let text = "The NASA team relesed `cargo_fmt` v2beta at https://exmaple.org/notse, read notse@exmaple.org";
+        let words: Vec<String> = checker().check(text).into_iter().map(|m| m.word).collect();
+        assert_eq!(words, vec!["relesed"]);
+    }
+
+    #[test]
+    fn test_extra_words_and_contractions() {
+        let checker = checker().with_words(["Rustacean"]);
+        assert!(checker.check("The rustacean can’t read the versions").is_empty());
+    }
+
+    #[test]
+    fn test_misspellings_become_issues_with_fixes() {
+        let mut registry = RuleRegistry::default();
+        registry.register(SpellingRule::new(checker()));
+        let grammar = GrammarChecker::with_rules(&registry, &RuleSettings::default());
+
+        let sentence = "The team relesed new verions.".to_string();
+        let issues: Vec<GrammarIssue> = grammar
+            .check(std::slice::from_ref(&sentence))
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.rule_id == "spelling")
+            .collect();
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(&sentence[issues[0].span.byte_range()], "relesed");
+        assert_eq!(issues[0].fixes[0].edits[0].replacement, "released");
+        assert_eq!(issues[1].fixes[0].edits[0].replacement, "versions");
+    }
+
+    #[test]
+    fn test_missing_dictionary_is_an_error() {
+        let settings = SpellingSettings {
+            enabled: true,
+            language: "xx_NONE".to_string(),
+            ..SpellingSettings::default()
+        };
+        assert!(matches!(SpellChecker::from_settings(&settings), Err(AnalysisError::DictionaryError(_))));
+    }
+}
@@ -0,0 +1,440 @@
+// Suppression directives
+// Comments like `<!-- grammar-disable passive-voice -->` that silence findings
+// in part of a document or in all of it
+
+use crate::analysis_reports::*;
+use crate::fix::Fix;
+use crate::grammar::{GrammarIssue, PassiveVoiceMatch};
+use crate::span::Span;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+
+lazy_static! {
+    /// `<!-- grammar-<kind> [rule ...] -->`, with rule IDs separated by spaces or commas
+    static ref DIRECTIVE: Regex = Regex::new(
+        r"<!--\s*grammar-(disable-next-line|disable-line|disable-file|disable|enable)((?:\s[^>]*?)?)\s*-->"
+    ).unwrap();
+}
+
+// IDs of report findings, usable in directives alongside grammar rule IDs
+pub const PASSIVE_VOICE: &str = "passive-voice";
+pub const STICKY_SENTENCE: &str = "sticky-sentence";
+pub const OVERUSED_WORD: &str = "overused-word";
+pub const REPEATED_PHRASE: &str = "repeated-phrase";
+pub const ECHO: &str = "echo";
+pub const VAGUE_WORD: &str = "vague-word";
+pub const CLICHE: &str = "cliche";
+pub const BUSINESS_JARGON: &str = "business-jargon";
+pub const COMPLEX_PARAGRAPH: &str = "complex-paragraph";
+pub const HIDDEN_VERB: &str = "hidden-verb";
+
+/// What a directive silences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirectiveKind {
+    /// Everything after it, up to a matching `grammar-enable`
+    Disable,
+    /// Ends earlier `grammar-disable` regions: those of the named rules, or all of them
+    Enable,
+    /// The line holding the comment
+    DisableLine,
+    /// The next line with text on it
+    DisableNextLine,
+    /// The whole document, wherever the comment is
+    DisableFile,
+}
+
+impl DirectiveKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DirectiveKind::Disable => "disable",
+            DirectiveKind::Enable => "enable",
+            DirectiveKind::DisableLine => "disable-line",
+            DirectiveKind::DisableNextLine => "disable-next-line",
+            DirectiveKind::DisableFile => "disable-file",
+        }
+    }
+
+    fn parse(name: &str) -> Self {
+        match name {
+            "enable" => DirectiveKind::Enable,
+            "disable-line" => DirectiveKind::DisableLine,
+            "disable-next-line" => DirectiveKind::DisableNextLine,
+            "disable-file" => DirectiveKind::DisableFile,
+            _ => DirectiveKind::Disable,
+        }
+    }
+}
+
+/// A suppression comment found in a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// Rule IDs the directive names; empty for every rule
+    pub rules: Vec<String>,
+    /// 1-based line of the comment
+    pub line: usize,
+    /// Location of the comment in the source
+    pub span: Span,
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "grammar-{}", self.kind.as_str())?;
+        for rule in &self.rules {
+            write!(f, " {}", rule)?;
+        }
+        Ok(())
+    }
+}
+
+/// Part of the source where one rule, or every rule, is silenced by a directive
+#[derive(Debug, Clone, PartialEq)]
+struct Region {
+    /// Index of the directive in `Suppressions::directives`
+    directive: usize,
+    /// Rule silenced; `None` for every rule
+    rule: Option<String>,
+    range: Range<usize>,
+}
+
+impl Region {
+    fn covers(&self, rule: &str, offset: usize) -> bool {
+        self.range.contains(&offset) && self.rule.as_deref().map_or(true, |r| r == rule)
+    }
+}
+
+/// The suppression directives of a document and the regions they silence
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Suppressions {
+    directives: Vec<Directive>,
+    regions: Vec<Region>,
+}
+
+impl Suppressions {
+    /// Directives in `source`, the document as written (plain text, Markdown or HTML)
+    pub fn parse(source: &str) -> Self {
+        let line_starts: Vec<usize> =
+            std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_range = |line: usize| line_starts[line]..line_starts.get(line + 1).copied().unwrap_or(source.len());
+        // Lines holding nothing but directives do not count as the next line
+        let blanked = blank_directives(source);
+
+        let mut suppressions = Self::default();
+        let mut open: Vec<Region> = Vec::new();
+        for captures in DIRECTIVE.captures_iter(source) {
+            let comment = captures.get(0).unwrap().range();
+            let kind = DirectiveKind::parse(&captures[1]);
+            let rules: Vec<String> = captures[2]
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|rule| !rule.is_empty())
+                .map(str::to_string)
+                .collect();
+            let directive = suppressions.directives.len();
+            let line = line_of(comment.start);
+
+            let covered = match kind {
+                DirectiveKind::Disable => Some(comment.end..source.len()),
+                DirectiveKind::Enable => None,
+                DirectiveKind::DisableLine => Some(line_range(line)),
+                DirectiveKind::DisableNextLine => (line_of(comment.end) + 1..line_starts.len())
+                    .map(line_range)
+                    .find(|range| !blanked[range.clone()].trim().is_empty()),
+                DirectiveKind::DisableFile => Some(0..source.len()),
+            };
+            let targets: Vec<Option<String>> =
+                if rules.is_empty() { vec![None] } else { rules.iter().cloned().map(Some).collect() };
+
+            if kind == DirectiveKind::Enable {
+                let (closed, still_open): (Vec<Region>, Vec<Region>) = open
+                    .drain(..)
+                    .partition(|r| rules.is_empty() || r.rule.as_ref().is_some_and(|rule| rules.contains(rule)));
+                open = still_open;
+                suppressions.regions.extend(closed.into_iter().map(|r| Region {
+                    range: r.range.start..comment.start,
+                    ..r
+                }));
+            } else if let Some(range) = covered {
+                let regions = targets.into_iter().map(|rule| Region { directive, rule, range: range.clone() });
+                if kind == DirectiveKind::Disable {
+                    open.extend(regions);
+                } else {
+                    suppressions.regions.extend(regions);
+                }
+            }
+
+            suppressions.directives.push(Directive {
+                kind,
+                rules,
+                line: line + 1,
+                span: Span::in_text(source, comment.start, comment.end),
+            });
+        }
+        suppressions.regions.extend(open);
+        suppressions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    /// Whether a finding of `rule` starting at byte `offset` of the source is silenced
+    pub fn suppresses(&self, rule: &str, offset: usize) -> bool {
+        self.regions.iter().any(|r| r.covers(rule, offset))
+    }
+
+    /// Whether applying `fix` leaves every directive comment intact
+    pub fn keeps_directives(&self, fix: &Fix) -> bool {
+        fix.edits.iter().all(|edit| {
+            self.directives
+                .iter()
+                .all(|d| edit.span.byte_end <= d.span.byte_start || d.span.byte_end <= edit.span.byte_start)
+        })
+    }
+
+    /// Directives that silence none of `findings`, given as rule ID and source offset
+    ///
+    /// `grammar-enable` directives silence nothing themselves and are never reported.
+    pub fn unused(&self, findings: &[(&str, usize)]) -> Vec<&Directive> {
+        self.directives
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.kind != DirectiveKind::Enable)
+            .filter(|(i, _)| {
+                !self
+                    .regions
+                    .iter()
+                    .filter(|r| r.directive == *i)
+                    .any(|r| findings.iter().any(|&(rule, offset)| r.covers(rule, offset)))
+            })
+            .map(|(_, d)| d)
+            .collect()
+    }
+}
+
+/// Byte ranges of the directive comments in `text`
+pub(crate) fn directive_ranges(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    DIRECTIVE.find_iter(text).map(|comment| comment.range())
+}
+
+/// `text` with each directive comment replaced by spaces, keeping offsets and line breaks
+pub fn blank_directives(text: &str) -> String {
+    let mut blanked = String::with_capacity(text.len());
+    let mut last = 0;
+    for comment in DIRECTIVE.find_iter(text) {
+        blanked.push_str(&text[last..comment.start()]);
+        for c in comment.as_str().chars() {
+            if c == '\n' {
+                blanked.push('\n');
+            } else {
+                blanked.extend(std::iter::repeat(' ').take(c.len_utf8()));
+            }
+        }
+        last = comment.end();
+    }
+    blanked.push_str(&text[last..]);
+    blanked
+}
+
+/// Findings that suppression directives can silence
+///
+/// Offsets are those of the source document, so structured input must be
+/// mapped to the source first.
+pub trait Suppress {
+    /// Drop silenced findings, updating the counts derived from them
+    fn suppress(&mut self, suppressions: &Suppressions);
+
+    /// Rule ID and source offset of each finding
+    fn findings(&self) -> Vec<(&str, usize)>;
+}
+
+impl Suppress for Vec<GrammarIssue> {
+    fn suppress(&mut self, suppressions: &Suppressions) {
+        self.retain(|issue| !suppressions.suppresses(&issue.rule_id, issue.span.byte_start));
+    }
+
+    fn findings(&self) -> Vec<(&str, usize)> {
+        self.iter().map(|issue| (issue.rule_id.as_str(), issue.span.byte_start)).collect()
+    }
+}
+
+impl Suppress for Vec<PassiveVoiceMatch> {
+    fn suppress(&mut self, suppressions: &Suppressions) {
+        self.retain(|m| !suppressions.suppresses(PASSIVE_VOICE, m.span.byte_start));
+    }
+
+    fn findings(&self) -> Vec<(&str, usize)> {
+        self.iter().map(|m| (PASSIVE_VOICE, m.span.byte_start)).collect()
+    }
+}
+
+/// Drop the silenced occurrences of a `rule` finding, returning how many are left
+fn retain_occurrences(occurrences: &mut Vec<WordOccurrence>, rule: &str, suppressions: &Suppressions) -> usize {
+    occurrences.retain(|o| !suppressions.suppresses(rule, o.span.byte_start));
+    occurrences.len()
+}
+
+fn occurrence_findings<'a>(rule: &'static str, occurrences: &'a [WordOccurrence]) -> impl Iterator<Item = (&'static str, usize)> + 'a {
+    occurrences.iter().map(move |o| (rule, o.span.byte_start))
+}
+
+impl Suppress for FullAnalysisReport {
+    fn suppress(&mut self, suppressions: &Suppressions) {
+        if suppressions.is_empty() {
+            return;
+        }
+        let sticky = &mut self.sticky_sentences;
+        sticky.sticky_sentences.retain(|s| !suppressions.suppresses(STICKY_SENTENCE, s.span.byte_start));
+        sticky.semi_sticky_sentences.retain(|s| !suppressions.suppresses(STICKY_SENTENCE, s.span.byte_start));
+        sticky.sticky_sentence_count = sticky.sticky_sentences.len();
+
+        self.style.hidden_verbs.retain(|h| !suppressions.suppresses(HIDDEN_VERB, h.span.byte_start));
+
+        let word_count = self.word_count.max(1) as f64;
+        self.overused_words.overused_words.retain_mut(|word| {
+            word.count = retain_occurrences(&mut word.occurrences, OVERUSED_WORD, suppressions);
+            word.frequency = (word.count as f64 / word_count * 10000.0).round() / 100.0;
+            word.count > 0
+        });
+
+        let repeated = &mut self.repeated_phrases;
+        let listed = repeated.most_repeated.len();
+        repeated.most_repeated.retain_mut(|phrase| {
+            phrase.count = retain_occurrences(&mut phrase.occurrences, REPEATED_PHRASE, suppressions);
+            phrase.count > 1
+        });
+        repeated.total_repeated_phrases -= listed - repeated.most_repeated.len();
+
+        if let Some(echoes) = &mut self.echoes {
+            let listed = echoes.echoes.len();
+            echoes.echoes.retain_mut(|echo| {
+                echo.occurrences = retain_occurrences(&mut echo.positions, ECHO, suppressions);
+                echo.occurrences > 1
+            });
+            echoes.total_echoes -= listed - echoes.echoes.len();
+        }
+
+        let diction = &mut self.diction;
+        diction.most_common_vague.retain_mut(|vague| {
+            vague.count = retain_occurrences(&mut vague.occurrences, VAGUE_WORD, suppressions);
+            vague.count > 0
+        });
+        diction.total_vague_words = diction.most_common_vague.iter().map(|v| v.count).sum();
+        diction.unique_vague_words = diction.most_common_vague.len();
+
+        if let Some(cliches) = &mut self.cliches {
+            cliches.cliches.retain_mut(|cliche| {
+                cliche.count = retain_occurrences(&mut cliche.occurrences, CLICHE, suppressions);
+                cliche.count > 0
+            });
+            cliches.total_cliches = cliches.cliches.len();
+        }
+
+        if let Some(jargon) = &mut self.business_jargon {
+            jargon.jargon_list.retain_mut(|found| {
+                found.count = retain_occurrences(&mut found.occurrences, BUSINESS_JARGON, suppressions);
+                found.count > 0
+            });
+            jargon.total_jargon = jargon.jargon_list.iter().map(|j| j.count).sum();
+            jargon.unique_jargon_phrases = jargon.jargon_list.len();
+        }
+
+        let complex = &mut self.complex_paragraphs;
+        complex.complex_paragraphs.retain(|p| !suppressions.suppresses(COMPLEX_PARAGRAPH, p.span.byte_start));
+        complex.complex_paragraph_count = complex.complex_paragraphs.len();
+        if self.paragraph_count > 0 {
+            let percentage = complex.complex_paragraph_count as f64 / self.paragraph_count as f64 * 100.0;
+            complex.percentage = (percentage * 10.0).round() / 10.0;
+        }
+    }
+
+    fn findings(&self) -> Vec<(&str, usize)> {
+        let sticky = &self.sticky_sentences;
+        let mut findings: Vec<(&str, usize)> = sticky
+            .sticky_sentences
+            .iter()
+            .chain(&sticky.semi_sticky_sentences)
+            .map(|s| (STICKY_SENTENCE, s.span.byte_start))
+            .collect();
+        for word in &self.overused_words.overused_words {
+            findings.extend(occurrence_findings(OVERUSED_WORD, &word.occurrences));
+        }
+        for phrase in &self.repeated_phrases.most_repeated {
+            findings.extend(occurrence_findings(REPEATED_PHRASE, &phrase.occurrences));
+        }
+        for echo in self.echoes.iter().flat_map(|r| &r.echoes) {
+            findings.extend(occurrence_findings(ECHO, &echo.positions));
+        }
+        for vague in &self.diction.most_common_vague {
+            findings.extend(occurrence_findings(VAGUE_WORD, &vague.occurrences));
+        }
+        for cliche in self.cliches.iter().flat_map(|r| &r.cliches) {
+            findings.extend(occurrence_findings(CLICHE, &cliche.occurrences));
+        }
+        for jargon in self.business_jargon.iter().flat_map(|r| &r.jargon_list) {
+            findings.extend(occurrence_findings(BUSINESS_JARGON, &jargon.occurrences));
+        }
+        findings.extend(self.complex_paragraphs.complex_paragraphs.iter().map(|p| (COMPLEX_PARAGRAPH, p.span.byte_start)));
+        findings.extend(self.style.hidden_verbs.iter().map(|h| (HIDDEN_VERB, h.span.byte_start)));
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions_by_directive_kind() {
+        let source = "One.\n<!-- grammar-disable passive-voice, cliche -->\nTwo.\n<!-- grammar-enable cliche -->\nThree.\n\n<!-- grammar-disable-next-line -->\n\nFour. <!-- grammar-disable-line echo -->\nFive.";
+        let suppressions = Suppressions::parse(source);
+        let at = |text: &str| source.find(text).unwrap();
+
+        assert_eq!(suppressions.directives().len(), 4);
+        assert_eq!(suppressions.directives()[0].to_string(), "grammar-disable passive-voice cliche");
+        assert_eq!(suppressions.directives()[2].line, 7);
+
+        assert!(!suppressions.suppresses("passive-voice", at("One")));
+        assert!(suppressions.suppresses("passive-voice", at("Two")));
+        assert!(suppressions.suppresses("cliche", at("Two")));
+        assert!(!suppressions.suppresses("cliche", at("Three")));
+        assert!(suppressions.suppresses("passive-voice", at("Five")));
+        // The next line is the next one with text, and the line directive covers its own line
+        assert!(suppressions.suppresses("repeated-word", at("Four")));
+        assert!(suppressions.suppresses("echo", at("Four")));
+        assert!(!suppressions.suppresses("echo", at("Five")));
+    }
+
+    #[test]
+    fn test_file_directive_and_unused() {
+        let source = "Intro.\n<!-- grammar-disable-next-line cliche -->\nBody text.\n<!-- grammar-disable-file double-space -->";
+        let suppressions = Suppressions::parse(source);
+        let body = source.find("Body").unwrap();
+
+        assert!(suppressions.suppresses("double-space", 0));
+        let unused: Vec<String> = suppressions
+            .unused(&[("double-space", body)])
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(unused, vec!["grammar-disable-next-line cliche"]);
+    }
+
+    #[test]
+    fn test_blanking_keeps_offsets() {
+        let text = "Before.\n<!-- grammar-disable -->\nAfter.";
+        let blanked = blank_directives(text);
+
+        assert_eq!(blanked.len(), text.len());
+        assert_eq!(blanked.find("After"), text.find("After"));
+        assert_eq!(blanked.lines().nth(1).unwrap().trim(), "");
+    }
+}
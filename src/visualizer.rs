@@ -1,6 +1,7 @@
 use crate::analysis_reports::*;
-use crate::grammar::{GrammarIssue, PassiveVoiceMatch};
-use crate::{TextStatistics, ReadabilityMetrics};
+use crate::grammar::{GrammarIssue, PassiveVoiceMatch, SentenceSpan};
+use crate::readability::{Difficulty, PassageReadability};
+use crate::{TextStatistics, ReadabilityBreakdown, ReadabilityMetrics};
 use std::collections::HashMap;
 
 /// HTML Visualizer - Highlights text issues with colors
@@ -15,7 +16,7 @@ impl HtmlVisualizer {
     /// Generate complete HTML visualization
     pub fn generate(
         text: &str,
-        sentences: &[String],
+        sentences: &[SentenceSpan],
         stats: &TextStatistics,
         readability: &ReadabilityMetrics,
         grammar: &[GrammarIssue],
@@ -62,10 +63,79 @@ impl HtmlVisualizer {
         viz.build_html(text, sentences, stats, readability, highlights, full_report)
     }
     
+    /// Generate an HTML heatmap of how hard each sentence is to read
+    ///
+    /// Sentences are shaded by difficulty within their paragraphs, and the
+    /// hard ones are listed below the text, hardest first.
+    pub fn generate_heatmap(
+        stats: &TextStatistics,
+        readability: &ReadabilityMetrics,
+        breakdown: &ReadabilityBreakdown,
+    ) -> String {
+        let viz = Self::new();
+        let mut html = String::new();
+
+        html.push_str(&viz.html_header());
+        html.push_str(&viz.summary_panel(stats, readability, None));
+
+        html.push_str("<div class='text-container'>\n");
+        html.push_str("<h2>🌡️ Readability Heatmap</h2>\n");
+        for paragraph in &breakdown.paragraphs {
+            html.push_str(&format!(
+                "<p class='heatmap-paragraph' title='Paragraph {}: grade {:.1}'>\n",
+                paragraph.index + 1,
+                paragraph.grade_level
+            ));
+            // Sentences belong to the paragraph whose span they start in
+            let range = paragraph.span.byte_range();
+            for sentence in breakdown.sentences.iter().filter(|s| range.contains(&s.span.byte_start)) {
+                html.push_str(&viz.heatmap_sentence(sentence));
+            }
+            html.push_str("</p>\n");
+        }
+        html.push_str("</div>\n");
+
+        html.push_str("<div class='text-container'>\n");
+        html.push_str("<h2>🔥 Hardest Sentences</h2>\n");
+        if breakdown.hardest_sentences.is_empty() {
+            html.push_str("<p>No sentence is hard to read.</p>\n");
+        } else {
+            html.push_str("<ol class='hardest'>\n");
+            for sentence in breakdown.hardest() {
+                html.push_str(&format!(
+                    "<li><span class='{}'>{}</span> <small>grade {:.1}, {} words</small></li>\n",
+                    difficulty_class(sentence.difficulty),
+                    escape(&sentence.text),
+                    sentence.grade_level,
+                    sentence.word_count
+                ));
+            }
+            html.push_str("</ol>\n");
+        }
+        html.push_str("</div>\n");
+
+        html.push_str(&viz.heatmap_legend());
+        html.push_str(&viz.html_footer());
+        html
+    }
+
+    fn heatmap_sentence(&self, sentence: &PassageReadability) -> String {
+        format!(
+            "<span class='sentence {}' data-sentence='{}' title='Grade {:.1} · Reading ease {:.1} · {} words, {} syllables'>{}</span> ",
+            difficulty_class(sentence.difficulty),
+            sentence.index + 1,
+            sentence.grade_level,
+            sentence.flesch_reading_ease,
+            sentence.word_count,
+            sentence.syllable_count,
+            escape(&sentence.text)
+        )
+    }
+
     fn build_html(
         &mut self,
         _text: &str,
-        sentences: &[String],
+        sentences: &[SentenceSpan],
         stats: &TextStatistics,
         readability: &ReadabilityMetrics,
         highlights: HashMap<usize, Vec<HighlightType>>,
@@ -106,7 +176,7 @@ impl HtmlVisualizer {
             }
             
             // Highlight individual words
-            sentence_html.push_str(&self.highlight_words(sentence, sentence_num, full_report));
+            sentence_html.push_str(&self.highlight_words(&sentence.text, sentence_num, full_report));
             sentence_html.push_str("</span> ");
             
             html.push_str(&sentence_html);
@@ -285,6 +355,34 @@ impl HtmlVisualizer {
             padding-left: 8px;
         }
         
+        /* Heatmap Colors */
+        .readable {
+            background: rgba(46, 204, 113, 0.15);
+            border-radius: 3px;
+        }
+
+        .hard {
+            background: rgba(241, 196, 15, 0.35);
+            border-radius: 3px;
+        }
+
+        .very-hard {
+            background: rgba(231, 76, 60, 0.35);
+            border-radius: 3px;
+        }
+
+        .heatmap-paragraph {
+            margin-bottom: 1em;
+        }
+
+        .hardest li {
+            margin: 0 0 10px 20px;
+        }
+
+        .hardest small {
+            color: #7f8c8d;
+        }
+
         .legend {
             display: grid;
             grid-template-columns: repeat(auto-fit, minmax(200px, 1fr));
@@ -413,7 +511,11 @@ impl HtmlVisualizer {
                 <h3>Readability</h3>
                 <div class="value">{:.1}</div>
             </div>
-        "#, stats.word_count, stats.sentence_count, stats.paragraph_count, readability.flesch_reading_ease));
+            <div class="summary-card">
+                <h3>Grade Level</h3>
+                <div class="value">{:.1}</div>
+            </div>
+        "#, stats.word_count, stats.sentence_count, stats.paragraph_count, readability.flesch_reading_ease, readability.consensus_grade));
         
         if let Some(report) = full_report {
             html.push_str(&format!(r#"
@@ -473,6 +575,26 @@ impl HtmlVisualizer {
         "#.to_string()
     }
     
+    fn heatmap_legend(&self) -> String {
+        r#"
+        <div class="legend">
+            <h3>🎨 Heatmap Legend</h3>
+            <div class="legend-item">
+                <div class="legend-color" style="background: rgba(46, 204, 113, 0.25);"></div>
+                <div class="legend-text"><strong>Readable</strong> - Below the hard grade level</div>
+            </div>
+            <div class="legend-item">
+                <div class="legend-color" style="background: rgba(241, 196, 15, 0.45);"></div>
+                <div class="legend-text"><strong>Hard</strong> - Consider shortening or simplifying</div>
+            </div>
+            <div class="legend-item">
+                <div class="legend-color" style="background: rgba(231, 76, 60, 0.45);"></div>
+                <div class="legend-text"><strong>Very Hard</strong> - Split it or use simpler words</div>
+            </div>
+        </div>
+        "#.to_string()
+    }
+
     fn html_footer(&self) -> String {
         r#"
     </div>
@@ -491,6 +613,20 @@ impl HtmlVisualizer {
     }
 }
 
+/// CSS class shading a sentence of the heatmap
+fn difficulty_class(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Readable => "readable",
+        Difficulty::Hard => "hard",
+        Difficulty::VeryHard => "very-hard",
+    }
+}
+
+/// Escape text for use in HTML content
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
 #[derive(Debug, Clone)]
 enum HighlightType {
     PassiveVoice(String),
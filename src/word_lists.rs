@@ -128,25 +128,6 @@ lazy_static! {
         map
     };
 
-    pub static ref HIDDEN_VERBS: HashMap<&'static str, &'static str> = {
-        let mut map = HashMap::new();
-        map.insert("decision", "decide");
-        map.insert("conclusion", "conclude");
-        map.insert("assumption", "assume");
-        map.insert("observation", "observe");
-        map.insert("consideration", "consider");
-        map.insert("implementation", "implement");
-        map.insert("investigation", "investigate");
-        map.insert("examination", "examine");
-        map.insert("explanation", "explain");
-        map.insert("discussion", "discuss");
-        map.insert("analysis", "analyze");
-        map.insert("recommendation", "recommend");
-        map.insert("suggestion", "suggest");
-        map.insert("description", "describe");
-        map
-    };
-
     pub static ref CONJUNCTIONS: HashSet<&'static str> = {
         ["and", "but", "or", "so", "yet", "for", "nor"].iter().copied().collect()
     };
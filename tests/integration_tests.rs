@@ -232,3 +232,62 @@ fn test_multiple_paragraphs() {
     assert_eq!(stats.paragraph_count, 3, "Should detect 3 paragraphs");
     assert_eq!(stats.sentence_count, 3, "Should detect 3 sentences");
 }
+
+#[test]
+fn test_parallel_report_matches_sequential() {
+    let paragraph = "The committee reviewed the proposal and the committee approved it. \
+        It was the best of the options that were on the table at the time. \
+        Organizational considerations necessitated comprehensive institutional restructuring, \
+        notwithstanding substantial interdepartmental disagreements regarding implementation.";
+    let text = vec![paragraph; 20].join("\n\n");
+
+    let mut sequential = Config::default();
+    sequential.analysis.parallel_processing = false;
+    let mut parallel = Config::default();
+    parallel.analysis.parallel_processing = true;
+
+    let a = TextAnalyzer::new(text.clone(), sequential).unwrap().generate_full_report().unwrap();
+    let b = TextAnalyzer::new(text, parallel).unwrap().generate_full_report().unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&a.sticky_sentences).unwrap(),
+        serde_json::to_string(&b.sticky_sentences).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&a.echoes).unwrap(),
+        serde_json::to_string(&b.echoes).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&a.complex_paragraphs).unwrap(),
+        serde_json::to_string(&b.complex_paragraphs).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&a.overused_words).unwrap(),
+        serde_json::to_string(&b.overused_words).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&a.repeated_phrases).unwrap(),
+        serde_json::to_string(&b.repeated_phrases).unwrap()
+    );
+    assert_eq!(a.style_score, b.style_score);
+}
+
+#[test]
+fn test_disabled_passes_are_absent_from_full_report() {
+    let text = "At the end of the day, we need to leverage our synergy. \
+        The cat sat on the mat while the other cat watched the mat.".to_string();
+
+    let mut config = Config::default();
+    config.features.cliche_detection = false;
+    config.features.jargon_detection = false;
+
+    let report = TextAnalyzer::new(text, config).unwrap().generate_full_report().unwrap();
+    assert!(report.cliches.is_none());
+    assert!(report.business_jargon.is_none());
+    assert!(report.echoes.is_some());
+    assert_eq!(report.skipped_passes, vec!["cliches", "business_jargon"]);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["cliches"].is_null());
+    assert!(json["echoes"].is_object());
+}